@@ -0,0 +1,286 @@
+use crate::compression::write_vsi_file;
+use crate::driver::open_dataset_for_read;
+use crate::{
+    _last_null_pointer_err, check_in_memory_read, layer_from_params, read_layer, write_mem_files,
+    Error, ReadParams, ToGdalDriver, WriteParams,
+};
+use polars::prelude::*;
+use std::ffi::CString;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Translate an `archive.zip!inner/file.shp` style path into the equivalent GDAL `/vsizip/` path.
+///
+/// Nested archives are supported by chaining `!` separators, eg `outer.zip!inner.zip!file.shp`.
+/// Archives fetched over http(s) are read through `/vsicurl/`. SOZip-optimized archives are handled transparently by GDAL.
+///
+/// Paths that don't use the `!` syntax are returned unchanged.
+///
+/// # Example
+/// ```
+/// use polars_gdal::vsi_path_from_archive_path;
+///
+/// assert_eq!(vsi_path_from_archive_path("data.zip!roads.shp"), "/vsizip/{data.zip}/roads.shp");
+/// assert_eq!(
+///     vsi_path_from_archive_path("outer.zip!inner.zip!roads.shp"),
+///     "/vsizip/{/vsizip/{outer.zip}/inner.zip}/roads.shp"
+/// );
+/// ```
+pub fn vsi_path_from_archive_path(path: &str) -> String {
+    let parts: Vec<&str> = path.split('!').collect();
+    if parts.len() < 2 || !parts[..parts.len() - 1].iter().all(|p| is_zip_archive(p)) {
+        return path.to_owned();
+    }
+
+    let mut vsi_path = parts[0].to_owned();
+    if vsi_path.starts_with("http://") || vsi_path.starts_with("https://") {
+        vsi_path = format!("/vsicurl/{}", vsi_path);
+    }
+    for member in &parts[1..] {
+        vsi_path = format!("/vsizip/{{{}}}/{}", vsi_path, member.trim_start_matches('/'));
+    }
+
+    vsi_path
+}
+
+/// List all members of a zip archive, recursively.
+///
+/// Nested archives can be listed using the same `!` syntax as [`vsi_path_from_archive_path`], eg `outer.zip!inner.zip`.
+/// Returned paths are relative to the root of the archive.
+pub fn list_archive_members(archive_path: &str) -> Result<Vec<String>, Error> {
+    let root = format!("/vsizip/{{{}}}", vsi_path_from_archive_path(archive_path));
+    let c_root = CString::new(root.as_bytes()).unwrap();
+
+    let c_list = unsafe { gdal_sys::VSIReadDirRecursive(c_root.as_ptr()) };
+    if c_list.is_null() {
+        return Err(Error::ArchiveNotReadable(archive_path.to_owned()));
+    }
+
+    let count = unsafe { gdal_sys::CSLCount(c_list) };
+    let members = (0..count)
+        .map(|i| crate::_string(unsafe { gdal_sys::CSLGetField(c_list, i) }))
+        .collect();
+    unsafe { gdal_sys::CSLDestroy(c_list) };
+
+    Ok(members)
+}
+
+/// Translate a path into a GDAL path like [`vsi_path_from_archive_path`], picking the vector file inside zip archives.
+///
+/// If the path is a zip archive (eg `data.zip`, or `outer.zip!inner.zip`), its members are listed and the single vector file
+/// inside (eg `roads.gpkg`, or `roads.shp` along with its sidecar files) is selected. Archives without a vector file fail with
+/// [`Error::NoVectorFileInArchive`].
+///
+/// Archives holding several shapefiles in the same directory resolve to that directory, which GDAL opens as a multi-layer
+/// dataset. Archives with several other vector files fail with [`Error::AmbiguousArchive`], in which case the file to read
+/// is given with the `archive.zip!inner.gpkg` syntax.
+///
+/// This is applied to every path read by this crate, where `ReadParams::layer_name` also selects the file of that name.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::resolve_archive_path;
+///
+/// assert_eq!(resolve_archive_path("roads.zip")?, "/vsizip/{roads.zip}/roads.shp");
+/// ```
+pub fn resolve_archive_path(path: &str) -> Result<String, Error> {
+    resolve_archive_layer_path(path, None)
+}
+
+/// Resolve a path to read with `params`, as with [`resolve_archive_path`].
+pub(crate) fn resolve_read_path(path: &str, params: &ReadParams) -> Result<String, Error> {
+    resolve_archive_layer_path(path, params.layer_name)
+}
+
+fn resolve_archive_layer_path(path: &str, layer_name: Option<&str>) -> Result<String, Error> {
+    if !is_zip_archive(path) {
+        return Ok(vsi_path_from_archive_path(path));
+    }
+
+    let members = list_archive_members(path)?;
+    let candidates: Vec<String> = members
+        .iter()
+        .filter(|member| is_vector_member(member))
+        .map(|member| member.trim_end_matches('/').to_owned())
+        .collect();
+    let vsi_archive_path = vsi_path_from_archive_path(path);
+    if let [member] = candidates.as_slice() {
+        return Ok(format!("/vsizip/{{{}}}/{}", vsi_archive_path, member));
+    }
+    if candidates.is_empty() {
+        return Err(Error::NoVectorFileInArchive(path.to_owned()));
+    }
+
+    // Several files, so pick the one named after the layer
+    let named = candidates.iter().find(|member| {
+        layer_name.map_or(false, |layer_name| {
+            Path::new(member)
+                .file_stem()
+                .map_or(false, |stem| stem == layer_name)
+        })
+    });
+    if let Some(member) = named {
+        return Ok(format!("/vsizip/{{{}}}/{}", vsi_archive_path, member));
+    }
+
+    // The Shapefile driver opens a directory of shapefiles as one dataset with a layer per file
+    let parents: Vec<&str> = candidates
+        .iter()
+        .map(|member| member.rsplit_once('/').map_or("", |(parent, _)| parent))
+        .collect();
+    let all_shapefiles = candidates
+        .iter()
+        .all(|member| member.to_ascii_lowercase().ends_with(".shp"));
+    if all_shapefiles && parents.iter().all(|parent| *parent == parents[0]) {
+        return Ok(match parents[0] {
+            "" => format!("/vsizip/{{{}}}", vsi_archive_path),
+            parent => format!("/vsizip/{{{}}}/{}", vsi_archive_path, parent),
+        });
+    }
+
+    Err(Error::AmbiguousArchive {
+        archive: path.to_owned(),
+        members: candidates,
+    })
+}
+
+/// Read a zip archive held in memory (eg an upload) into a DataFrame.
+///
+/// The file to read is given by `inner_path` (eg `roads.gpkg`), or else picked as with [`resolve_archive_path`].
+///
+/// # Example
+/// ```rust # ignore
+/// let df = polars_gdal::df_from_zip_bytes(&upload, None, None)?;
+/// ```
+pub fn df_from_zip_bytes(
+    data: &[u8],
+    inner_path: Option<&str>,
+    params: Option<ReadParams>,
+) -> Result<DataFrame, Error> {
+    let params = params.unwrap_or_default();
+    if data.is_empty() {
+        return Err(Error::EmptyData);
+    }
+    check_in_memory_read(data.len(), &params)?;
+
+    static DF_FROM_ZIP_BYTES_MEM_FILE_INCREMENTOR: AtomicU64 = AtomicU64::new(0);
+    let mem_path = format!(
+        "/vsimem/polars_gdal/df_from_zip_bytes/{}/{}/archive.zip",
+        std::process::id(),
+        DF_FROM_ZIP_BYTES_MEM_FILE_INCREMENTOR.fetch_add(1, Ordering::SeqCst),
+    );
+
+    // SAFETY: GDAL doesn't take ownership of the buffer, and the file is opened read-only and unlinked before returning,
+    //         so the immutable &[u8] is never written to or used after this function returns.
+    let c_mem_path = CString::new(mem_path.as_bytes()).unwrap();
+    let handle = unsafe {
+        gdal_sys::VSIFileFromMemBuffer(
+            c_mem_path.as_ptr(),
+            data.as_ptr() as *mut u8,
+            data.len() as u64,
+            false as i32,
+        )
+    };
+    if handle.is_null() {
+        return Err(_last_null_pointer_err("VSIFileFromMemBuffer").into());
+    }
+    unsafe { gdal_sys::VSIFCloseL(handle) };
+
+    let archive_path = match inner_path {
+        Some(inner_path) => format!("{}!{}", mem_path, inner_path),
+        None => mem_path,
+    };
+    let df = resolve_read_path(&archive_path, &params).and_then(|path| {
+        let dataset = open_dataset_for_read(&path, &params)?;
+        let mut layer = layer_from_params(&dataset, &params)?;
+        read_layer(&mut layer, Some(&dataset), params, None)
+    });
+
+    unsafe { gdal_sys::VSIUnlink(c_mem_path.as_ptr()) };
+
+    df
+}
+
+/// Write a DataFrame into a new zip archive, given as `archive.zip!inner.gpkg` or `archive.zip`.
+///
+/// Without an inner path, the files are named after `WriteParams::layer_name` (or `layer`), as with [`gdal_files_from_df`](crate::gdal_files_from_df).
+/// Every file written by the driver (eg the `.shp`, `.shx`, `.dbf` and `.prj` files of a Shapefile) is added to the archive,
+/// which replaces any existing file. `WriteParams::compression` is ignored.
+///
+/// # Example
+/// ```rust # ignore
+/// polars_gdal::gdal_zip_from_df(&df, &VectorDriver::Shapefile, "exports/roads.zip!roads.shp", None)?;
+/// ```
+pub fn gdal_zip_from_df<D: ToGdalDriver + ?Sized>(
+    df: &DataFrame,
+    driver: &D,
+    archive_path: &str,
+    params: Option<WriteParams>,
+) -> Result<(), Error> {
+    let driver = driver.to_gdal_driver()?;
+    let params = params.unwrap_or_default();
+    let (archive_path, inner_path) = match archive_path.rsplit_once('!') {
+        Some((archive_path, inner_path)) => {
+            (archive_path, Some(inner_path.trim_start_matches('/')))
+        }
+        None => (archive_path, None),
+    };
+    let inner_path = inner_path.map(Path::new);
+    let inner_stem = inner_path
+        .and_then(Path::file_stem)
+        .map(|stem| stem.to_string_lossy().into_owned());
+
+    // Files are named after the layer, so an inner file name names the layer unless it's given
+    let params = WriteParams {
+        layer_name: params.layer_name.or(inner_stem.as_deref()),
+        ..params
+    };
+    let (files, _) = write_mem_files(df, &driver, params)?;
+
+    let inner_dir = inner_path
+        .and_then(Path::parent)
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .filter(|dir| !dir.is_empty());
+    let single_file = files.len() == 1;
+    let vsi_archive_path = vsi_path_from_archive_path(archive_path);
+    let c_archive_path = CString::new(vsi_archive_path.as_bytes()).unwrap();
+    unsafe { gdal_sys::VSIUnlink(c_archive_path.as_ptr()) };
+    for (name, data) in &files {
+        // A single file takes the inner file name, which may have another extension than the driver's
+        let name = match inner_path {
+            Some(inner_path) if single_file => inner_path.to_string_lossy().into_owned(),
+            _ => match &inner_dir {
+                Some(dir) => format!("{}/{}", dir, name),
+                None => name.clone(),
+            },
+        };
+        write_vsi_file(&format!("/vsizip/{{{}}}/{}", vsi_archive_path, name), data)?;
+    }
+
+    Ok(())
+}
+
+/// The extensions of the main file of vector datasets, as opposed to sidecar files (eg `.dbf` or `.prj`).
+const VECTOR_EXTENSIONS: &[&str] = &[
+    "shp", "gpkg", "geojson", "json", "geojsonl", "fgb", "kml", "gml", "gpx", "csv", "parquet",
+    "arrow", "sqlite", "tab", "mif", "gdb",
+];
+
+/// Whether an archive member is the main file of a vector dataset.
+fn is_vector_member(member: &str) -> bool {
+    // Skip macOS resource forks, and the files inside File Geodatabase directories
+    if member.starts_with("__MACOSX/") || (member.contains(".gdb/") && !member.ends_with(".gdb/")) {
+        return false;
+    }
+
+    Path::new(member.trim_end_matches('/'))
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            VECTOR_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        })
+}
+
+fn is_zip_archive(path: &str) -> bool {
+    path.to_ascii_lowercase().ends_with(".zip")
+}
@@ -0,0 +1,102 @@
+use crate::{Error, ReadParamsOwned, ToGdalDriver, WriteParamsOwned};
+use polars::prelude::DataFrame;
+use std::path::Path;
+
+/// Async version of [`df_from_resource`](crate::df_from_resource), reading on tokio's blocking thread pool.
+///
+/// Params are owned so they can be sent to the blocking thread, where the borrowed [`ReadParams`](crate::ReadParams) are rebuilt.
+///
+/// # Example
+/// ```rust # ignore
+/// let mut params = polars_gdal::ReadParamsOwned::default();
+/// params.layer_name = Some("parcels".to_string());
+/// let df = polars_gdal::df_from_resource_async("/vsis3/bucket/parcels.gpkg", Some(params)).await?;
+/// ```
+pub async fn df_from_resource_async<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParamsOwned>,
+) -> Result<DataFrame, Error> {
+    let path = path.as_ref().to_path_buf();
+    run_blocking(move || match params {
+        Some(params) => params.with_params(|params| crate::df_from_resource(&path, Some(params))),
+        None => crate::df_from_resource(&path, None),
+    })
+    .await
+}
+
+/// Async version of [`df_from_bytes`](crate::df_from_bytes), parsing on tokio's blocking thread pool.
+///
+/// The data is moved to the blocking thread, so it can be any owned buffer, such as a `Vec<u8>` or a `bytes::Bytes` request body.
+///
+/// # Example
+/// ```rust # ignore
+/// let body: bytes::Bytes = request.into_body().collect().await?.to_bytes();
+/// let df = polars_gdal::df_from_bytes_async(body, Some("upload.geojson"), None).await?;
+/// ```
+pub async fn df_from_bytes_async<B: AsRef<[u8]> + Send + 'static>(
+    data: B,
+    filename_hint: Option<&str>,
+    params: Option<ReadParamsOwned>,
+) -> Result<DataFrame, Error> {
+    let filename_hint = filename_hint.map(str::to_owned);
+    run_blocking(move || {
+        let data = data.as_ref();
+        let filename_hint = filename_hint.as_deref();
+        match params {
+            Some(params) => {
+                params.with_params(|params| crate::df_from_bytes(data, filename_hint, Some(params)))
+            }
+            None => crate::df_from_bytes(data, filename_hint, None),
+        }
+    })
+    .await
+}
+
+/// Async version of [`gdal_resource_from_df`](crate::gdal_resource_from_df), writing on tokio's blocking thread pool.
+///
+/// The dataset is closed once written, since it can't leave the blocking thread, so nothing is returned. The dataframe is moved
+/// to the blocking thread: clone it to keep using it, which is cheap since polars columns are reference counted.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{gdal_resource_from_df_async, VectorDriver};
+///
+/// gdal_resource_from_df_async(df.clone(), VectorDriver::Gpkg, "/some/path/roads.gpkg", None).await?;
+/// ```
+pub async fn gdal_resource_from_df_async<D, P>(
+    df: DataFrame,
+    driver: D,
+    path: P,
+    params: Option<WriteParamsOwned>,
+) -> Result<(), Error>
+where
+    D: ToGdalDriver + Send + 'static,
+    P: AsRef<Path>,
+{
+    let path = path.as_ref().to_path_buf();
+    run_blocking(move || {
+        match params {
+            Some(params) => params.with_params(|params| {
+                crate::gdal_resource_from_df(&df, &driver, &path, Some(params))
+            }),
+            None => crate::gdal_resource_from_df(&df, &driver, &path, None),
+        }?;
+        Ok(())
+    })
+    .await
+}
+
+/// Run `f` on tokio's blocking thread pool.
+///
+/// GDAL handles aren't `Send`, so they must be opened and dropped within `f`: only its result is sent back to the task.
+/// A panic in `f` is resumed in the task.
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, Error> + Send + 'static,
+) -> Result<T, Error> {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        // The runtime is shutting down
+        Err(_) => Err(Error::Cancelled),
+    }
+}
@@ -0,0 +1,210 @@
+use crate::driver::open_dataset;
+use crate::layer::field_as_binary;
+use crate::{resolve_read_path, Error, ReadParams};
+use gdal::vector::sql::Dialect;
+use gdal::vector::FieldValue as GdalValue;
+use gdal::vector::LayerAccess;
+use polars::prelude::*;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Names of the fields of an attachments table.
+struct AttachmentFields<'a> {
+    fid: &'a str,
+    name: Option<&'a str>,
+    content_type: &'a str,
+    data: &'a str,
+}
+
+/// Read the attachments (photos, documents, and other BLOBs) linked to the features of a layer.
+///
+/// Supported sources are ESRI File Geodatabase attachment tables (`<layer>__ATTACH`) and
+/// GeoPackage related media tables (the `media` relation of the GeoPackage Related Tables Extension).
+///
+/// The returned DataFrame has the columns `fid` (the feature id of the feature the attachment belongs to),
+/// `name`, `content_type`, and `data` (the attachment bytes as a Binary column).
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{attachments_from_resource, write_attachments_to_dir};
+///
+/// let attachments = attachments_from_resource("inspections.gdb", "poles", None)?;
+/// let paths = write_attachments_to_dir(&attachments, "/tmp/pole_photos")?;
+/// ```
+pub fn attachments_from_resource<P: AsRef<Path>>(
+    path: P,
+    layer_name: &str,
+    params: Option<ReadParams>,
+) -> Result<DataFrame, Error> {
+    let params = params.unwrap_or_default();
+    let gdal_options: gdal::DatasetOptions = (&params).into();
+
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset(&path, gdal_options)?;
+
+    // ESRI File Geodatabase attachment table
+    if let Ok(mut layer) = dataset.layer_by_name(&format!("{}__ATTACH", layer_name)) {
+        return attachments_from_layer(
+            &mut layer,
+            &AttachmentFields {
+                fid: "REL_OBJECTID",
+                name: Some("ATT_NAME"),
+                content_type: "CONTENT_TYPE",
+                data: "DATA",
+            },
+        );
+    }
+
+    // GeoPackage related media tables
+    let query = format!(
+        "SELECT mapping_table_name, related_table_name, related_primary_column FROM gpkgext_relations WHERE base_table_name = '{}' AND relation_name = 'media'",
+        layer_name.replace('\'', "''")
+    );
+    let relations: Vec<(String, String, String)> =
+        match dataset.execute_sql(&query, None, Dialect::DEFAULT) {
+            Ok(Some(mut result_set)) => result_set
+                .features()
+                .filter_map(|feature| {
+                    let mapping = feature.field("mapping_table_name").ok()??.into_string()?;
+                    let related = feature.field("related_table_name").ok()??.into_string()?;
+                    let related_pk = feature
+                        .field("related_primary_column")
+                        .ok()??
+                        .into_string()?;
+                    Some((mapping, related, related_pk))
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+    let mut attachments: Option<DataFrame> = None;
+    for (mapping, related, related_pk) in relations {
+        let mapping = mapping.replace('"', "\"\"");
+        let related = related.replace('"', "\"\"");
+        let related_pk = related_pk.replace('"', "\"\"");
+
+        // The media table may have a column naming its rows, which isn't required by the extension
+        let has_name = match dataset.execute_sql(
+            &format!(r#"SELECT * FROM "{}" LIMIT 0"#, related),
+            None,
+            Dialect::DEFAULT,
+        )? {
+            Some(result_set) => result_set
+                .defn()
+                .fields()
+                .any(|field| field.name().eq_ignore_ascii_case("name")),
+            None => false,
+        };
+        let name_column = if has_name {
+            r#", r."name" AS name"#
+        } else {
+            ""
+        };
+
+        let query = format!(
+            r#"SELECT m.base_id AS fid{}, r.content_type AS content_type, r.data AS data FROM "{}" m JOIN "{}" r ON r."{}" = m.related_id"#,
+            name_column, mapping, related, related_pk
+        );
+        let mut result_set = match dataset.execute_sql(&query, None, Dialect::DEFAULT)? {
+            Some(result_set) => result_set,
+            None => continue,
+        };
+        let df = attachments_from_layer(
+            &mut result_set,
+            &AttachmentFields {
+                fid: "fid",
+                name: has_name.then_some("name"),
+                content_type: "content_type",
+                data: "data",
+            },
+        )?;
+        attachments = Some(match attachments {
+            Some(existing) => existing.vstack(&df)?,
+            None => df,
+        });
+    }
+
+    attachments.ok_or_else(|| Error::NoAttachments(layer_name.to_owned()))
+}
+
+/// Write attachments read by [`attachments_from_resource`] to files in a directory, returning the paths written.
+///
+/// Files are named `<fid>_<name>`, falling back to `<fid>_<row-index>` for attachments without a name. When a feature has
+/// several attachments of the same name (eg `IMG_0001.jpg` photos), the later ones are named `<fid>_<row-index>_<name>`.
+pub fn write_attachments_to_dir<P: AsRef<Path>>(
+    attachments: &DataFrame,
+    dir: P,
+) -> Result<Vec<PathBuf>, Error> {
+    let fids = attachments.column("fid")?.u64()?;
+    let names = attachments.column("name")?.utf8()?;
+    let data = attachments.column("data")?.binary()?;
+
+    std::fs::create_dir_all(dir.as_ref())?;
+
+    let mut paths = Vec::with_capacity(attachments.height());
+    let mut file_names = HashSet::with_capacity(attachments.height());
+    for (idx, ((fid, name), bytes)) in fids.into_iter().zip(names).zip(data).enumerate() {
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => continue,
+        };
+        let name = match name {
+            Some(name) => name.replace(['/', '\\'], "_"),
+            None => idx.to_string(),
+        };
+        let fid = fid.map(|f| f.to_string()).unwrap_or_default();
+        let mut file_name = format!("{}_{}", fid, name);
+        if !file_names.insert(file_name.clone()) {
+            file_name = format!("{}_{}_{}", fid, idx, name);
+            file_names.insert(file_name.clone());
+        }
+        let path = dir.as_ref().join(file_name);
+        std::fs::write(&path, bytes)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+fn attachments_from_layer<L: LayerAccess>(
+    layer: &mut L,
+    fields: &AttachmentFields,
+) -> Result<DataFrame, Error> {
+    let mut fids: Vec<Option<u64>> = vec![];
+    let mut names: Vec<Option<String>> = vec![];
+    let mut content_types: Vec<Option<String>> = vec![];
+    let mut data: Vec<Option<Vec<u8>>> = vec![];
+
+    for feature in layer.features() {
+        // Negative ids can't be feature ids, so they're read as nulls
+        let fid = match feature.field(fields.fid)? {
+            Some(GdalValue::IntegerValue(v)) => u64::try_from(v).ok(),
+            Some(GdalValue::Integer64Value(v)) => u64::try_from(v).ok(),
+            _ => None,
+        };
+        let name = match fields.name {
+            Some(name) => feature.field(name)?.and_then(|v| v.into_string()),
+            None => None,
+        };
+        fids.push(fid);
+        names.push(name);
+        content_types.push(
+            feature
+                .field(fields.content_type)?
+                .and_then(|v| v.into_string()),
+        );
+        data.push(field_as_binary(&feature, fields.data));
+    }
+
+    let data: BinaryChunked = data.into_iter().collect();
+    let mut data = data.into_series();
+    data.rename("data");
+
+    Ok(DataFrame::new(vec![
+        Series::new("fid", fids),
+        Series::new("name", names),
+        Series::new("content_type", content_types),
+        data,
+    ])?)
+}
@@ -0,0 +1,460 @@
+use crate::{
+    geom, Error, GeometryFormat, GeometryTypePolicy, HttpOptions, LayerCreationOptions,
+    LayerMetadata, NullGeometryPolicy, OutputCompression, Progress, ProgressOwned, ReadParams,
+    SrsDefinition, TimeFallback, WriteParams, WriteParamsOwned,
+};
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::{Geometry, OGRwkbGeometryType};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// A builder for [`ReadParams`], created with [`ReadParams::builder`].
+///
+/// Fields without a builder method can still be set on the built params.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::ReadParams;
+///
+/// let params = ReadParams::builder()
+///     .layer("parcels")
+///     .bbox([-123.3, 49.1, -122.9, 49.4])
+///     .columns(&["pid", "zoning"])
+///     .limit(10_000)
+///     .build()?;
+/// let df = polars_gdal::df_from_resource("city.gpkg", Some(params))?;
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ReadParamsBuilder<'a> {
+    params: ReadParams<'a>,
+}
+
+impl<'a> ReadParams<'a> {
+    /// Start building params, see [`ReadParamsBuilder`].
+    pub fn builder() -> ReadParamsBuilder<'a> {
+        ReadParamsBuilder::default()
+    }
+
+    /// Check that no two fields are set that can't be used together.
+    fn check_conflicts(&self) -> Result<(), Error> {
+        let conflicts = [
+            (
+                self.truncating_limit.is_some() && self.erroring_limit.is_some(),
+                "`truncating_limit` can't be combined with `erroring_limit`",
+            ),
+            (
+                self.layer_name.is_some() && self.layer_index.is_some(),
+                "`layer_name` can't be combined with `layer_index`",
+            ),
+            (
+                self.bbox.is_some() && self.spatial_filter.is_some(),
+                "`bbox` can't be combined with `spatial_filter`",
+            ),
+            (
+                self.geometry_only && self.skip_geometry,
+                "`geometry_only` can't be combined with `skip_geometry`",
+            ),
+        ];
+        match conflicts.iter().find(|(conflicting, _)| *conflicting) {
+            Some((_, conflict)) => Err(Error::ConflictingParams((*conflict).to_owned())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> ReadParamsBuilder<'a> {
+    /// Set [`ReadParams::open_flags`].
+    pub fn open_flags(mut self, open_flags: gdal::GdalOpenFlags) -> Self {
+        self.params.open_flags = open_flags;
+        self
+    }
+
+    /// Set [`ReadParams::allowed_drivers`].
+    pub fn allowed_drivers(mut self, drivers: &'a [&'a str]) -> Self {
+        self.params.allowed_drivers = Some(drivers);
+        self
+    }
+
+    /// Set [`ReadParams::open_options`].
+    pub fn open_options(mut self, options: &'a [&'a str]) -> Self {
+        self.params.open_options = Some(options);
+        self
+    }
+
+    /// Set GDAL config options while reading, see [`ReadParams::config_options`].
+    pub fn config_options(mut self, options: &'a [(&'a str, &'a str)]) -> Self {
+        self.params.config_options = Some(options);
+        self
+    }
+
+    /// Set retries, timeouts and headers for remote reads, see [`ReadParams::http_options`].
+    pub fn http_options(mut self, options: &'a HttpOptions) -> Self {
+        self.params.http_options = Some(options);
+        self
+    }
+
+    /// Read the layer with this name, setting [`ReadParams::layer_name`].
+    pub fn layer(mut self, name: &'a str) -> Self {
+        self.params.layer_name = Some(name);
+        self
+    }
+
+    /// Read the layer at this index, setting [`ReadParams::layer_index`].
+    pub fn layer_index(mut self, index: usize) -> Self {
+        self.params.layer_index = Some(index);
+        self
+    }
+
+    /// Read feature IDs into a column with this name, setting [`ReadParams::fid_column_name`].
+    pub fn fid_column(mut self, name: &'a str) -> Self {
+        self.params.fid_column_name = Some(name);
+        self
+    }
+
+    /// Set [`ReadParams::geometry_column_name`].
+    pub fn geometry_column(mut self, name: &'a str) -> Self {
+        self.params.geometry_column_name = Some(name);
+        self
+    }
+
+    /// Set [`ReadParams::geometry_format`].
+    pub fn geometry_format(mut self, format: GeometryFormat) -> Self {
+        self.params.geometry_format = format;
+        self
+    }
+
+    /// Stop reading after `n` features, setting [`ReadParams::truncating_limit`].
+    pub fn limit(mut self, n: usize) -> Self {
+        self.params.truncating_limit = Some(n);
+        self
+    }
+
+    /// Fail if the layer has more than `n` features, setting [`ReadParams::erroring_limit`].
+    pub fn erroring_limit(mut self, n: usize) -> Self {
+        self.params.erroring_limit = Some(n);
+        self
+    }
+
+    /// Set [`ReadParams::offset`].
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.params.offset = Some(offset);
+        self
+    }
+
+    /// Set [`ReadParams::attribute_filter`].
+    pub fn attribute_filter(mut self, filter: &'a str) -> Self {
+        self.params.attribute_filter = Some(filter);
+        self
+    }
+
+    /// Set [`ReadParams::bbox`], as `[xmin, ymin, xmax, ymax]`.
+    pub fn bbox(mut self, bbox: [f64; 4]) -> Self {
+        self.params.bbox = Some(bbox);
+        self
+    }
+
+    /// Set [`ReadParams::spatial_filter`].
+    pub fn spatial_filter(mut self, geometry: &'a Geometry) -> Self {
+        self.params.spatial_filter = Some(geometry);
+        self
+    }
+
+    /// Set [`ReadParams::fids`].
+    pub fn fids(mut self, fids: &'a [u64]) -> Self {
+        self.params.fids = Some(fids);
+        self
+    }
+
+    /// Set [`ReadParams::columns`].
+    pub fn columns(mut self, columns: &'a [&'a str]) -> Self {
+        self.params.columns = Some(columns);
+        self
+    }
+
+    /// Set [`ReadParams::exclude_columns`].
+    pub fn exclude_columns(mut self, columns: &'a [&'a str]) -> Self {
+        self.params.exclude_columns = Some(columns);
+        self
+    }
+
+    /// Set [`ReadParams::geometry_only`].
+    pub fn geometry_only(mut self) -> Self {
+        self.params.geometry_only = true;
+        self
+    }
+
+    /// Set [`ReadParams::skip_geometry`].
+    pub fn skip_geometry(mut self) -> Self {
+        self.params.skip_geometry = true;
+        self
+    }
+
+    /// Set [`ReadParams::target_srs`].
+    pub fn target_srs(mut self, srs: &'a SpatialRef) -> Self {
+        self.params.target_srs = Some(srs);
+        self
+    }
+
+    /// Set [`ReadParams::progress`].
+    pub fn progress(mut self, progress: &'a (dyn Fn(u64, Option<u64>) + Sync)) -> Self {
+        self.params.progress = Some(Progress(progress));
+        self
+    }
+
+    /// Set [`ReadParams::cancel`].
+    pub fn cancel(mut self, cancel: &'a AtomicBool) -> Self {
+        self.params.cancel = Some(cancel);
+        self
+    }
+
+    /// Build the params, returning [`Error::ConflictingParams`] if options were set that can't be used together
+    /// (eg both a truncating and an erroring limit).
+    pub fn build(self) -> Result<ReadParams<'a>, Error> {
+        self.params.check_conflicts()?;
+        Ok(self.params)
+    }
+}
+
+/// A builder for write params, created with [`WriteParams::builder`].
+///
+/// Since the builder assembles driver options and SRSs itself, it builds [`WriteParamsOwned`], which are passed to the write
+/// functions through [`WriteParamsOwned::with_params`].
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{gdal::vector::OGRwkbGeometryType, gdal_bytes_from_df, VectorDriver, WriteParams};
+///
+/// let params = WriteParams::builder()
+///     .layer_name("roads")
+///     .srs_epsg(4326)
+///     .geometry_type(OGRwkbGeometryType::wkbLineString)
+///     .option("SPATIAL_INDEX", "YES")
+///     .build()?;
+/// let bytes = params.with_params(|params| gdal_bytes_from_df(&df, &VectorDriver::Gpkg, Some(params)))?;
+/// ```
+#[derive(Debug, Default)]
+pub struct WriteParamsBuilder {
+    params: WriteParamsOwned,
+
+    /// The first error found while building, reported by `build` so that methods can be chained.
+    error: Option<Error>,
+}
+
+impl WriteParams<'_> {
+    /// Start building params, see [`WriteParamsBuilder`].
+    pub fn builder() -> WriteParamsBuilder {
+        WriteParamsBuilder::default()
+    }
+}
+
+impl WriteParamsBuilder {
+    /// Set [`WriteParams::layer_name`].
+    pub fn layer_name(mut self, name: &str) -> Self {
+        self.params.layer_name = Some(name.to_owned());
+        self
+    }
+
+    /// Set [`WriteParams::geometry_column_name`].
+    pub fn geometry_column(mut self, name: &str) -> Self {
+        self.params.geometry_column_name = Some(name.to_owned());
+        self
+    }
+
+    /// Set [`WriteParams::geometry_format`].
+    pub fn geometry_format(mut self, format: GeometryFormat) -> Self {
+        self.params.geometry_format = format;
+        self
+    }
+
+    /// Set [`WriteParams::null_geometry_policy`].
+    pub fn null_geometry_policy(mut self, policy: NullGeometryPolicy) -> Self {
+        self.params.null_geometry_policy = policy;
+        self
+    }
+
+    /// Write feature IDs from this column, setting [`WriteParams::fid_column_name`].
+    pub fn fid_column(mut self, name: &str) -> Self {
+        self.params.fid_column_name = Some(name.to_owned());
+        self
+    }
+
+    /// Set [`WriteParams::srs`].
+    pub fn srs(mut self, srs: &SpatialRef) -> Self {
+        self.params.srs = self.record(SrsDefinition::from_srs(srs));
+        self
+    }
+
+    /// Set [`WriteParams::srs`] to an EPSG SRS, with longitude / latitude (or easting / northing) axis order.
+    pub fn srs_epsg(mut self, code: u32) -> Self {
+        self.params.srs = self.record(SrsDefinition::from_epsg(code));
+        self
+    }
+
+    /// Reproject geometries from `source` to `target`, setting [`WriteParams::source_srs`] and [`WriteParams::target_srs`].
+    pub fn reproject(mut self, source: &SpatialRef, target: &SpatialRef) -> Self {
+        self.params.source_srs = self.record(SrsDefinition::from_srs(source));
+        self.params.target_srs = self.record(SrsDefinition::from_srs(target));
+        self
+    }
+
+    /// Reproject geometries between EPSG SRSs, with longitude / latitude (or easting / northing) axis order.
+    pub fn reproject_epsg(mut self, source: u32, target: u32) -> Self {
+        self.params.source_srs = self.record(SrsDefinition::from_epsg(source));
+        self.params.target_srs = self.record(SrsDefinition::from_epsg(target));
+        self
+    }
+
+    /// Set [`WriteParams::geometry_type`].
+    pub fn geometry_type(mut self, geometry_type: OGRwkbGeometryType::Type) -> Self {
+        self.params.geometry_type = Some(geometry_type);
+        self
+    }
+
+    /// Set [`WriteParams::geometry_type_policy`].
+    pub fn geometry_type_policy(mut self, policy: GeometryTypePolicy) -> Self {
+        self.params.geometry_type_policy = policy;
+        self
+    }
+
+    /// Add a layer creation option, eg `.option("SPATIAL_INDEX", "YES")`.
+    pub fn option(mut self, name: &str, value: &str) -> Self {
+        if name.is_empty() || name.contains('=') {
+            self.record::<()>(Err(Error::ConflictingParams(format!(
+                "`{}` is not a valid option name",
+                name
+            ))));
+        }
+        let prefix = format!("{}=", name);
+        let options = self.params.options.get_or_insert_with(Vec::new);
+        let duplicate = options.iter().any(|option| option.starts_with(&prefix));
+        options.push(format!("{}={}", name, value));
+        if duplicate {
+            self.record::<()>(Err(Error::ConflictingParams(format!(
+                "option `{}` is set more than once",
+                name
+            ))));
+        }
+        self
+    }
+
+    /// Add typed layer creation options, eg [`GpkgCreationOptions`].
+    pub fn creation_options(self, options: &impl LayerCreationOptions) -> Self {
+        options
+            .option_pairs()
+            .into_iter()
+            .fold(self, |builder, (name, value)| builder.option(name, &value))
+    }
+
+    /// Set [`WriteParams::segmentize_max_length`].
+    pub fn segmentize_max_length(mut self, length: f64) -> Self {
+        self.params.segmentize_max_length = Some(length);
+        self
+    }
+
+    /// Set [`WriteParams::snap_to_grid`].
+    pub fn snap_to_grid(mut self, size: f64) -> Self {
+        self.params.snap_to_grid = Some(size);
+        self
+    }
+
+    /// Set [`WriteParams::force_2d`].
+    pub fn force_2d(mut self) -> Self {
+        self.params.force_2d = true;
+        self
+    }
+
+    /// Set [`WriteParams::force_multi`].
+    pub fn force_multi(mut self) -> Self {
+        self.params.force_multi = true;
+        self
+    }
+
+    /// Set [`WriteParams::layer_metadata`].
+    pub fn layer_metadata(mut self, metadata: LayerMetadata) -> Self {
+        self.params.layer_metadata = Some(metadata);
+        self
+    }
+
+    /// Set [`WriteParams::time_fallback`].
+    pub fn time_fallback(mut self, fallback: TimeFallback) -> Self {
+        self.params.time_fallback = fallback;
+        self
+    }
+
+    /// Set [`WriteParams::struct_as_json`].
+    pub fn struct_as_json(mut self) -> Self {
+        self.params.struct_as_json = true;
+        self
+    }
+
+    /// Set [`WriteParams::categorical_as_domain`].
+    pub fn categorical_as_domain(mut self) -> Self {
+        self.params.categorical_as_domain = true;
+        self
+    }
+
+    /// Set [`WriteParams::id_column`].
+    pub fn id_column(mut self, name: &str) -> Self {
+        self.params.id_column = Some(name.to_owned());
+        self
+    }
+
+    /// Set [`WriteParams::strict`].
+    pub fn strict(mut self) -> Self {
+        self.params.strict = true;
+        self
+    }
+
+    /// Set [`WriteParams::compression`].
+    pub fn compression(mut self, compression: OutputCompression) -> Self {
+        self.params.compression = compression;
+        self
+    }
+
+    /// Set [`WriteParams::progress`].
+    pub fn progress(mut self, progress: Arc<dyn Fn(u64, Option<u64>) + Send + Sync>) -> Self {
+        self.params.progress = Some(ProgressOwned(progress));
+        self
+    }
+
+    /// Set [`WriteParams::cancel`].
+    pub fn cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.params.cancel = Some(cancel);
+        self
+    }
+
+    /// Build the params, returning the first error found while building (eg an unknown EPSG code),
+    /// or [`Error::ConflictingParams`] if options were set that can't be used together.
+    pub fn build(self) -> Result<WriteParamsOwned, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let params = self.params;
+        if params.target_srs.is_some() && params.source_srs.is_none() {
+            return Err(Error::MissingSourceSrs);
+        }
+        if let (Some(geometry_type), true) = (params.geometry_type, params.force_multi) {
+            // Forcing multi-part geometries changes their type, so they wouldn't fit a single-part layer
+            if geom::forced_geometry_type(geometry_type, true, true)
+                != geom::forced_geometry_type(geometry_type, true, false)
+            {
+                return Err(Error::ConflictingParams(
+                    "`force_multi` can't be combined with a single-part `geometry_type`".to_owned(),
+                ));
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Keep the first error, so that it's reported by `build`.
+    fn record<T>(&mut self, result: Result<T, Error>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.error.get_or_insert(error);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::driver::open_dataset_for_read;
+use crate::{layer_from_params, resolve_read_path, Error, ReadParams};
+use gdal::vector::LayerAccess;
+use std::ffi::CString;
+use std::path::Path;
+
+/// What a layer's driver can do efficiently, as reported by GDAL.
+///
+/// Reads against layers without these capabilities still work, but may need to scan every feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LayerCapabilities {
+    /// Counting features doesn't need a scan of the layer.
+    pub fast_feature_count: bool,
+
+    /// Spatial filters use a spatial index rather than testing every feature.
+    pub fast_spatial_filter: bool,
+
+    /// The layer extent is known without a scan of the layer.
+    pub fast_get_extent: bool,
+
+    /// Features can be read by FID without a scan of the layer.
+    pub random_read: bool,
+
+    /// Skipping to the nth feature (eg for `ReadParams::offset`) doesn't need to read the skipped features.
+    pub fast_set_next_by_index: bool,
+
+    /// Fields can be skipped when reading, making `ReadParams::columns` cheaper.
+    pub ignore_fields: bool,
+
+    /// Writes can be grouped into transactions.
+    pub transactions: bool,
+
+    /// Curve geometries (eg CircularString) are supported, rather than being approximated by line segments.
+    pub curve_geometries: bool,
+
+    /// Strings are guaranteed to be UTF-8.
+    pub strings_as_utf8: bool,
+}
+
+/// Test the capabilities of a layer of a resource.
+///
+/// The layer is selected with `ReadParams::layer_name` or `ReadParams::layer_index`.
+/// Use this to choose a read strategy, or to understand why a read is slow.
+///
+/// # Example
+/// ```rust # ignore
+/// let capabilities = polars_gdal::layer_capabilities("my_geojson.json", None)?;
+/// if !capabilities.fast_spatial_filter {
+///     println!("Spatial filters will scan every feature");
+/// }
+/// ```
+pub fn layer_capabilities<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<LayerCapabilities, Error> {
+    let params = params.unwrap_or_default();
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset_for_read(&path, &params)?;
+
+    let layer = layer_from_params(&dataset, &params)?;
+
+    Ok(capabilities_of_layer(&layer))
+}
+
+/// Test the capabilities of an open layer.
+pub(crate) fn capabilities_of_layer<L: LayerAccess>(layer: &L) -> LayerCapabilities {
+    LayerCapabilities {
+        fast_feature_count: test_capability(layer, "FastFeatureCount"),
+        fast_spatial_filter: test_capability(layer, "FastSpatialFilter"),
+        fast_get_extent: test_capability(layer, "FastGetExtent"),
+        random_read: test_capability(layer, "RandomRead"),
+        fast_set_next_by_index: test_capability(layer, "FastSetNextByIndex"),
+        ignore_fields: test_capability(layer, "IgnoreFields"),
+        transactions: test_capability(layer, "Transactions"),
+        curve_geometries: test_capability(layer, "CurveGeometries"),
+        strings_as_utf8: test_capability(layer, "StringsAsUTF8"),
+    }
+}
+
+fn test_capability<L: LayerAccess>(layer: &L, capability: &str) -> bool {
+    let c_capability = CString::new(capability).unwrap();
+    unsafe { gdal_sys::OGR_L_TestCapability(layer.c_layer(), c_capability.as_ptr()) == 1 }
+}
@@ -0,0 +1,79 @@
+use crate::Error;
+use gdal_sys::{CPLErr, VSIFCloseL, VSIFOpenL, VSIFWriteL};
+use std::collections::BTreeMap;
+use std::ffi::CString;
+
+/// How the output of [`gdal_bytes_from_df`](crate::gdal_bytes_from_df) is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputCompression {
+    /// Return the file written by the driver as is.
+    None,
+
+    /// Compress the file written by the driver with gzip (eg to serve `.geojson.gz`). Fails with [`Error::MultipleFiles`]
+    /// for drivers writing several files.
+    Gzip,
+
+    /// Return a zip archive of every file written by the driver, so that multi-file formats such as Shapefile fit in a single buffer.
+    Zip,
+}
+
+impl Default for OutputCompression {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// The single buffer returned for the files written by a driver, by their path relative to the directory they were written to.
+///
+/// Compressed output is written through `/vsigzip/` or `/vsizip/` to a temporary in-memory file at `archive_path`.
+pub(crate) fn output_bytes(
+    files: BTreeMap<String, Vec<u8>>,
+    compression: OutputCompression,
+    archive_path: &str,
+) -> Result<Vec<u8>, Error> {
+    if files.len() > 1 && compression != OutputCompression::Zip {
+        return Err(Error::MultipleFiles(files.into_keys().collect()));
+    }
+
+    let written = match compression {
+        OutputCompression::None => return Ok(files.into_values().next().unwrap_or_default()),
+        OutputCompression::Gzip => {
+            let data = files.values().next().map(Vec::as_slice).unwrap_or_default();
+            write_vsi_file(&format!("/vsigzip/{}", archive_path), data)
+        }
+        OutputCompression::Zip => files.iter().try_for_each(|(name, data)| {
+            write_vsi_file(&format!("/vsizip/{{{}}}/{}", archive_path, name), data)
+        }),
+    };
+
+    let mut bytes = vec![];
+    let read = written.and_then(|_| {
+        gdal::vsi::call_on_mem_file_bytes(archive_path, |data| bytes.extend_from_slice(data))
+            .map_err(Error::from)
+    });
+    // Release the archive, even if it couldn't be written
+    let c_archive_path = CString::new(archive_path).unwrap();
+    unsafe { gdal_sys::VSIUnlink(c_archive_path.as_ptr()) };
+    read?;
+
+    Ok(bytes)
+}
+
+/// Write a file through GDAL's virtual file systems.
+pub(crate) fn write_vsi_file(path: &str, data: &[u8]) -> Result<(), Error> {
+    let c_path = CString::new(path).unwrap();
+    let c_mode = CString::new("wb").unwrap();
+    let handle = unsafe { VSIFOpenL(c_path.as_ptr(), c_mode.as_ptr()) };
+    if handle.is_null() {
+        return Err(crate::_last_null_pointer_err("VSIFOpenL").into());
+    }
+
+    let written = unsafe { VSIFWriteL(data.as_ptr().cast(), 1, data.len(), handle) };
+    let closed = unsafe { VSIFCloseL(handle) };
+    if written != data.len() || closed != 0 {
+        return Err(crate::_last_cpl_err(CPLErr::CE_Failure).into());
+    }
+
+    Ok(())
+}
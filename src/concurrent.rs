@@ -0,0 +1,204 @@
+use crate::driver::open_dataset_for_read;
+use crate::{layer_from_params, read_layer, resolve_read_path, Error, Progress, ReadParams};
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::{Geometry, LayerAccess};
+use polars::prelude::*;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Drivers that can safely be opened several times at once, and that can cheaply skip to a range of FIDs.
+const CONCURRENT_DRIVERS: &[&str] = &[
+    "ESRI Shapefile",
+    "GPKG",
+    "SQLite",
+    "FlatGeobuf",
+    "OpenFileGDB",
+    "Parquet",
+    "Arrow",
+];
+
+/// Read a resource into a DataFrame using several readers in parallel, each with its own dataset handle.
+///
+/// The layer is split into `n_readers` ranges of FIDs, which are read on separate threads and concatenated in FID order.
+/// A single reader is used instead when the driver isn't known to handle concurrent opens safely and efficiently, when the layer
+/// can't count its features cheaply, or when params depend on reading features in sequence (`fids`, `offset`, the feature limits or sampling).
+///
+/// Categorical columns are merged by value. With `ReadParams::parse_json_fields` set, a single reader is always used, since
+/// each reader would infer the structure of JSON fields separately.
+///
+/// # Example
+/// ```rust # ignore
+/// let df = polars_gdal::df_from_resource_concurrent("parcels.gpkg", 8, None)?;
+/// ```
+pub fn df_from_resource_concurrent<P: AsRef<Path>>(
+    path: P,
+    n_readers: usize,
+    params: Option<ReadParams>,
+) -> Result<DataFrame, Error> {
+    let params = params.unwrap_or_default();
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+
+    let dataset = open_dataset_for_read(&path, &params)?;
+    let mut layer = layer_from_params(&dataset, &params)?;
+    let driver_name = dataset.driver().short_name();
+    let sequential = params.fids.is_some()
+        || params.offset.is_some()
+        || params.truncating_limit.is_some()
+        || params.erroring_limit.is_some()
+        || params.sample_every_n.is_some()
+        || params.sample_fraction.is_some()
+        || params.parse_json_fields;
+    let feature_count = layer.try_feature_count();
+    let first_fid = layer.features().next().and_then(|feature| feature.fid());
+
+    let (feature_count, first_fid) = match (feature_count, first_fid) {
+        (Some(feature_count), Some(first_fid))
+            if n_readers > 1 && !sequential && CONCURRENT_DRIVERS.contains(&driver_name.as_str()) =>
+        {
+            (feature_count, first_fid)
+        }
+        _ => return read_layer(&mut layer, Some(&dataset), params, None),
+    };
+    let fid_column = match layer_fid_column(&layer) {
+        Some(fid_column) => format!("\"{}\"", fid_column.replace('"', "\"\"")),
+        None => "FID".to_owned(),
+    };
+    drop(layer);
+    drop(dataset);
+
+    // Every FID falls in exactly one range, since the first and last ranges are open-ended. Gaps in FIDs only affect how evenly work is split.
+    let chunk = ((feature_count as usize + n_readers - 1) / n_readers).max(1) as u64;
+    let filters: Vec<String> = (0..n_readers as u64)
+        .map(|i| {
+            let mut range = vec![];
+            if i > 0 {
+                range.push(format!("{} >= {}", fid_column, first_fid + i * chunk));
+            }
+            if i + 1 < n_readers as u64 {
+                range.push(format!("{} < {}", fid_column, first_fid + (i + 1) * chunk));
+            }
+            match params.attribute_filter {
+                Some(filter) => format!("({}) AND {}", filter, range.join(" AND ")),
+                None => range.join(" AND "),
+            }
+        })
+        .collect();
+
+    // GDAL objects can't be shared between threads, so each reader rebuilds its own spatial filter and target SRS
+    let spatial_filter_wkt = params.spatial_filter.map(|g| g.wkt()).transpose()?;
+    let target_srs = params
+        .target_srs
+        .map(|srs| -> Result<_, Error> {
+            let strategy = unsafe { gdal_sys::OSRGetAxisMappingStrategy(srs.to_c_hsrs()) };
+            Ok((srs.to_wkt()?, strategy))
+        })
+        .transpose()?;
+    let shared = SharedParams(ReadParams {
+        spatial_filter: None,
+        target_srs: None,
+        ..params
+    });
+
+    // Each reader reports its own progress, which is summed into the progress of the whole layer
+    let total_processed = AtomicU64::new(0);
+
+    let frames: Vec<Result<DataFrame, Error>> = std::thread::scope(|scope| {
+        let readers: Vec<_> = filters
+            .iter()
+            .map(|filter| {
+                let (path, shared) = (&path, &shared);
+                let (spatial_filter_wkt, target_srs) = (&spatial_filter_wkt, &target_srs);
+                let total_processed = &total_processed;
+                scope.spawn(move || -> Result<DataFrame, Error> {
+                    let spatial_filter = spatial_filter_wkt
+                        .as_deref()
+                        .map(Geometry::from_wkt)
+                        .transpose()?;
+                    let target_srs = target_srs
+                        .as_ref()
+                        .map(|(wkt, strategy)| -> Result<_, Error> {
+                            let srs = SpatialRef::from_wkt(wkt)?;
+                            unsafe { gdal_sys::OSRSetAxisMappingStrategy(srs.to_c_hsrs(), *strategy) };
+                            Ok(srs)
+                        })
+                        .transpose()?;
+                    let reader_processed = AtomicU64::new(0);
+                    let report_progress = |processed: u64, _: Option<u64>| {
+                        let delta = processed - reader_processed.swap(processed, Ordering::Relaxed);
+                        let total = total_processed.fetch_add(delta, Ordering::Relaxed) + delta;
+                        if let Some(progress) = shared.0.progress {
+                            (progress.0)(total, Some(feature_count));
+                        }
+                    };
+                    let params = ReadParams {
+                        attribute_filter: Some(filter.as_str()),
+                        spatial_filter: spatial_filter.as_ref(),
+                        target_srs: target_srs.as_ref(),
+                        progress: shared.0.progress.map(|_| Progress(&report_progress)),
+                        ..shared.0.clone()
+                    };
+
+                    let dataset = open_dataset_for_read(path, &params)?;
+                    let mut layer = layer_from_params(&dataset, &params)?;
+                    read_layer(&mut layer, Some(&dataset), params, None)
+                })
+            })
+            .collect();
+
+        readers
+            .into_iter()
+            .map(|reader| reader.join().expect("polars_gdal: reader thread panicked"))
+            .collect()
+    });
+
+    merge_frames(frames.into_iter().collect::<Result<Vec<_>, _>>()?)
+}
+
+/// ReadParams without the fields that refer to GDAL objects, so that they can be shared between reader threads.
+struct SharedParams<'a>(ReadParams<'a>);
+
+// SAFETY: `spatial_filter` and `target_srs` are the only fields of ReadParams referring to GDAL objects, which aren't thread-safe.
+//         SharedParams is only built with both set to None. All other fields are plain values or references to Sync data,
+//         including the `progress` callback, which is required to be Sync.
+unsafe impl Sync for SharedParams<'_> {}
+
+/// The name of the FID column of a layer, if the driver has one (eg `fid` in a GeoPackage).
+fn layer_fid_column<L: LayerAccess>(layer: &L) -> Option<String> {
+    let c_fid_column = unsafe { gdal_sys::OGR_L_GetFIDColumn(layer.c_layer()) };
+    if c_fid_column.is_null() {
+        return None;
+    }
+    Some(crate::_string(c_fid_column)).filter(|fid_column| !fid_column.is_empty())
+}
+
+/// Concatenate DataFrames read by separate readers. Categorical columns are concatenated by value, since each reader has its own categories.
+pub(crate) fn merge_frames(frames: Vec<DataFrame>) -> Result<DataFrame, Error> {
+    let mut merged: Option<DataFrame> = None;
+    let mut categorical_columns = vec![];
+    for mut df in frames {
+        for series in df.get_columns().to_vec() {
+            if let DataType::Categorical(_) = series.dtype() {
+                if !categorical_columns.contains(&series.name().to_owned()) {
+                    categorical_columns.push(series.name().to_owned());
+                }
+                df.with_column(series.cast(&DataType::Utf8)?)?;
+            }
+        }
+        merged = Some(match merged {
+            Some(mut merged) => {
+                merged.vstack_mut(&df)?;
+                merged
+            }
+            None => df,
+        });
+    }
+
+    let mut merged = merged.unwrap_or_default();
+    merged.rechunk();
+    for name in &categorical_columns {
+        let categorical = merged.column(name)?.cast(&DataType::Categorical(None))?;
+        merged.with_column(categorical)?;
+    }
+
+    Ok(merged)
+}
@@ -0,0 +1,243 @@
+use crate::Error;
+use gdal::errors::GdalError;
+use std::ffi::{c_char, CString};
+use std::time::Duration;
+
+/// GDAL config options set on the current thread, which are restored to their previous values when the guard is dropped.
+///
+/// Thread-local options take precedence over global ones (eg set with `CPLSetConfigOption` or environment variables),
+/// so reads on other threads aren't affected.
+pub(crate) struct ConfigScope {
+    /// The options that were set, with their previous thread-local values.
+    previous: Vec<(CString, Option<CString>)>,
+}
+
+impl ConfigScope {
+    /// Set `(name, value)` options on the current thread.
+    pub(crate) fn enter<'o>(
+        options: impl IntoIterator<Item = (&'o str, &'o str)>,
+    ) -> Result<Self, Error> {
+        let mut scope = Self { previous: vec![] };
+        for (name, value) in options {
+            let c_name = CString::new(name).map_err(GdalError::from)?;
+            let c_value = CString::new(value).map_err(GdalError::from)?;
+
+            let c_previous = unsafe {
+                gdal_sys::CPLGetThreadLocalConfigOption(c_name.as_ptr(), std::ptr::null())
+            };
+            let previous = (!c_previous.is_null())
+                .then(|| CString::new(crate::_string(c_previous)).unwrap_or_default());
+            unsafe { gdal_sys::CPLSetThreadLocalConfigOption(c_name.as_ptr(), c_value.as_ptr()) };
+            // Pushed after setting, so that a failure in a later option restores this one
+            scope.previous.push((c_name, previous));
+        }
+
+        Ok(scope)
+    }
+
+    /// Set the config options of a read: `params.http_options`, then `params.config_options`, which override them.
+    pub(crate) fn for_read(params: &crate::ReadParams) -> Result<Self, Error> {
+        let http_options = params
+            .http_options
+            .map(HttpOptions::config_pairs)
+            .unwrap_or_default();
+        let http_options = http_options
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()));
+        let config_options = params.config_options.unwrap_or_default().iter().copied();
+
+        Self::enter(http_options.chain(config_options))
+    }
+}
+
+impl Drop for ConfigScope {
+    fn drop(&mut self) {
+        // In reverse, so that an option given twice gets its original value back
+        for (c_name, previous) in self.previous.iter().rev() {
+            let c_previous: *const c_char = previous
+                .as_ref()
+                .map_or(std::ptr::null(), |previous| previous.as_ptr());
+            unsafe { gdal_sys::CPLSetThreadLocalConfigOption(c_name.as_ptr(), c_previous) };
+        }
+    }
+}
+
+/// Retry, timeout and header settings for reads of remote files (eg `/vsicurl/` URLs), set with `ReadParams::http_options`.
+///
+/// They are applied to a single read, like `ReadParams::config_options`. Fields left unset keep GDAL's default, or the
+/// value set with [`init`] or environment variables. See <https://gdal.org/user/configoptions.html#networking-options>
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::HttpOptions;
+/// use std::time::Duration;
+///
+/// let http_options = HttpOptions {
+///     max_retry: Some(3),
+///     retry_delay: Some(Duration::from_millis(500)),
+///     timeout: Some(Duration::from_secs(30)),
+///     bearer_token: Some(token),
+///     ..Default::default()
+/// };
+/// let params = polars_gdal::ReadParams {
+///     http_options: Some(&http_options),
+///     ..Default::default()
+/// };
+/// let df = polars_gdal::df_from_resource("/vsicurl/https://example.com/parcels.fgb", Some(params))?;
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HttpOptions {
+    /// The number of times a request failing with a 429, 502, 503 or 504 status is retried (`GDAL_HTTP_MAX_RETRY`).
+    /// GDAL doesn't retry by default.
+    pub max_retry: Option<u32>,
+
+    /// The delay before the first retry, which doubles for each following one (`GDAL_HTTP_RETRY_DELAY`). Defaults to 30 seconds.
+    pub retry_delay: Option<Duration>,
+
+    /// The maximum duration of a request (`GDAL_HTTP_TIMEOUT`).
+    pub timeout: Option<Duration>,
+
+    /// The maximum duration of connecting to the server (`GDAL_HTTP_CONNECTTIMEOUT`).
+    pub connect_timeout: Option<Duration>,
+
+    /// Headers sent with every request, as `(name, value)` pairs (`GDAL_HTTP_HEADERS`). Requires GDAL >= 3.6.
+    pub headers: Vec<(String, String)>,
+
+    /// A token sent as an `Authorization: Bearer` header. Requires GDAL >= 3.6.
+    pub bearer_token: Option<String>,
+}
+
+impl HttpOptions {
+    /// The settings as `(name, value)` config options.
+    pub(crate) fn config_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut options = vec![];
+        if let Some(max_retry) = self.max_retry {
+            options.push(("GDAL_HTTP_MAX_RETRY", max_retry.to_string()));
+        }
+        if let Some(delay) = self.retry_delay {
+            options.push(("GDAL_HTTP_RETRY_DELAY", delay.as_secs_f64().to_string()));
+        }
+        if let Some(timeout) = self.timeout {
+            options.push(("GDAL_HTTP_TIMEOUT", timeout.as_secs_f64().to_string()));
+        }
+        if let Some(timeout) = self.connect_timeout {
+            options.push((
+                "GDAL_HTTP_CONNECTTIMEOUT",
+                timeout.as_secs_f64().to_string(),
+            ));
+        }
+
+        let authorization = self
+            .bearer_token
+            .as_ref()
+            .map(|token| ("Authorization".to_owned(), format!("Bearer {}", token)));
+        // Every header ends with CRLF, so that GDAL splits them on CRLF rather than on the commas that values may contain
+        let headers: String = self
+            .headers
+            .iter()
+            .chain(authorization.as_ref())
+            .map(|(name, value)| format!("{}: {}\r\n", name, value))
+            .collect();
+        if !headers.is_empty() {
+            options.push(("GDAL_HTTP_HEADERS", headers));
+        }
+
+        options
+    }
+}
+
+/// Process-wide GDAL configuration, applied with [`init`].
+///
+/// Fields left unset keep GDAL's default, or the value of the matching environment variable.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{init, GdalConfig, NumThreads};
+///
+/// init(GdalConfig {
+///     curl_cache_size: Some(64 * 1024 * 1024),
+///     num_threads: Some(NumThreads::AllCpus),
+///     http_proxy: Some("http://proxy.internal:3128".to_string()),
+///     skip_drivers: vec!["KML".to_string()],
+///     ..Default::default()
+/// })?;
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GdalConfig {
+    /// The size of the cache of remote files read through `/vsicurl/` and the cloud storage file systems, in bytes
+    /// (`CPL_VSIL_CURL_CACHE_SIZE`). Defaults to 16 MB.
+    pub curl_cache_size: Option<u64>,
+
+    /// The number of threads drivers may use for compression and decoding (`GDAL_NUM_THREADS`).
+    pub num_threads: Option<NumThreads>,
+
+    /// The proxy used for HTTP requests, as `host:port` or a URL (`GDAL_HTTP_PROXY`).
+    pub http_proxy: Option<String>,
+
+    /// The proxy used for HTTPS requests, if different from `http_proxy` (`GDAL_HTTPS_PROXY`).
+    pub https_proxy: Option<String>,
+
+    /// The proxy credentials, as `user:password` (`GDAL_HTTP_PROXYUSERPWD`).
+    pub proxy_credentials: Option<String>,
+
+    /// The short names of drivers to deregister (`GDAL_SKIP`), eg to stop untrusted uploads from being opened with them.
+    pub skip_drivers: Vec<String>,
+
+    /// Any other config options, as `(name, value)` pairs. See <https://gdal.org/user/configoptions.html>
+    pub options: Vec<(String, String)>,
+}
+
+/// A number of threads for [`GdalConfig::num_threads`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumThreads {
+    /// One thread per CPU.
+    AllCpus,
+
+    /// A fixed number of threads.
+    Count(usize),
+}
+
+/// Apply a process-wide GDAL configuration and register GDAL's drivers.
+///
+/// Call this once at startup, before reading or writing. Options are set globally, so they apply to every thread, unlike
+/// `ReadParams::config_options`, which take precedence over them for a single read.
+pub fn init(config: GdalConfig) -> Result<(), Error> {
+    let mut options: Vec<(&str, String)> = vec![];
+    if let Some(size) = config.curl_cache_size {
+        options.push(("CPL_VSIL_CURL_CACHE_SIZE", size.to_string()));
+    }
+    match config.num_threads {
+        Some(NumThreads::AllCpus) => options.push(("GDAL_NUM_THREADS", "ALL_CPUS".to_owned())),
+        Some(NumThreads::Count(count)) => options.push(("GDAL_NUM_THREADS", count.to_string())),
+        None => {}
+    }
+    if let Some(proxy) = config.http_proxy {
+        options.push(("GDAL_HTTP_PROXY", proxy));
+    }
+    if let Some(proxy) = config.https_proxy {
+        options.push(("GDAL_HTTPS_PROXY", proxy));
+    }
+    if let Some(credentials) = config.proxy_credentials {
+        options.push(("GDAL_HTTP_PROXYUSERPWD", credentials));
+    }
+    if !config.skip_drivers.is_empty() {
+        options.push(("GDAL_SKIP", config.skip_drivers.join(" ")));
+    }
+
+    for (name, value) in &config.options {
+        options.push((name.as_str(), value.clone()));
+    }
+
+    for (name, value) in options {
+        let c_name = CString::new(name).map_err(GdalError::from)?;
+        let c_value = CString::new(value).map_err(GdalError::from)?;
+        unsafe { gdal_sys::CPLSetConfigOption(c_name.as_ptr(), c_value.as_ptr()) };
+    }
+
+    // Registering drivers also deregisters the ones listed in GDAL_SKIP, even if they were registered before
+    unsafe { gdal_sys::GDALAllRegister() };
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use crate::driver::open_dataset;
+use crate::{resolve_read_path, srs_epsg, Error, ReadParams};
+use gdal::vector::{LayerAccess, OGRFieldType, OGRwkbGeometryType};
+use std::path::Path;
+
+/// A summary of one layer of a resource, as returned by [`describe_resource`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerInfo {
+    /// The name of the layer.
+    pub name: String,
+
+    /// The geometry type declared by the layer. This is `wkbUnknown` for layers with mixed geometry types.
+    pub geometry_type: OGRwkbGeometryType::Type,
+
+    /// Whether the declared geometry type can be relied on, eg to choose `WriteParams::geometry_type` when re-exporting.
+    ///
+    /// This is false if the layer declares `wkbUnknown` (as GeoJSON and CSV layers do), or if a scan found features of another type
+    /// (eg MultiPolygons in a Shapefile polygon layer).
+    pub geometry_type_honored: bool,
+
+    /// The geometry type of the layer's features, found by scanning every feature with [`describe_resource_with_geometry_scan`].
+    ///
+    /// Mixed single and multi geometries of the same kind (eg Polygon and MultiPolygon) give the multi type, and other mixes give `wkbUnknown`.
+    /// `None` if the layer wasn't scanned or has no non-empty geometries.
+    pub scanned_geometry_type: Option<OGRwkbGeometryType::Type>,
+
+    /// Whether the layer's geometries have Z coordinates, according to the scanned geometry type if the layer was scanned, or else the declared type.
+    ///
+    /// Read geometries keep their Z coordinates, unless `ReadParams::force_2d` is set.
+    pub has_z: bool,
+
+    /// Whether the layer's geometries have M (measure) values, according to the scanned geometry type if the layer was scanned, or else the declared type.
+    pub has_m: bool,
+
+    /// The spatial reference of the layer as WKT, or `None` if the layer has no SRS.
+    pub srs_wkt: Option<String>,
+
+    /// The EPSG code matching the spatial reference of the layer, if there is one.
+    pub srs_epsg: Option<u32>,
+
+    /// The number of features in the layer.
+    pub feature_count: u64,
+
+    /// The extent of the layer as `[xmin, ymin, xmax, ymax]`, or `None` if the layer is empty or has no geometries.
+    pub extent: Option<[f64; 4]>,
+
+    /// The attribute fields of the layer.
+    pub fields: Vec<FieldInfo>,
+}
+
+/// The definition of an attribute field of a layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The name of the field.
+    pub name: String,
+
+    /// The GDAL type of the field.
+    pub field_type: OGRFieldType::Type,
+
+    /// The declared width of the field, or 0 if unbounded.
+    pub width: i32,
+
+    /// The declared number of decimal places of the field, or 0 if unspecified.
+    pub precision: i32,
+}
+
+/// List the layers of a resource without reading any features.
+///
+/// Use this to inspect an unfamiliar file before deciding which layer and columns to read.
+/// Only the `open_flags`, `allowed_drivers`, `open_options` and `sibling_files` fields of `params` are used.
+///
+/// Counting features and computing the extent is fast for most formats, but may need a full scan of formats without an index (eg GeoJSON or CSV).
+///
+/// # Example
+/// ```rust # ignore
+/// for layer in polars_gdal::describe_resource("my_geopackage.gpkg", None)? {
+///     println!("{}: {} features", layer.name, layer.feature_count);
+/// }
+/// ```
+pub fn describe_resource<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<Vec<LayerInfo>, Error> {
+    let params = params.unwrap_or_default();
+    let gdal_options: gdal::DatasetOptions = (&params).into();
+
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset(&path, gdal_options)?;
+
+    dataset
+        .layers()
+        .map(|mut layer| describe_layer(&mut layer, false))
+        .collect()
+}
+
+/// List the layers of a resource like [`describe_resource`], also scanning every feature to find the true geometry type of each layer.
+///
+/// Use this when the declared geometry type isn't honored, eg to choose `WriteParams::geometry_type` for re-exporting a GeoJSON file.
+/// The scan reads every feature, so is as slow as reading the whole resource.
+pub fn describe_resource_with_geometry_scan<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<Vec<LayerInfo>, Error> {
+    let params = params.unwrap_or_default();
+    let gdal_options: gdal::DatasetOptions = (&params).into();
+
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset(&path, gdal_options)?;
+
+    dataset
+        .layers()
+        .map(|mut layer| describe_layer(&mut layer, true))
+        .collect()
+}
+
+/// Summarize a single layer, optionally scanning its features for their geometry type.
+pub(crate) fn describe_layer<L: LayerAccess>(layer: &mut L, scan: bool) -> Result<LayerInfo, Error> {
+    let geometry_type = unsafe { gdal_sys::OGR_L_GetGeomType(layer.c_layer()) };
+    let scanned_geometry_type = if scan {
+        scan_geometry_type(layer)
+    } else {
+        None
+    };
+    let geometry_type_honored = geometry_type_honored(geometry_type, scanned_geometry_type);
+    let dimensions_type = scanned_geometry_type.unwrap_or(geometry_type);
+    let (has_z, has_m) = unsafe {
+        (
+            gdal_sys::OGR_GT_HasZ(dimensions_type) != 0,
+            gdal_sys::OGR_GT_HasM(dimensions_type) != 0,
+        )
+    };
+    let srs = layer.spatial_ref();
+
+    let extent = layer.try_get_extent()?.or_else(|| layer.get_extent().ok());
+    let extent = extent
+        .filter(|e| e.MinX <= e.MaxX && e.MinY <= e.MaxY)
+        .map(|e| [e.MinX, e.MinY, e.MaxX, e.MaxY]);
+
+    let fields = layer
+        .defn()
+        .fields()
+        .map(|field| FieldInfo {
+            name: field.name(),
+            field_type: field.field_type(),
+            width: field.width(),
+            precision: field.precision(),
+        })
+        .collect();
+
+    Ok(LayerInfo {
+        name: layer.name(),
+        geometry_type,
+        geometry_type_honored,
+        scanned_geometry_type,
+        has_z,
+        has_m,
+        srs_wkt: srs.as_ref().map(|srs| srs.to_wkt()).transpose()?,
+        srs_epsg: srs.as_ref().and_then(srs_epsg),
+        feature_count: layer.feature_count(),
+        extent,
+        fields,
+    })
+}
+
+/// Find the geometry type shared by every non-empty geometry of a layer, reading every feature.
+pub(crate) fn scan_geometry_type<L: LayerAccess>(layer: &mut L) -> Option<OGRwkbGeometryType::Type> {
+    let mut scanned = None;
+    for feature in layer.features() {
+        let geometry = feature.geometry();
+        if geometry.is_empty() {
+            continue;
+        }
+        let geometry_type = geometry.geometry_type();
+        scanned = Some(match scanned {
+            Some(scanned) => common_geometry_type(scanned, geometry_type),
+            None => geometry_type,
+        });
+    }
+
+    scanned
+}
+
+/// Whether a declared geometry type can be relied on, given the type found by a scan (if any).
+pub(crate) fn geometry_type_honored(
+    declared: OGRwkbGeometryType::Type,
+    scanned: Option<OGRwkbGeometryType::Type>,
+) -> bool {
+    declared != OGRwkbGeometryType::wkbUnknown && scanned.map_or(true, |scanned| scanned == declared)
+}
+
+/// The most specific geometry type that can hold geometries of both types, with Z (or M) values if either type has them.
+pub(crate) fn common_geometry_type(
+    a: OGRwkbGeometryType::Type,
+    b: OGRwkbGeometryType::Type,
+) -> OGRwkbGeometryType::Type {
+    if a == b {
+        return a;
+    }
+
+    unsafe {
+        let multi_type = |geometry_type| {
+            let flat = gdal_sys::OGR_GT_Flatten(geometry_type);
+            if gdal_sys::OGR_GT_IsSubClassOf(flat, OGRwkbGeometryType::wkbGeometryCollection) != 0 {
+                flat
+            } else {
+                gdal_sys::OGR_GT_GetCollection(flat)
+            }
+        };
+        let common = match (multi_type(a), multi_type(b)) {
+            (multi_a, multi_b) if multi_a == multi_b => multi_a,
+            _ => return OGRwkbGeometryType::wkbUnknown,
+        };
+        gdal_sys::OGR_GT_SetModifier(
+            common,
+            gdal_sys::OGR_GT_HasZ(a) | gdal_sys::OGR_GT_HasZ(b),
+            gdal_sys::OGR_GT_HasM(a) | gdal_sys::OGR_GT_HasM(b),
+        )
+    }
+}
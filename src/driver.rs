@@ -0,0 +1,376 @@
+use crate::json::json_escape;
+use crate::{Error, ReadParams};
+use gdal::Dataset;
+use std::ffi::CString;
+use std::path::Path;
+
+/// Look up a GDAL driver by its short name (eg `"GeoJSON"`, `"GPKG"`, `"Parquet"`).
+///
+/// Unlike [`gdal::DriverManager::get_driver_by_name`], a missing driver is reported as [`Error::DriverUnavailable`]
+/// with guidance on how to build GDAL with that driver enabled.
+///
+/// # Example
+/// ```rust # ignore
+/// let parquet_driver = polars_gdal::driver_by_name("Parquet")?;
+/// let bytes = polars_gdal::gdal_bytes_from_df(&df, &parquet_driver, None)?;
+/// ```
+pub fn driver_by_name(name: &str) -> Result<gdal::Driver, Error> {
+    gdal::DriverManager::get_driver_by_name(name).map_err(|_| driver_unavailable(name))
+}
+
+/// Common GDAL vector drivers.
+///
+/// Use these instead of driver name strings to avoid mistakes such as `"GeoJson"` instead of `"GeoJSON"`, which only fail at runtime.
+/// Anywhere a [`gdal::Driver`] is accepted by this crate's write functions, a `VectorDriver` can be used instead.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{gdal_bytes_from_df, VectorDriver};
+///
+/// let geojson_bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, None)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VectorDriver {
+    /// GeoJSON (`GeoJSON`)
+    GeoJson,
+
+    /// GeoPackage (`GPKG`)
+    Gpkg,
+
+    /// ESRI Shapefile (`ESRI Shapefile`)
+    Shapefile,
+
+    /// FlatGeobuf (`FlatGeobuf`)
+    FlatGeobuf,
+
+    /// GeoParquet (`Parquet`). Requires GDAL to be built against Apache Parquet.
+    Parquet,
+
+    /// PostgreSQL / PostGIS (`PostgreSQL`)
+    PostgreSql,
+
+    /// Comma Separated Values (`CSV`)
+    Csv,
+
+    /// Keyhole Markup Language (`KML`)
+    Kml,
+
+    /// Mapbox Vector Tiles (`MVT`)
+    Mvt,
+}
+
+impl VectorDriver {
+    /// The GDAL short name of the driver.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::GeoJson => "GeoJSON",
+            Self::Gpkg => "GPKG",
+            Self::Shapefile => "ESRI Shapefile",
+            Self::FlatGeobuf => "FlatGeobuf",
+            Self::Parquet => "Parquet",
+            Self::PostgreSql => "PostgreSQL",
+            Self::Csv => "CSV",
+            Self::Kml => "KML",
+            Self::Mvt => "MVT",
+        }
+    }
+
+    /// Whether the GDAL library this crate is linked against was built with this driver.
+    pub fn available(&self) -> bool {
+        driver_available(self.name())
+    }
+
+    /// The driver usually used for files with an extension (eg `"gpkg"` or `"shp"`), ignoring case.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "geojson" | "json" => Some(Self::GeoJson),
+            "gpkg" => Some(Self::Gpkg),
+            "shp" => Some(Self::Shapefile),
+            "fgb" => Some(Self::FlatGeobuf),
+            "parquet" | "geoparquet" => Some(Self::Parquet),
+            "csv" => Some(Self::Csv),
+            "kml" => Some(Self::Kml),
+            _ => None,
+        }
+    }
+
+    /// The driver usually used for a file path, from its extension.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        Self::from_extension(path.as_ref().extension()?.to_str()?)
+    }
+}
+
+impl std::fmt::Display for VectorDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Something that identifies a GDAL driver, accepted by the write functions.
+///
+/// Implemented for [`gdal::Driver`] and [`VectorDriver`].
+pub trait ToGdalDriver {
+    /// Get the GDAL driver, reporting [`Error::DriverUnavailable`] if GDAL was built without it.
+    fn to_gdal_driver(&self) -> Result<gdal::Driver, Error>;
+}
+
+impl ToGdalDriver for gdal::Driver {
+    fn to_gdal_driver(&self) -> Result<gdal::Driver, Error> {
+        // Drivers are owned by GDAL's driver manager, so the handle can be shared freely
+        Ok(unsafe { gdal::Driver::from_c_driver(self.c_driver()) })
+    }
+}
+
+impl ToGdalDriver for VectorDriver {
+    fn to_gdal_driver(&self) -> Result<gdal::Driver, Error> {
+        driver_by_name(self.name())
+    }
+}
+
+/// A GDAL driver and its capabilities, as reported by [`list_drivers`] and [`identify_bytes`](crate::identify_bytes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriverInfo {
+    /// The short name of the driver (eg `"GPKG"`), as accepted by [`driver_by_name`].
+    pub name: String,
+
+    /// The long name of the driver (eg `"GeoPackage"`).
+    pub long_name: String,
+
+    /// The file extensions of the format, without the leading dot.
+    pub extensions: Vec<String>,
+
+    /// Whether the driver can create datasets, so DataFrames can be written with it.
+    pub create: bool,
+
+    /// Whether the driver can read and write through GDAL's virtual file systems, as needed by [`gdal_bytes_from_df`](crate::gdal_bytes_from_df)
+    /// and [`df_from_bytes`](crate::df_from_bytes).
+    pub virtual_io: bool,
+
+    /// Whether features can be written to the layers of a dataset in any order, rather than one layer after another.
+    pub random_layer_write: bool,
+
+    /// The OGR field types the driver can create (eg `"Integer64"`, `"Date"`), or `None` if the driver doesn't say.
+    pub field_types: Option<Vec<String>>,
+}
+
+impl DriverInfo {
+    /// Describe a driver.
+    pub fn of(driver: &gdal::Driver) -> Self {
+        let extensions = driver_metadata_item(driver, "DMD_EXTENSIONS")
+            .or_else(|| driver_metadata_item(driver, "DMD_EXTENSION"))
+            .unwrap_or_default();
+
+        Self {
+            name: driver.short_name(),
+            long_name: driver.long_name(),
+            extensions: extensions.split_whitespace().map(str::to_owned).collect(),
+            create: driver_capability(driver, "DCAP_CREATE"),
+            virtual_io: driver_capability(driver, "DCAP_VIRTUALIO"),
+            random_layer_write: driver_capability(driver, "DCAP_RANDOM_LAYER_WRITE"),
+            field_types: driver_metadata_item(driver, "DMD_CREATIONFIELDDATATYPES")
+                .map(|types| types.split_whitespace().map(str::to_owned).collect()),
+        }
+    }
+}
+
+/// List the vector drivers of the GDAL library this crate is linked against, with their capabilities.
+///
+/// Use this to tell at runtime which formats can be read or written, since GDAL builds differ in the drivers they include.
+///
+/// # Example
+/// ```rust # ignore
+/// for driver in polars_gdal::list_drivers().iter().filter(|d| d.create) {
+///     println!("{} ({})", driver.long_name, driver.extensions.join(", "));
+/// }
+/// ```
+pub fn list_drivers() -> Vec<DriverInfo> {
+    (0..gdal::DriverManager::count())
+        .filter_map(|index| gdal::DriverManager::get_driver(index).ok())
+        .filter(|driver| driver_capability(driver, "DCAP_VECTOR"))
+        .map(|driver| DriverInfo::of(&driver))
+        .collect()
+}
+
+/// Whether a driver declares a `DCAP_*` capability.
+fn driver_capability(driver: &gdal::Driver, capability: &str) -> bool {
+    driver_metadata_item(driver, capability)
+        .map_or(false, |value| value.eq_ignore_ascii_case("YES"))
+}
+
+/// A metadata item of a driver in the default domain (eg `DMD_EXTENSIONS`).
+pub(crate) fn driver_metadata_item(driver: &gdal::Driver, key: &str) -> Option<String> {
+    let c_key = CString::new(key).ok()?;
+    let c_value = unsafe {
+        gdal_sys::GDALGetMetadataItem(driver.c_driver(), c_key.as_ptr(), std::ptr::null())
+    };
+    if c_value.is_null() {
+        return None;
+    }
+
+    Some(crate::_string(c_value))
+}
+
+/// Whether the GDAL library this crate is linked against was built with the named driver.
+pub fn driver_available(name: &str) -> bool {
+    gdal::DriverManager::get_driver_by_name(name).is_ok()
+}
+
+/// Open a dataset, reporting [`Error::DriverUnavailable`] when the open fails because a required driver is missing.
+pub(crate) fn open_dataset(path: &str, options: gdal::DatasetOptions) -> Result<Dataset, Error> {
+    let _log_scope = crate::log_scope();
+    if let Some(allowed_drivers) = options.allowed_drivers {
+        if !allowed_drivers.is_empty() && !allowed_drivers.iter().any(|d| driver_available(d)) {
+            return Err(driver_unavailable(allowed_drivers[0]));
+        }
+    }
+
+    Dataset::open_ex(path, options).map_err(|e| match required_driver_for_path(path) {
+        Some(driver) if !driver_available(driver) => driver_unavailable(driver),
+        _ => e.into(),
+    })
+}
+
+/// Open a dataset to read features from, passing any read-specific open options derived from `params`.
+pub(crate) fn open_dataset_for_read(path: &str, params: &ReadParams) -> Result<Dataset, Error> {
+    let _config_scope = crate::ConfigScope::for_read(params)?;
+    let mut gdal_options: gdal::DatasetOptions = params.into();
+
+    let schema_option;
+    let mut open_options: Vec<&str> = params.open_options.unwrap_or_default().to_vec();
+    if let Some(column_types) = params.csv_column_types {
+        // Older GDALs silently ignore the open option
+        require_gdal_version("csv_column_types", 3110000, "3.11")?;
+        schema_option = format!("OGR_SCHEMA={}", csv_schema(path, column_types));
+        open_options.push(&schema_option);
+    }
+    if let Some(spreadsheet_options) = params.spreadsheet_options {
+        open_options.extend(spreadsheet_options.open_options());
+    }
+    if params.open_options.is_some() || !open_options.is_empty() {
+        gdal_options.open_options = Some(&open_options);
+    }
+
+    open_dataset(path, gdal_options)
+}
+
+/// Fail with [`Error::GdalVersionTooOld`] if the GDAL in use is older than `required_num`, in the format of GDAL's `VERSION_NUM`.
+fn require_gdal_version(
+    param: &'static str,
+    required_num: i32,
+    required: &'static str,
+) -> Result<(), Error> {
+    let version_num: i32 = gdal::version::version_info("VERSION_NUM")
+        .parse()
+        .unwrap_or_default();
+    if version_num >= required_num {
+        return Ok(());
+    }
+
+    Err(Error::GdalVersionTooOld {
+        param,
+        required,
+        found: gdal::version::version_info("RELEASE_NAME"),
+    })
+}
+
+/// Whether the driver of a dataset can create fields of an OGR type, given by its name in `DMD_CREATIONFIELDDATATYPES` (eg `"Time"`).
+///
+/// Drivers that don't advertise their field types are assumed to support all of them.
+pub(crate) fn dataset_supports_field_type(dataset: &Dataset, type_name: &str) -> bool {
+    driver_supports_field_type(&dataset.driver(), type_name)
+}
+
+/// Whether a driver can create fields of an OGR type, as with [`dataset_supports_field_type`].
+pub(crate) fn driver_supports_field_type(driver: &gdal::Driver, type_name: &str) -> bool {
+    let c_key = CString::new("DMD_CREATIONFIELDDATATYPES").unwrap();
+    let c_types = unsafe {
+        gdal_sys::GDALGetMetadataItem(driver.c_driver(), c_key.as_ptr(), std::ptr::null())
+    };
+    if c_types.is_null() {
+        return true;
+    }
+
+    crate::_string(c_types)
+        .split_whitespace()
+        .any(|t| t == type_name)
+}
+
+/// Whether the driver of a dataset lists a layer creation option (eg `"FID"`) in `DMD_LAYER_CREATIONOPTIONLIST`.
+pub(crate) fn dataset_supports_layer_creation_option(dataset: &Dataset, option: &str) -> bool {
+    let c_key = CString::new("DMD_LAYER_CREATIONOPTIONLIST").unwrap();
+    let c_options = unsafe {
+        let c_driver = gdal_sys::GDALGetDatasetDriver(dataset.c_dataset());
+        gdal_sys::GDALGetMetadataItem(c_driver, c_key.as_ptr(), std::ptr::null())
+    };
+    if c_options.is_null() {
+        return false;
+    }
+
+    crate::_string(c_options).contains(&format!("name='{}'", option))
+}
+
+/// Build an `OGR_SCHEMA` patch setting the types of the given columns of a CSV file.
+///
+/// The CSV driver names the layer after the file, so the layer name is taken from the path.
+fn csv_schema(path: &str, column_types: &[(&str, &str)]) -> String {
+    let layer_name = Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let fields: Vec<String> = column_types
+        .iter()
+        .map(|(name, field_type)| {
+            format!(
+                r#"{{"name":"{}","type":"{}"}}"#,
+                json_escape(name),
+                json_escape(field_type)
+            )
+        })
+        .collect();
+
+    format!(
+        r#"{{"layers":[{{"name":"{}","schema_type":"Patch","fields":[{}]}}]}}"#,
+        json_escape(&layer_name),
+        fields.join(",")
+    )
+}
+
+/// Guess the driver required to open a path from its connection prefix or file extension.
+fn required_driver_for_path(path: &str) -> Option<&'static str> {
+    let lower = path.to_ascii_lowercase();
+    let lower = lower.trim_end_matches('/');
+
+    if lower.starts_with("pg:") || lower.starts_with("postgresql://") || lower.starts_with("postgres://") {
+        Some("PostgreSQL")
+    } else if lower.ends_with(".parquet") || lower.ends_with(".geoparquet") {
+        Some("Parquet")
+    } else if lower.ends_with(".arrow") || lower.ends_with(".feather") || lower.ends_with(".arrows") {
+        Some("Arrow")
+    } else if lower.ends_with(".gdb") {
+        Some("OpenFileGDB")
+    } else if lower.ends_with(".gpkg") {
+        Some("GPKG")
+    } else if lower.ends_with(".fgb") {
+        Some("FlatGeobuf")
+    } else {
+        None
+    }
+}
+
+fn driver_unavailable(driver: &str) -> Error {
+    let hint = match driver {
+        "Parquet" | "Arrow" => "GDAL must be built against Apache Arrow and Parquet (cmake -DGDAL_USE_ARROW=ON -DGDAL_USE_PARQUET=ON, GDAL >= 3.5).".to_owned(),
+        "FileGDB" => "GDAL must be built against the ESRI FileGDB SDK (cmake -DGDAL_USE_FILEGDB=ON). The built-in `OpenFileGDB` driver can read most .gdb files without it.".to_owned(),
+        "OpenFileGDB" => "The `OpenFileGDB` driver is built into GDAL by default; check that it wasn't disabled (cmake -DOGR_ENABLE_DRIVER_OPENFILEGDB=ON).".to_owned(),
+        "PostgreSQL" | "PostGISRaster" => "GDAL must be built against libpq (cmake -DGDAL_USE_POSTGRESQL=ON).".to_owned(),
+        "SQLite" | "GPKG" => "GDAL must be built against SQLite3 (cmake -DGDAL_USE_SQLITE3=ON).".to_owned(),
+        _ => format!("GDAL was built without the `{}` driver. Run `ogrinfo --formats` to list available drivers, and rebuild GDAL with the driver enabled.", driver),
+    };
+
+    Error::DriverUnavailable {
+        driver: driver.to_owned(),
+        hint,
+    }
+}
@@ -0,0 +1,219 @@
+/// Typed open options for a driver, compiled down to the `NAME=VALUE` strings GDAL expects.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{CsvOpenOptions, DriverOpenOptions, ReadParamsOwned};
+///
+/// let csv_options = CsvOpenOptions {
+///     x_possible_names: vec!["Lon*".to_string()],
+///     y_possible_names: vec!["Lat*".to_string()],
+///     keep_geom_columns: Some(false),
+///     ..Default::default()
+/// };
+/// let mut params = ReadParamsOwned::default();
+/// params.open_options = Some(csv_options.to_options());
+/// let df = params.with_params(|params| polars_gdal::df_from_resource("countries.csv", Some(params)))?;
+/// ```
+pub trait DriverOpenOptions {
+    /// The options as `(name, value)` pairs. Options left unset aren't included, so they keep the driver default.
+    fn option_pairs(&self) -> Vec<(&'static str, String)>;
+
+    /// The options as `NAME=VALUE` strings, for `ReadParamsOwned::open_options`.
+    fn to_options(&self) -> Vec<String> {
+        join_options(self.option_pairs())
+    }
+}
+
+/// Typed layer creation options for a driver, compiled down to the `NAME=VALUE` strings GDAL expects.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{GeoJsonCreationOptions, VectorDriver, WriteParams};
+///
+/// let params = WriteParams::builder()
+///     .creation_options(&GeoJsonCreationOptions {
+///         rfc7946: true,
+///         coordinate_precision: Some(6),
+///         ..Default::default()
+///     })
+///     .build()?;
+/// let bytes = params.with_params(|params| polars_gdal::gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)))?;
+/// ```
+pub trait LayerCreationOptions {
+    /// The options as `(name, value)` pairs. Options left unset aren't included, so they keep the driver default.
+    fn option_pairs(&self) -> Vec<(&'static str, String)>;
+
+    /// The options as `NAME=VALUE` strings, for `WriteParamsOwned::options`.
+    fn to_options(&self) -> Vec<String> {
+        join_options(self.option_pairs())
+    }
+}
+
+/// Open options of the CSV driver. See <https://gdal.org/drivers/vector/csv.html#open-options>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CsvOpenOptions {
+    /// Patterns of the names of the column holding X coordinates (eg `Lon*`), from which point geometries are read.
+    pub x_possible_names: Vec<String>,
+
+    /// Patterns of the names of the column holding Y coordinates (eg `Lat*`).
+    pub y_possible_names: Vec<String>,
+
+    /// Patterns of the names of the column holding Z coordinates.
+    pub z_possible_names: Vec<String>,
+
+    /// Patterns of the names of the column holding WKT or hex WKB geometries.
+    pub geom_possible_names: Vec<String>,
+
+    /// Whether the columns geometries are read from are also kept as attribute columns.
+    pub keep_geom_columns: Option<bool>,
+
+    /// Whether to detect column types from the values, rather than reading every column as a string.
+    pub autodetect_type: Option<bool>,
+
+    /// Whether empty values are read as nulls, rather than empty strings.
+    pub empty_string_as_null: Option<bool>,
+}
+
+impl DriverOpenOptions for CsvOpenOptions {
+    fn option_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut options = vec![];
+        push_list(&mut options, "X_POSSIBLE_NAMES", &self.x_possible_names);
+        push_list(&mut options, "Y_POSSIBLE_NAMES", &self.y_possible_names);
+        push_list(&mut options, "Z_POSSIBLE_NAMES", &self.z_possible_names);
+        push_list(
+            &mut options,
+            "GEOM_POSSIBLE_NAMES",
+            &self.geom_possible_names,
+        );
+        push_bool(&mut options, "KEEP_GEOM_COLUMNS", self.keep_geom_columns);
+        push_bool(&mut options, "AUTODETECT_TYPE", self.autodetect_type);
+        push_bool(
+            &mut options,
+            "EMPTY_STRING_AS_NULL",
+            self.empty_string_as_null,
+        );
+        options
+    }
+}
+
+/// Layer creation options of the GeoJSON driver. See <https://gdal.org/drivers/vector/geojson.html#layer-creation-options>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GeoJsonCreationOptions {
+    /// Write RFC 7946 GeoJSON: coordinates are reprojected to WGS84, rounded to 7 decimals by default, and polygons are
+    /// wound counterclockwise.
+    pub rfc7946: bool,
+
+    /// The maximum number of decimals of coordinates.
+    pub coordinate_precision: Option<u32>,
+
+    /// The maximum number of significant figures of floating point attribute values.
+    pub significant_figures: Option<u32>,
+
+    /// Whether to write the bounding box of each feature.
+    pub write_bbox: bool,
+}
+
+impl LayerCreationOptions for GeoJsonCreationOptions {
+    fn option_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut options = vec![];
+        if self.rfc7946 {
+            options.push(("RFC7946", "YES".to_owned()));
+        }
+        if let Some(precision) = self.coordinate_precision {
+            options.push(("COORDINATE_PRECISION", precision.to_string()));
+        }
+        if let Some(figures) = self.significant_figures {
+            options.push(("SIGNIFICANT_FIGURES", figures.to_string()));
+        }
+        if self.write_bbox {
+            options.push(("WRITE_BBOX", "YES".to_owned()));
+        }
+        options
+    }
+}
+
+/// Open options of the ESRI Shapefile driver. See <https://gdal.org/drivers/vector/shapefile.html#open-options>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShapefileOpenOptions {
+    /// The encoding of the `.dbf` file (eg `CP1252`), overriding the one declared in the `.cpg` file or the `.dbf` header.
+    /// An empty string reads the values without recoding them.
+    pub encoding: Option<String>,
+}
+
+impl DriverOpenOptions for ShapefileOpenOptions {
+    fn option_pairs(&self) -> Vec<(&'static str, String)> {
+        self.encoding
+            .iter()
+            .map(|encoding| ("ENCODING", encoding.clone()))
+            .collect()
+    }
+}
+
+/// Layer creation options of the ESRI Shapefile driver. See <https://gdal.org/drivers/vector/shapefile.html#layer-creation-options>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShapefileCreationOptions {
+    /// The encoding the `.dbf` file is written in (eg `CP1252`), which is declared in a `.cpg` file. Defaults to `LDID/87` (ISO-8859-1).
+    pub encoding: Option<String>,
+
+    /// Whether to write a `.qix` spatial index.
+    pub spatial_index: bool,
+}
+
+impl LayerCreationOptions for ShapefileCreationOptions {
+    fn option_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut options = vec![];
+        if let Some(encoding) = &self.encoding {
+            options.push(("ENCODING", encoding.clone()));
+        }
+        if self.spatial_index {
+            options.push(("SPATIAL_INDEX", "YES".to_owned()));
+        }
+        options
+    }
+}
+
+/// Layer creation options of the GPKG driver. See <https://gdal.org/drivers/vector/gpkg.html#layer-creation-options>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GpkgCreationOptions {
+    /// Whether to create an R-tree spatial index. GDAL creates one by default.
+    pub spatial_index: Option<bool>,
+
+    /// A human-readable identifier of the layer, written to the `gpkg_contents` table.
+    pub identifier: Option<String>,
+
+    /// A description of the layer, written to the `gpkg_contents` table.
+    pub description: Option<String>,
+}
+
+impl LayerCreationOptions for GpkgCreationOptions {
+    fn option_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut options = vec![];
+        push_bool(&mut options, "SPATIAL_INDEX", self.spatial_index);
+        if let Some(identifier) = &self.identifier {
+            options.push(("IDENTIFIER", identifier.clone()));
+        }
+        if let Some(description) = &self.description {
+            options.push(("DESCRIPTION", description.clone()));
+        }
+        options
+    }
+}
+
+fn push_list(options: &mut Vec<(&'static str, String)>, name: &'static str, values: &[String]) {
+    if !values.is_empty() {
+        options.push((name, values.join(",")));
+    }
+}
+
+fn push_bool(options: &mut Vec<(&'static str, String)>, name: &'static str, value: Option<bool>) {
+    if let Some(value) = value {
+        options.push((name, if value { "YES" } else { "NO" }.to_owned()));
+    }
+}
+
+fn join_options(pairs: Vec<(&'static str, String)>) -> Vec<String> {
+    pairs
+        .into_iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect()
+}
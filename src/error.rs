@@ -29,6 +29,38 @@ pub enum Error {
     #[error("Feature limit of {0} features reached")]
     FeatureLimitReached(usize),
 
+    /// Hard column limit reached
+    #[error("Column limit of {0} columns reached")]
+    ColumnLimitReached(usize),
+
+    /// Hard string length limit reached
+    #[error("Value in field `{0}` exceeds the string length limit of {1} bytes")]
+    StringLengthLimitReached(String, usize),
+
+    /// Hard geometry vertex limit reached
+    #[error("Geometry of feature {0} exceeds the vertex limit of {1} vertices")]
+    VertexLimitReached(usize, usize),
+
+    /// A feature (or DataFrame row, when writing) has no geometry, or an empty geometry, with [`NullGeometryPolicy::Error`](crate::NullGeometryPolicy::Error)
+    #[error("Feature {0} has a null or empty geometry")]
+    NullGeometry(usize),
+
+    /// A field has a value of another type than the rest of the column, eg a field whose type varies between features
+    #[error("Unexpected value `{value}` in column `{column}` at feature {feature_index}")]
+    UnexpectedFieldValue {
+        column: String,
+        feature_index: usize,
+        value: String,
+    },
+
+    /// A field has a type that can't be read yet (eg list fields)
+    #[error("Field `{0}` has a type that is not supported yet")]
+    UnsupportedFieldType(String),
+
+    /// Hard input size limit reached
+    #[error("Input of {0} bytes exceeds the limit of {1} bytes")]
+    InputTooLarge(usize, usize),
+
     /// Geomery column name collision
     #[error("Field named `{0}` not allowed as it would conflict with the geometry column")]
     GeometryColumnCollision(String),
@@ -52,4 +84,134 @@ pub enum Error {
     /// Cannot find geometry column in dataframe
     #[error("Cannot find geometry column `{0}` in dataframe")]
     CannotFindGeometryColumn(String),
+
+    /// Unable to read the contents of an archive
+    #[error("Unable to read archive `{0}`")]
+    ArchiveNotReadable(String),
+
+    /// An archive holds no vector file
+    #[error("No vector file found in archive `{0}`")]
+    NoVectorFileInArchive(String),
+
+    /// An archive holds several vector files, so the one to read must be selected
+    #[error("Archive `{archive}` holds several vector files ({}), select one with `{archive}!<file>`", .members.join(", "))]
+    AmbiguousArchive { archive: String, members: Vec<String> },
+
+    /// A parameter needs a newer GDAL than the one in use
+    #[error("`{param}` requires GDAL >= {required}, but GDAL {found} is in use")]
+    GdalVersionTooOld {
+        param: &'static str,
+        required: &'static str,
+        found: String,
+    },
+
+    /// GDAL was built without a required driver
+    #[error("GDAL driver `{driver}` is not available. {hint}")]
+    DriverUnavailable { driver: String, hint: String },
+
+    /// IO Error
+    #[error("IO Error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// No attachments found for a layer
+    #[error("No attachments found for layer `{0}`")]
+    NoAttachments(String),
+
+    /// SQL query did not return a result set
+    #[error("SQL query did not return a result set: `{0}`")]
+    NoResultSet(String),
+
+    /// The data read did not match the expected schema
+    #[error("Data does not match the expected schema: {}", .0.iter().map(|m| m.to_string()).collect::<Vec<_>>().join("; "))]
+    SchemaMismatch(Vec<SchemaMismatch>),
+
+    /// Reprojection was requested but the source has no SRS
+    #[error("Unable to reproject geometries since the source SRS is unknown")]
+    MissingSourceSrs,
+
+    /// GeoJSON Error
+    #[cfg(feature = "geojson")]
+    #[error("GeoJSON Error: {0}")]
+    GeoJson(#[from] geojson::Error),
+
+    /// GDAL wrote bytes that aren't valid UTF-8
+    #[error("Invalid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+
+    /// A geometry could not be parsed
+    #[error("Invalid geometry: {0}")]
+    InvalidGeometry(String),
+
+    /// A geometry in a DataFrame could not be parsed. `preview` is the start of the value, as hex for WKB.
+    #[error("Malformed geometry in column `{column}` at row {row}: {reason} (value starts `{preview}`)")]
+    MalformedGeometry {
+        row: usize,
+        column: String,
+        preview: String,
+        reason: String,
+    },
+
+    /// A write option isn't supported by the driver
+    #[error("`{option}` is not supported by the `{driver}` driver")]
+    UnsupportedByDriver { option: String, driver: String },
+
+    /// A write with `WriteParams::strict` would have lost or changed data
+    #[error("Strict write failed: {0}")]
+    StrictWrite(String),
+
+    /// A dataset has no layer of the given name
+    #[error("Layer `{0}` not found")]
+    LayerNotFound(String),
+
+    /// No driver is known for the extension of a path
+    #[error("Cannot determine a driver from the extension of `{0}`")]
+    UnknownExtension(String),
+
+    /// No vector driver recognizes the format of some data
+    #[error("No vector driver recognizes the data")]
+    UnrecognizedFormat,
+
+    /// A driver wrote several files where a single one was expected
+    #[error("The driver wrote several files ({}), use `gdal_files_from_df` to get them all", .0.join(", "))]
+    MultipleFiles(Vec<String>),
+
+    /// Parameters were set that can't be used together
+    #[error("Conflicting parameters: {0}")]
+    ConflictingParams(String),
+
+    /// A read or write was cancelled through `ReadParams::cancel` or `WriteParams::cancel`
+    #[error("Cancelled")]
+    Cancelled,
+
+    /// A length parameter, such as `snap_to_grid`, was zero, negative or not finite
+    #[error("`{param}` must be a positive finite number, got {value}")]
+    InvalidLength { param: &'static str, value: f64 },
+}
+
+/// A single difference between the data read and the expected schema.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SchemaMismatch {
+    /// An expected column is missing
+    #[error("missing column `{0}`")]
+    MissingColumn(String),
+
+    /// A column is present that isn't in the expected schema
+    #[error("unexpected column `{0}`")]
+    UnexpectedColumn(String),
+
+    /// A column has the wrong dtype
+    #[error("column `{column}` has type `{actual}`, expected `{expected}`")]
+    ColumnType {
+        column: String,
+        expected: polars::datatypes::DataType,
+        actual: polars::datatypes::DataType,
+    },
+
+    /// A feature has the wrong geometry type
+    #[error("feature {feature_index} has geometry type {actual}, expected {expected}")]
+    GeometryType {
+        feature_index: usize,
+        expected: gdal::vector::OGRwkbGeometryType::Type,
+        actual: gdal::vector::OGRwkbGeometryType::Type,
+    },
 }
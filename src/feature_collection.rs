@@ -0,0 +1,36 @@
+use crate::{df_from_bytes, gdal_bytes_from_df, Error, ReadParams, VectorDriver, WriteParams};
+use geojson::{FeatureCollection, GeoJson};
+use polars::prelude::*;
+
+/// Convert a parsed GeoJSON FeatureCollection into a DataFrame.
+///
+/// Property types are detected by the GDAL GeoJSON driver, exactly as when reading a GeoJSON file with [`df_from_bytes`].
+///
+/// # Example
+/// ```rust # ignore
+/// let collection: geojson::FeatureCollection = request_body.parse()?;
+/// let df = polars_gdal::df_from_feature_collection(&collection, None)?;
+/// ```
+pub fn df_from_feature_collection(
+    collection: &FeatureCollection,
+    params: Option<ReadParams>,
+) -> Result<DataFrame, Error> {
+    let mut params = params.unwrap_or_default();
+    params.allowed_drivers = Some(&["GeoJSON"]);
+
+    let geojson = collection.to_string();
+    df_from_bytes(geojson.as_bytes(), Some("layer.geojson"), Some(params))
+}
+
+/// Convert a DataFrame into a GeoJSON FeatureCollection.
+///
+/// The DataFrame is written with the GDAL GeoJSON driver, exactly as with [`gdal_bytes_from_df`].
+pub fn feature_collection_from_df(
+    df: &DataFrame,
+    params: Option<WriteParams>,
+) -> Result<FeatureCollection, Error> {
+    let bytes = gdal_bytes_from_df(df, &VectorDriver::GeoJson, params)?;
+
+    let geojson: GeoJson = String::from_utf8(bytes)?.parse()?;
+    Ok(FeatureCollection::try_from(geojson)?)
+}
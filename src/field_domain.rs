@@ -0,0 +1,150 @@
+use crate::Error;
+use gdal::errors::GdalError;
+use gdal::vector::LayerAccess;
+use gdal::Dataset;
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::ffi::CString;
+
+/// The name of the field domain of a layer's field, if it has one. The gdal crate doesn't expose field domains.
+pub(crate) fn field_domain_name<L: LayerAccess>(layer: &L, field_idx: i32) -> Option<String> {
+    let c_domain_name = unsafe {
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+        let c_field_defn = gdal_sys::OGR_FD_GetFieldDefn(c_defn, field_idx);
+        if c_field_defn.is_null() {
+            return None;
+        }
+        gdal_sys::OGR_Fld_GetDomainName(c_field_defn)
+    };
+    if c_domain_name.is_null() {
+        return None;
+    }
+
+    let domain_name = crate::_string(c_domain_name);
+    if domain_name.is_empty() {
+        None
+    } else {
+        Some(domain_name)
+    }
+}
+
+/// The codes of a coded-value field domain mapped to their values, or `None` if the dataset has no coded domain of that name.
+///
+/// Codes without a value map to themselves.
+pub(crate) fn coded_values(dataset: &Dataset, domain_name: &str) -> Option<HashMap<String, String>> {
+    let c_name = CString::new(domain_name).ok()?;
+    let c_domain = unsafe { gdal_sys::GDALDatasetGetFieldDomain(dataset.c_dataset(), c_name.as_ptr()) };
+    if c_domain.is_null()
+        || unsafe { gdal_sys::OGR_FldDomain_GetDomainType(c_domain) }
+            != gdal_sys::OGRFieldDomainType::OFDT_CODED
+    {
+        return None;
+    }
+
+    let mut values = HashMap::new();
+    let mut c_value = unsafe { gdal_sys::OGR_CodedFldDomain_GetEnumeration(c_domain) };
+    while !c_value.is_null() && !unsafe { (*c_value).pszCode }.is_null() {
+        let code = crate::_string(unsafe { (*c_value).pszCode });
+        let value = match unsafe { (*c_value).pszValue } {
+            c_value if c_value.is_null() => code.clone(),
+            c_value => crate::_string(c_value),
+        };
+        values.insert(code, value);
+        c_value = unsafe { c_value.add(1) };
+    }
+
+    Some(values)
+}
+
+/// Replace the codes of a column with their coded values, returning a Categorical column.
+pub(crate) fn decode_coded_values(
+    series: &Series,
+    values: &HashMap<String, String>,
+) -> Result<Series, Error> {
+    let decoded: Utf8Chunked = series
+        .cast(&DataType::Utf8)?
+        .utf8()?
+        .into_iter()
+        .map(|code| code.map(|code| values.get(code).map_or(code, |value| value.as_str())))
+        .collect();
+    let mut decoded = decoded.into_series();
+    decoded.rename(series.name());
+
+    Ok(decoded.cast(&DataType::Categorical(None))?)
+}
+
+/// Whether the driver of a dataset can store field domains.
+pub(crate) fn supports_field_domains(dataset: &Dataset) -> bool {
+    let c_capability = CString::new("AddFieldDomain").unwrap();
+    unsafe { gdal_sys::GDALDatasetTestCapability(dataset.c_dataset(), c_capability.as_ptr()) == 1 }
+}
+
+/// Add a coded-value String field domain to a dataset, whose codes are the given categories. An existing domain of the same name is kept.
+pub(crate) fn add_coded_domain(
+    dataset: &mut Dataset,
+    domain_name: &str,
+    categories: &[&str],
+) -> Result<(), Error> {
+    let c_name = CString::new(domain_name).map_err(GdalError::from)?;
+    if !unsafe { gdal_sys::GDALDatasetGetFieldDomain(dataset.c_dataset(), c_name.as_ptr()) }
+        .is_null()
+    {
+        return Ok(());
+    }
+
+    let c_codes = categories
+        .iter()
+        .map(|c| CString::new(*c))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(GdalError::from)?;
+    let mut enumeration: Vec<gdal_sys::OGRCodedValue> = c_codes
+        .iter()
+        .map(|c| gdal_sys::OGRCodedValue {
+            pszCode: c.as_ptr() as *mut _,
+            pszValue: std::ptr::null_mut(),
+        })
+        .collect();
+    enumeration.push(gdal_sys::OGRCodedValue {
+        pszCode: std::ptr::null_mut(),
+        pszValue: std::ptr::null_mut(),
+    });
+
+    let c_description = CString::new("").unwrap();
+    let mut c_failure_reason: *mut std::ffi::c_char = std::ptr::null_mut();
+    let added = unsafe {
+        let c_domain = gdal_sys::OGR_CodedFldDomain_Create(
+            c_name.as_ptr(),
+            c_description.as_ptr(),
+            gdal_sys::OGRFieldType::OFTString,
+            gdal_sys::OGRFieldSubType::OFSTNone,
+            enumeration.as_ptr(),
+        );
+        if c_domain.is_null() {
+            return Err(crate::_last_null_pointer_err("OGR_CodedFldDomain_Create").into());
+        }
+        let added = gdal_sys::GDALDatasetAddFieldDomain(
+            dataset.c_dataset(),
+            c_domain,
+            &mut c_failure_reason,
+        );
+        gdal_sys::OGR_FldDomain_Destroy(c_domain);
+        added
+    };
+    if !added {
+        let msg = if c_failure_reason.is_null() {
+            format!("Unable to add field domain {}", domain_name)
+        } else {
+            let msg = crate::_string(c_failure_reason);
+            unsafe { gdal_sys::VSIFree(c_failure_reason as *mut std::ffi::c_void) };
+            msg
+        };
+        return Err(GdalError::CplError {
+            class: gdal_sys::CPLErr::CE_Failure,
+            number: 1, // CPLE_AppDefined
+            msg,
+        }
+        .into());
+    }
+
+    Ok(())
+}
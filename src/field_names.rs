@@ -0,0 +1,145 @@
+use crate::Error;
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// A field renamed by [`sanitize_field_names`] so that it can be written by a driver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldRename {
+    /// The column name in the original DataFrame.
+    pub original: String,
+
+    /// The column name that will be written.
+    pub renamed: String,
+
+    /// Why the column was renamed.
+    pub reason: FieldRenameReason,
+}
+
+/// Why a field was renamed by [`sanitize_field_names`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldRenameReason {
+    /// The name is longer than the driver allows, eg Shapefile field names are limited to 10 characters.
+    TooLong,
+
+    /// The name is reserved by the driver, eg `fid` in a GeoPackage.
+    Reserved,
+
+    /// The driver would launder the name, eg PostgreSQL folds names to lowercase.
+    Laundered,
+
+    /// The name collides with another field once sanitized, or once compared case-insensitively.
+    Duplicate,
+}
+
+/// Rename the attribute columns of a DataFrame so that they can be written by the named driver, returning the renamed DataFrame and a report of every rename.
+///
+/// Drivers often rename fields themselves (or fail outright) when a name doesn't fit the format, which can silently collide two columns.
+/// Sanitizing first makes the renames explicit:
+///
+/// - `ESRI Shapefile`: names are truncated to 10 characters.
+/// - `GPKG`: `fid` is reserved for the feature ID column.
+/// - `PostgreSQL`: names are lowercased, characters other than `a-z`, `0-9` and `_` are replaced with `_`, and names are truncated to 63 characters.
+///
+/// For all drivers, names that collide case-insensitively are deduplicated with a `_1`, `_2`... suffix. The geometry column is never renamed.
+///
+/// # Example
+/// ```rust # ignore
+/// let (df, renames) = polars_gdal::sanitize_field_names(&df, "ESRI Shapefile", "geometry")?;
+/// for rename in renames {
+///     println!("{} -> {} ({:?})", rename.original, rename.renamed, rename.reason);
+/// }
+/// ```
+pub fn sanitize_field_names(
+    df: &DataFrame,
+    driver_name: &str,
+    geometry_column_name: &str,
+) -> Result<(DataFrame, Vec<FieldRename>), Error> {
+    let max_len = match driver_name {
+        "ESRI Shapefile" => Some(10),
+        "PostgreSQL" => Some(63),
+        _ => None,
+    };
+    let reserved: &[&str] = match driver_name {
+        "GPKG" => &["fid"],
+        _ => &[],
+    };
+
+    let mut renames = vec![];
+    let mut taken: HashSet<String> = HashSet::new();
+    taken.insert(geometry_column_name.to_lowercase());
+
+    let mut names = Vec::with_capacity(df.width());
+    for name in df.get_column_names() {
+        if name == geometry_column_name {
+            names.push(name.to_owned());
+            continue;
+        }
+
+        let mut renamed = name.to_owned();
+        let mut reason = None;
+        if driver_name == "PostgreSQL" {
+            let laundered = launder_pg(&renamed);
+            if laundered != renamed {
+                renamed = laundered;
+                reason = Some(FieldRenameReason::Laundered);
+            }
+        }
+        if let Some(max_len) = max_len {
+            if renamed.chars().count() > max_len {
+                renamed = renamed.chars().take(max_len).collect();
+                reason = Some(FieldRenameReason::TooLong);
+            }
+        }
+        if reserved.contains(&renamed.to_lowercase().as_str()) {
+            renamed = format!("{}_1", renamed);
+            reason = Some(FieldRenameReason::Reserved);
+        }
+        if taken.contains(&renamed.to_lowercase()) {
+            renamed = deduplicate(&renamed, &taken, max_len);
+            reason = Some(FieldRenameReason::Duplicate);
+        }
+
+        taken.insert(renamed.to_lowercase());
+        if let Some(reason) = reason {
+            renames.push(FieldRename {
+                original: name.to_owned(),
+                renamed: renamed.clone(),
+                reason,
+            });
+        }
+        names.push(renamed);
+    }
+
+    let mut df = df.clone();
+    df.set_column_names(&names)?;
+
+    Ok((df, renames))
+}
+
+/// Launder a name the same way the PostgreSQL driver does with `LAUNDER=YES`.
+fn launder_pg(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Find the first `name_N` that isn't taken, truncating `name` so the result fits in `max_len` characters.
+fn deduplicate(name: &str, taken: &HashSet<String>, max_len: Option<usize>) -> String {
+    (1..)
+        .map(|i| {
+            let suffix = format!("_{}", i);
+            let keep = max_len
+                .map(|max_len| max_len.saturating_sub(suffix.len()))
+                .unwrap_or(usize::MAX);
+            format!("{}{}", name.chars().take(keep).collect::<String>(), suffix)
+        })
+        .find(|candidate| !taken.contains(&candidate.to_lowercase()))
+        .unwrap()
+}
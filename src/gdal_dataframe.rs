@@ -0,0 +1,176 @@
+use crate::{
+    df_from_resource_full, gdal_bytes_from_df, gdal_layer_from_df, gdal_resource_from_df, Error,
+    GeometryColumnSpec, GeometryFormat, ReadParams, ResourceDataFrame, ToGdalDriver, WriteParams,
+};
+use gdal::spatial_ref::SpatialRef;
+use gdal::Dataset;
+use polars::prelude::*;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+/// A DataFrame that remembers its CRS and how its geometry column is encoded.
+///
+/// Derefs to [`DataFrame`], so it can be used anywhere a DataFrame can.
+/// Writing it with [`GdalDataFrame::to_gdal_bytes`] or [`GdalDataFrame::to_gdal_resource`] fills in the SRS, geometry column name and geometry format,
+/// so a read followed by a write is lossless without re-specifying them in `WriteParams`.
+///
+/// # Example
+/// ```rust # ignore
+/// let gdf = polars_gdal::GdalDataFrame::from_resource("my_shapefile.shp", None)?;
+/// let json_driver = polars_gdal::driver_by_name("GeoJSON")?;
+/// let geojson_bytes = gdf.to_gdal_bytes(&json_driver, None)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GdalDataFrame {
+    df: DataFrame,
+    srs: Option<SpatialRef>,
+    geometry_column_name: String,
+    geometry_format: GeometryFormat,
+}
+
+impl GdalDataFrame {
+    /// Wrap a DataFrame with its CRS and geometry column.
+    pub fn new(
+        df: DataFrame,
+        srs: Option<SpatialRef>,
+        geometry_column: GeometryColumnSpec,
+    ) -> Self {
+        Self {
+            df,
+            srs,
+            geometry_column_name: geometry_column.name.to_owned(),
+            geometry_format: geometry_column.format,
+        }
+    }
+
+    /// Read a resource, keeping the layer's CRS. See [`df_from_resource_full`].
+    pub fn from_resource<P: AsRef<Path>>(
+        path: P,
+        params: Option<ReadParams>,
+    ) -> Result<Self, Error> {
+        let geometry_format = params
+            .as_ref()
+            .map(|p| p.geometry_format)
+            .unwrap_or_default();
+        let resource = df_from_resource_full(path, params)?;
+        let srs = resource_srs(&resource)?;
+
+        Ok(Self {
+            df: resource.df,
+            srs,
+            geometry_column_name: resource.geometry_column_name,
+            geometry_format,
+        })
+    }
+
+    /// The CRS of the geometry column, if known.
+    pub fn srs(&self) -> Option<&SpatialRef> {
+        self.srs.as_ref()
+    }
+
+    /// Set the CRS of the geometry column, without reprojecting any geometries.
+    pub fn set_srs(&mut self, srs: Option<SpatialRef>) {
+        self.srs = srs;
+    }
+
+    /// The geometry column name and format.
+    pub fn geometry_column(&self) -> GeometryColumnSpec<'_> {
+        GeometryColumnSpec {
+            name: &self.geometry_column_name,
+            format: self.geometry_format,
+        }
+    }
+
+    /// Unwrap the DataFrame, discarding the CRS and geometry column information.
+    pub fn into_inner(self) -> DataFrame {
+        self.df
+    }
+
+    /// Fill in the SRS, geometry column name and geometry format of `params` from this frame.
+    ///
+    /// The geometry column name and format always come from this frame. The SRS is only filled in when `params` doesn't set one;
+    /// if `params` sets a `target_srs`, this frame's SRS is used as the `source_srs`.
+    pub fn write_params<'a>(&'a self, params: Option<WriteParams<'a>>) -> WriteParams<'a> {
+        let mut params = params.unwrap_or_default();
+        params.geometry_column_name = Some(&self.geometry_column_name);
+        params.geometry_format = self.geometry_format;
+        if params.target_srs.is_some() {
+            params.source_srs = params.source_srs.or(self.srs.as_ref());
+        } else {
+            params.srs = params.srs.or(self.srs.as_ref());
+        }
+        params
+    }
+
+    /// Write this frame to a new layer of a pre-existing dataset. See [`gdal_layer_from_df`].
+    pub fn to_gdal_layer<'d>(
+        &self,
+        dataset: &'d mut Dataset,
+        params: Option<WriteParams>,
+    ) -> Result<gdal::vector::Layer<'d>, Error> {
+        gdal_layer_from_df(&self.df, dataset, Some(self.write_params(params)))
+    }
+
+    /// Write this frame to bytes in a GDAL geospatial format. See [`gdal_bytes_from_df`].
+    pub fn to_gdal_bytes<D: ToGdalDriver + ?Sized>(
+        &self,
+        driver: &D,
+        params: Option<WriteParams>,
+    ) -> Result<Vec<u8>, Error> {
+        gdal_bytes_from_df(&self.df, driver, Some(self.write_params(params)))
+    }
+
+    /// Write this frame to a GDAL resource path. See [`gdal_resource_from_df`].
+    pub fn to_gdal_resource<D: ToGdalDriver + ?Sized, P: AsRef<Path>>(
+        &self,
+        driver: &D,
+        path: P,
+        params: Option<WriteParams>,
+    ) -> Result<Dataset, Error> {
+        gdal_resource_from_df(&self.df, driver, path, Some(self.write_params(params)))
+    }
+}
+
+impl Deref for GdalDataFrame {
+    type Target = DataFrame;
+
+    fn deref(&self) -> &DataFrame {
+        &self.df
+    }
+}
+
+impl DerefMut for GdalDataFrame {
+    fn deref_mut(&mut self) -> &mut DataFrame {
+        &mut self.df
+    }
+}
+
+impl From<GdalDataFrame> for DataFrame {
+    fn from(gdf: GdalDataFrame) -> Self {
+        gdf.df
+    }
+}
+
+impl TryFrom<ResourceDataFrame> for GdalDataFrame {
+    type Error = Error;
+
+    /// Convert a [`ResourceDataFrame`] read with the default WKB geometry format.
+    fn try_from(resource: ResourceDataFrame) -> Result<Self, Error> {
+        let srs = resource_srs(&resource)?;
+
+        Ok(Self {
+            df: resource.df,
+            srs,
+            geometry_column_name: resource.geometry_column_name,
+            geometry_format: GeometryFormat::default(),
+        })
+    }
+}
+
+/// Rebuild the SRS of a resource, preferring PROJJSON since WKT1 can't represent every CRS.
+fn resource_srs(resource: &ResourceDataFrame) -> Result<Option<SpatialRef>, Error> {
+    let definition = resource.srs_projjson.as_ref().or(resource.srs_wkt.as_ref());
+    Ok(definition
+        .map(|d| SpatialRef::from_definition(d))
+        .transpose()?)
+}
@@ -0,0 +1,1020 @@
+//! Helpers for working with geometry columns.
+
+#[cfg(feature = "lazy")]
+pub mod expr;
+
+use crate::{Error, GeometryColumnSpec, GeometryFormat};
+use gdal::errors::GdalError;
+use gdal::spatial_ref::{CoordTransform, SpatialRef};
+use gdal::vector::{Geometry, OGRwkbGeometryType};
+use polars::prelude::*;
+
+/// The byte order of WKB geometries.
+///
+/// Geometries read by this crate are always encoded as little-endian WKB, regardless of the platform or the source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Little-endian (NDR) byte order.
+    LittleEndian,
+
+    /// Big-endian (XDR) byte order.
+    BigEndian,
+}
+
+impl Default for ByteOrder {
+    fn default() -> Self {
+        Self::LittleEndian
+    }
+}
+
+/// Re-encode a Binary series of WKB geometries with the given byte order.
+///
+/// WKB produced by other tools may use either byte order, so two columns describing identical geometries can have different bytes.
+/// Normalizing both columns to the same byte order makes equality comparisons, hashing, and joins on the WKB column reliable.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::geom::{normalize_wkb, ByteOrder};
+///
+/// let normalized = normalize_wkb(df.column("geometry")?, ByteOrder::LittleEndian)?;
+/// ```
+pub fn normalize_wkb(series: &Series, byte_order: ByteOrder) -> Result<Series, Error> {
+    let ca: BinaryChunked = series
+        .binary()?
+        .into_iter()
+        .map(|wkb| wkb.map(|wkb| wkb_with_byte_order(wkb, byte_order)).transpose())
+        .collect::<Result<_, Error>>()?;
+
+    let mut normalized = ca.into_series();
+    normalized.rename(series.name());
+    Ok(normalized)
+}
+
+/// Convert a Binary series of WKB geometries to a Utf8 series of WKT geometries.
+///
+/// With the `wkt-wkb` feature enabled, this is done in pure Rust with the `wkb` and `wkt` crates instead of going through GDAL.
+pub fn wkb_to_wkt(series: &Series) -> Result<Series, Error> {
+    let ca: Utf8Chunked = series
+        .binary()?
+        .into_iter()
+        .map(|wkb| wkb.map(wkb_value_to_wkt).transpose())
+        .collect::<Result<_, Error>>()?;
+
+    let mut wkt = ca.into_series();
+    wkt.rename(series.name());
+    Ok(wkt)
+}
+
+/// Convert a Utf8 series of WKT geometries to a Binary series of little-endian WKB geometries.
+///
+/// With the `wkt-wkb` feature enabled, this is done in pure Rust with the `wkb` and `wkt` crates instead of going through GDAL.
+pub fn wkt_to_wkb(series: &Series) -> Result<Series, Error> {
+    let ca: BinaryChunked = series
+        .utf8()?
+        .into_iter()
+        .map(|wkt| wkt.map(wkt_value_to_wkb).transpose())
+        .collect::<Result<_, Error>>()?;
+
+    let mut wkb = ca.into_series();
+    wkb.rename(series.name());
+    Ok(wkb)
+}
+
+#[cfg(feature = "wkt-wkb")]
+fn wkb_value_to_wkt(wkb: &[u8]) -> Result<String, Error> {
+    use wkt::ToWkt;
+
+    let geometry = wkb::wkb_to_geom(&mut &wkb[..])
+        .map_err(|e| Error::InvalidGeometry(format!("{:?}", e)))?;
+    Ok(geometry.wkt_string())
+}
+
+#[cfg(not(feature = "wkt-wkb"))]
+fn wkb_value_to_wkt(wkb: &[u8]) -> Result<String, Error> {
+    Ok(Geometry::from_wkb(wkb)?.wkt()?)
+}
+
+#[cfg(feature = "wkt-wkb")]
+fn wkt_value_to_wkb(wkt: &str) -> Result<Vec<u8>, Error> {
+    use std::str::FromStr;
+
+    let wkt = wkt::Wkt::<f64>::from_str(wkt).map_err(|e| Error::InvalidGeometry(e.to_string()))?;
+    let geometry: geo_types::Geometry<f64> = wkt
+        .try_into()
+        .map_err(|e: wkt::conversion::Error| Error::InvalidGeometry(e.to_string()))?;
+    wkb::geom_to_wkb(&geometry).map_err(|e| Error::InvalidGeometry(format!("{:?}", e)))
+}
+
+#[cfg(not(feature = "wkt-wkb"))]
+fn wkt_value_to_wkb(wkt: &str) -> Result<Vec<u8>, Error> {
+    geometry_to_wkb(&Geometry::from_wkt(wkt)?, ByteOrder::LittleEndian)
+}
+
+/// Encode a WKB geometry with the given byte order.
+pub(crate) fn wkb_with_byte_order(wkb: &[u8], byte_order: ByteOrder) -> Result<Vec<u8>, Error> {
+    let geometry = Geometry::from_wkb(wkb)?;
+    geometry_to_wkb(&geometry, byte_order)
+}
+
+/// Export a geometry to WKB with the given byte order.
+pub(crate) fn geometry_to_wkb(geometry: &Geometry, byte_order: ByteOrder) -> Result<Vec<u8>, Error> {
+    let c_byte_order = match byte_order {
+        ByteOrder::LittleEndian => gdal_sys::OGRwkbByteOrder::wkbNDR,
+        ByteOrder::BigEndian => gdal_sys::OGRwkbByteOrder::wkbXDR,
+    };
+
+    let size = unsafe { gdal_sys::OGR_G_WkbSize(geometry.c_geometry()) } as usize;
+    let mut wkb = vec![0u8; size];
+    let rv = unsafe {
+        gdal_sys::OGR_G_ExportToWkb(geometry.c_geometry(), c_byte_order, wkb.as_mut_ptr())
+    };
+    if rv != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalError::OgrError {
+            err: rv,
+            method_name: "OGR_G_ExportToWkb",
+        }
+        .into());
+    }
+
+    Ok(wkb)
+}
+
+/// Densify a geometry in place so that no segment is longer than `max_length`.
+///
+/// Wraps OGR's `OGR_G_Segmentize`, which the gdal crate does not expose.
+pub(crate) fn segmentize(geometry: &mut Geometry, max_length: f64) {
+    unsafe { gdal_sys::OGR_G_Segmentize(geometry.c_geometry(), max_length) };
+}
+
+/// Count the vertices of a geometry, including all of its sub-geometries.
+pub(crate) fn vertex_count(geometry: &Geometry) -> usize {
+    unsafe { c_vertex_count(geometry.c_geometry()) }
+}
+
+unsafe fn c_vertex_count(c_geom: gdal_sys::OGRGeometryH) -> usize {
+    let sub_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if sub_count > 0 {
+        (0..sub_count)
+            .map(|i| c_vertex_count(gdal_sys::OGR_G_GetGeometryRef(c_geom, i)))
+            .sum()
+    } else {
+        gdal_sys::OGR_G_GetPointCount(c_geom).max(0) as usize
+    }
+}
+
+/// Round every coordinate of a geometry in place to the nearest multiple of `grid_size`.
+pub(crate) fn snap_to_grid(geometry: &mut Geometry, grid_size: f64) {
+    unsafe { snap_c_geometry(geometry.c_geometry(), grid_size) };
+}
+
+unsafe fn snap_c_geometry(c_geom: gdal_sys::OGRGeometryH, grid_size: f64) {
+    let snap = |v: f64| (v / grid_size).round() * grid_size;
+
+    let sub_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if sub_count > 0 {
+        for i in 0..sub_count {
+            snap_c_geometry(gdal_sys::OGR_G_GetGeometryRef(c_geom, i), grid_size);
+        }
+        return;
+    }
+
+    let is_3d = gdal_sys::OGR_G_Is3D(c_geom) != 0;
+    for i in 0..gdal_sys::OGR_G_GetPointCount(c_geom) {
+        let x = snap(gdal_sys::OGR_G_GetX(c_geom, i));
+        let y = snap(gdal_sys::OGR_G_GetY(c_geom, i));
+        if is_3d {
+            let z = snap(gdal_sys::OGR_G_GetZ(c_geom, i));
+            gdal_sys::OGR_G_SetPoint(c_geom, i, x, y, z);
+        } else {
+            gdal_sys::OGR_G_SetPoint_2D(c_geom, i, x, y);
+        }
+    }
+}
+
+/// A space-filling curve used to order geometries by location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceFillingCurve {
+    /// Hilbert curve. Preserves locality better than a Morton curve, at a slightly higher cost to compute.
+    Hilbert,
+
+    /// Morton (Z-order) curve.
+    Morton,
+}
+
+impl Default for SpaceFillingCurve {
+    fn default() -> Self {
+        Self::Hilbert
+    }
+}
+
+/// Sort a dataframe along a space-filling curve through the centers of its geometries' bounding boxes.
+///
+/// Spatially close features end up in nearby rows, which speeds up later bounding-box filtering and improves the locality of tiled writes.
+/// Rows with null or empty geometries are sorted last.
+///
+/// If `key_column` is given, the curve key is kept in a UInt64 column of that name.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::geom::{spatial_sort, SpaceFillingCurve};
+/// use polars_gdal::GeometryColumnSpec;
+///
+/// let sorted = spatial_sort(&df, GeometryColumnSpec::default(), SpaceFillingCurve::Hilbert, Some("hilbert"))?;
+/// ```
+pub fn spatial_sort(
+    df: &DataFrame,
+    geometry_column: GeometryColumnSpec,
+    curve: SpaceFillingCurve,
+    key_column: Option<&str>,
+) -> Result<DataFrame, Error> {
+    let centers = bbox_centers(df.column(geometry_column.name)?, geometry_column.format)?;
+    let keys = curve_keys(&centers, curve);
+
+    let mut order: Vec<IdxSize> = (0..keys.len() as IdxSize).collect();
+    order.sort_by_key(|idx| keys[*idx as usize]);
+
+    let mut df = df.clone();
+    if let Some(key_column) = key_column {
+        df.with_column(Series::new(key_column, keys))?;
+    }
+
+    Ok(df.take(&IdxCa::from_vec("", order))?)
+}
+
+/// Number of bits per axis used when computing space-filling curve keys.
+const CURVE_ORDER: u32 = 16;
+
+/// Compute the bounding-box center of every geometry in a series. Null geometries have no center.
+pub(crate) fn bbox_centers(
+    series: &Series,
+    format: GeometryFormat,
+) -> Result<Vec<Option<(f64, f64)>>, Error> {
+    let (series, format) = geometry_series(series, format)?;
+    let series = series.rechunk();
+    series
+        .iter()
+        .map(|value| {
+            if let AnyValue::Null = value {
+                return Ok(None);
+            }
+            let geometry = crate::polars_anyvalue_to_gdal_geometry(&value, format, series.name())?;
+            if geometry.is_empty() {
+                return Ok(None);
+            }
+            let envelope = geometry.envelope();
+            Ok(Some((
+                (envelope.MinX + envelope.MaxX) / 2.0,
+                (envelope.MinY + envelope.MaxY) / 2.0,
+            )))
+        })
+        .collect()
+}
+
+/// The type of every geometry in a series. Null geometries have no type.
+///
+/// WKB geometries are not parsed if their header has a known type code. Others are parsed, failing with the row of the first malformed geometry.
+pub(crate) fn geometry_types(
+    series: &Series,
+    format: GeometryFormat,
+) -> Result<Vec<Option<OGRwkbGeometryType::Type>>, Error> {
+    let (series, format) = geometry_series(series, format)?;
+    let series = series.rechunk();
+    series
+        .iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            if let AnyValue::Null = value {
+                return Ok(None);
+            }
+            if let AnyValue::Binary(wkb) = &value {
+                if let Some(geometry_type) = wkb_geometry_type(wkb) {
+                    return Ok(Some(geometry_type));
+                }
+            }
+            let geometry = crate::row_geometry(&value, format, series.name(), idx)?;
+            Ok(Some(geometry.geometry_type()))
+        })
+        .collect()
+}
+
+/// Read the geometry type from the header of a WKB geometry. ISO WKB and PostGIS EWKB type codes are both understood.
+fn wkb_geometry_type(wkb: &[u8]) -> Option<OGRwkbGeometryType::Type> {
+    let header: [u8; 4] = wkb.get(1..5)?.try_into().ok()?;
+    let code = match wkb[0] {
+        0 => u32::from_be_bytes(header),
+        1 => u32::from_le_bytes(header),
+        _ => return None,
+    };
+
+    // EWKB flags the Z and M dimensions (and an embedded SRID) in the high bits, and ISO WKB adds 1000, 2000 or 3000 to the code
+    let (ewkb_z, ewkb_m) = (code & 0x8000_0000 != 0, code & 0x4000_0000 != 0);
+    let code = code & 0x0fff_ffff;
+    let (flat, iso_dimensions) = (code % 1000, code / 1000);
+    if flat > OGRwkbGeometryType::wkbTriangle || iso_dimensions > 3 {
+        return None;
+    }
+    let has_z = ewkb_z || iso_dimensions == 1 || iso_dimensions == 3;
+    let has_m = ewkb_m || iso_dimensions == 2 || iso_dimensions == 3;
+
+    Some(unsafe { gdal_sys::OGR_GT_SetModifier(flat, has_z as i32, has_m as i32) })
+}
+
+/// Whether `multi_type` is the collection type of `single_type`, eg MultiPolygon for Polygon.
+pub(crate) fn is_collection_of(
+    multi_type: OGRwkbGeometryType::Type,
+    single_type: OGRwkbGeometryType::Type,
+) -> bool {
+    unsafe {
+        let single_flat = gdal_sys::OGR_GT_Flatten(single_type);
+        single_flat != gdal_sys::OGR_GT_GetCollection(single_flat)
+            && gdal_sys::OGR_GT_GetCollection(single_flat) == gdal_sys::OGR_GT_Flatten(multi_type)
+    }
+}
+
+/// Wrap a single geometry in a collection of the given type, eg a Polygon in a MultiPolygon.
+pub(crate) fn promote_to_multi(
+    geometry: Geometry,
+    multi_type: OGRwkbGeometryType::Type,
+) -> Result<Geometry, Error> {
+    let mut multi = Geometry::empty(multi_type)?;
+    multi.add_geometry(geometry)?;
+    Ok(multi)
+}
+
+/// Drop the Z and M coordinates of a geometry in place.
+///
+/// Wraps OGR's `OGR_G_FlattenTo2D`, which the gdal crate does not expose.
+pub(crate) fn force_2d(geometry: &mut Geometry) {
+    unsafe { gdal_sys::OGR_G_FlattenTo2D(geometry.c_geometry()) };
+}
+
+/// Wrap a single-part geometry in the matching multi-part type, eg a Polygon in a MultiPolygon, keeping its Z and M dimensions.
+///
+/// Geometries without a multi-part type (including multi-part geometries and collections) are returned unchanged.
+pub(crate) fn force_multi(geometry: Geometry) -> Result<Geometry, Error> {
+    let geometry_type = geometry.geometry_type();
+    let multi_type = forced_geometry_type(geometry_type, false, true);
+    if multi_type == geometry_type {
+        return Ok(geometry);
+    }
+    promote_to_multi(geometry, multi_type)
+}
+
+/// The type a geometry of `geometry_type` has after [`force_2d`] and/or [`force_multi`].
+pub(crate) fn forced_geometry_type(
+    geometry_type: OGRwkbGeometryType::Type,
+    force_2d: bool,
+    force_multi: bool,
+) -> OGRwkbGeometryType::Type {
+    let mut geometry_type = geometry_type;
+    unsafe {
+        if force_2d {
+            geometry_type = gdal_sys::OGR_GT_Flatten(geometry_type);
+        }
+        if force_multi {
+            // OGR_GT_GetCollection keeps the Z and M modifiers, and gives wkbUnknown for types without a multi-part type
+            let multi_type = gdal_sys::OGR_GT_GetCollection(geometry_type);
+            if multi_type != OGRwkbGeometryType::wkbUnknown {
+                geometry_type = multi_type;
+            }
+        }
+    }
+    geometry_type
+}
+
+/// Get the plain geometry series out of a geometry column, along with its format.
+///
+/// For [`GeometryFormat::WKBWithBbox`] this is the `wkb` field of the struct, renamed to the column name. Other formats are returned unchanged.
+pub(crate) fn geometry_series(
+    series: &Series,
+    format: GeometryFormat,
+) -> Result<(Series, GeometryFormat), Error> {
+    match format {
+        GeometryFormat::WKBWithBbox => {
+            let mut wkb = series
+                .struct_()?
+                .fields()
+                .iter()
+                .find(|field| field.name() == "wkb")
+                .cloned()
+                .ok_or_else(|| Error::CannotFindGeometryColumn(format!("{}.wkb", series.name())))?;
+            wkb.rename(series.name());
+            Ok((wkb, GeometryFormat::WKB))
+        }
+        format => Ok((series.clone(), format)),
+    }
+}
+
+/// Compute space-filling curve keys for a set of points, scaled to their combined extent.
+///
+/// Missing points get the largest possible key so they sort last.
+pub(crate) fn curve_keys(points: &[Option<(f64, f64)>], curve: SpaceFillingCurve) -> Vec<u64> {
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for (x, y) in points.iter().flatten() {
+        min_x = min_x.min(*x);
+        min_y = min_y.min(*y);
+        max_x = max_x.max(*x);
+        max_y = max_y.max(*y);
+    }
+
+    let max_cell = ((1u64 << CURVE_ORDER) - 1) as f64;
+    let scale = |v: f64, min: f64, max: f64| -> u64 {
+        if max > min {
+            (((v - min) / (max - min)) * max_cell).round() as u64
+        } else {
+            0
+        }
+    };
+
+    points
+        .iter()
+        .map(|point| match point {
+            Some((x, y)) => {
+                let x = scale(*x, min_x, max_x);
+                let y = scale(*y, min_y, max_y);
+                match curve {
+                    SpaceFillingCurve::Hilbert => hilbert_key(x, y),
+                    SpaceFillingCurve::Morton => morton_key(x, y),
+                }
+            }
+            None => u64::MAX,
+        })
+        .collect()
+}
+
+fn hilbert_key(mut x: u64, mut y: u64) -> u64 {
+    let n = 1u64 << CURVE_ORDER;
+    let mut key = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        key += s * s * ((3 * rx) ^ ry);
+
+        // Rotate the quadrant
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    key
+}
+
+fn morton_key(x: u64, y: u64) -> u64 {
+    let spread = |mut v: u64| {
+        v &= 0xFFFF_FFFF;
+        v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+        v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+        v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+        v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+        v = (v | (v << 1)) & 0x5555_5555_5555_5555;
+        v
+    };
+    spread(x) | (spread(y) << 1)
+}
+
+/// The unit of areas computed by [`area`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AreaUnit {
+    /// Square meters (m²).
+    SquareMeters,
+
+    /// Hectares (10,000 m²).
+    Hectares,
+
+    /// Square kilometers (km²).
+    SquareKilometers,
+
+    /// International square miles.
+    SquareMiles,
+}
+
+impl Default for AreaUnit {
+    fn default() -> Self {
+        Self::SquareMeters
+    }
+}
+
+impl AreaUnit {
+    fn square_meters(&self) -> f64 {
+        match self {
+            Self::SquareMeters => 1.0,
+            Self::Hectares => 10_000.0,
+            Self::SquareKilometers => 1_000_000.0,
+            Self::SquareMiles => METERS_PER_MILE * METERS_PER_MILE,
+        }
+    }
+}
+
+/// The unit of lengths computed by [`length`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthUnit {
+    /// Meters.
+    Meters,
+
+    /// Kilometers.
+    Kilometers,
+
+    /// International miles.
+    Miles,
+}
+
+impl Default for LengthUnit {
+    fn default() -> Self {
+        Self::Meters
+    }
+}
+
+impl LengthUnit {
+    fn meters(&self) -> f64 {
+        match self {
+            Self::Meters => 1.0,
+            Self::Kilometers => 1000.0,
+            Self::Miles => METERS_PER_MILE,
+        }
+    }
+}
+
+const METERS_PER_MILE: f64 = 1609.344;
+
+/// Compute the area of every geometry in a series, in the given unit. Null geometries have a null area.
+///
+/// `srs` is the SRS of the geometries. In a geographic SRS (eg EPSG:4326), each geometry is projected onto a
+/// Lambert azimuthal equal-area projection centered on it, so areas are in square meters on the ellipsoid rather than in square degrees.
+/// Geographic coordinates are taken to be in longitude / latitude order. If `srs` is None, coordinates are taken to be in meters.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::geom::{area, AreaUnit};
+///
+/// let wgs84 = gdal::spatial_ref::SpatialRef::from_epsg(4326)?;
+/// let hectares = area(df.column("geometry")?, GeometryFormat::WKB, Some(&wgs84), AreaUnit::Hectares)?;
+/// ```
+pub fn area(
+    series: &Series,
+    format: GeometryFormat,
+    srs: Option<&SpatialRef>,
+    unit: AreaUnit,
+) -> Result<Float64Chunked, Error> {
+    let measure = Measure::for_srs(srs)?;
+    map_geometries(series, format, |geometry| {
+        let square_meters = match &measure {
+            Measure::Planar { meters_per_unit } => geometry.area() * meters_per_unit * meters_per_unit,
+            Measure::Geodesic { srs, .. } => {
+                let envelope = geometry.envelope();
+                let (lon, lat) = (
+                    (envelope.MinX + envelope.MaxX) / 2.0,
+                    (envelope.MinY + envelope.MaxY) / 2.0,
+                );
+                let equal_area = SpatialRef::from_proj4(&format!(
+                    "+proj=laea +lat_0={} +lon_0={} +x_0=0 +y_0=0 +units=m +no_defs {}",
+                    lat,
+                    lon,
+                    ellipsoid_proj4(srs)
+                ))?;
+                geometry.transform(&CoordTransform::new(srs, &equal_area)?)?.area()
+            }
+        };
+        Ok(square_meters / unit.square_meters())
+    })
+}
+
+/// Compute the length of every geometry in a series, in the given unit. Null geometries have a null length.
+///
+/// The length of a polygon is the length of its rings, and points have no length.
+///
+/// `srs` is the SRS of the geometries. In a geographic SRS (eg EPSG:4326), lengths are great-circle distances on a sphere
+/// with the mean radius of the SRS's ellipsoid, which are within about 0.5% of geodesic lengths.
+/// Geographic coordinates are taken to be in longitude / latitude order. If `srs` is None, coordinates are taken to be in meters.
+pub fn length(
+    series: &Series,
+    format: GeometryFormat,
+    srs: Option<&SpatialRef>,
+    unit: LengthUnit,
+) -> Result<Float64Chunked, Error> {
+    let measure = Measure::for_srs(srs)?;
+    map_geometries(series, format, |geometry| {
+        let meters = match &measure {
+            Measure::Planar { meters_per_unit } => {
+                let planar_distance =
+                    |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| (x2 - x1).hypot(y2 - y1);
+                unsafe { c_length(geometry.c_geometry(), &planar_distance) * meters_per_unit }
+            }
+            Measure::Geodesic { radius, .. } => {
+                let geodesic_distance = |a, b| great_circle_distance(*radius, a, b);
+                unsafe { c_length(geometry.c_geometry(), &geodesic_distance) }
+            }
+        };
+        Ok(meters / unit.meters())
+    })
+}
+
+/// How to measure geometries in an SRS.
+enum Measure {
+    /// Measure in the plane, scaling the SRS's linear units to meters.
+    Planar { meters_per_unit: f64 },
+
+    /// Measure on the ellipsoid of a geographic SRS, which is set to longitude / latitude axis order.
+    Geodesic { srs: SpatialRef, radius: f64 },
+}
+
+impl Measure {
+    fn for_srs(srs: Option<&SpatialRef>) -> Result<Self, Error> {
+        let srs = match srs {
+            Some(srs) => srs,
+            None => return Ok(Self::Planar { meters_per_unit: 1.0 }),
+        };
+
+        if unsafe { gdal_sys::OSRIsGeographic(srs.to_c_hsrs()) } == 0 {
+            let meters_per_unit =
+                unsafe { gdal_sys::OSRGetLinearUnits(srs.to_c_hsrs(), std::ptr::null_mut()) };
+            return Ok(Self::Planar { meters_per_unit });
+        }
+
+        let srs = srs.clone();
+        unsafe {
+            gdal_sys::OSRSetAxisMappingStrategy(
+                srs.to_c_hsrs(),
+                gdal_sys::OSRAxisMappingStrategy::OAMS_TRADITIONAL_GIS_ORDER,
+            )
+        };
+        let (semi_major, semi_minor) = ellipsoid_axes(&srs);
+        Ok(Self::Geodesic {
+            srs,
+            radius: (2.0 * semi_major + semi_minor) / 3.0,
+        })
+    }
+}
+
+/// The semi-major and semi-minor axes of the ellipsoid of an SRS, in meters.
+fn ellipsoid_axes(srs: &SpatialRef) -> (f64, f64) {
+    let mut err = gdal_sys::OGRErr::OGRERR_NONE;
+    let semi_major = unsafe { gdal_sys::OSRGetSemiMajor(srs.to_c_hsrs(), &mut err) };
+    let semi_minor = unsafe { gdal_sys::OSRGetSemiMinor(srs.to_c_hsrs(), &mut err) };
+    (semi_major, semi_minor)
+}
+
+/// The PROJ parameters describing the ellipsoid of an SRS.
+fn ellipsoid_proj4(srs: &SpatialRef) -> String {
+    let (semi_major, semi_minor) = ellipsoid_axes(srs);
+    format!("+a={} +b={}", semi_major, semi_minor)
+}
+
+/// The great-circle distance between two longitude / latitude points on a sphere, using the haversine formula.
+fn great_circle_distance(radius: f64, (lon1, lat1): (f64, f64), (lon2, lat2): (f64, f64)) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2 - lon1).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * radius * h.sqrt().min(1.0).asin()
+}
+
+/// Sum the lengths of the segments of a geometry and all of its sub-geometries.
+unsafe fn c_length(
+    c_geom: gdal_sys::OGRGeometryH,
+    segment_length: &dyn Fn((f64, f64), (f64, f64)) -> f64,
+) -> f64 {
+    let sub_count = gdal_sys::OGR_G_GetGeometryCount(c_geom);
+    if sub_count > 0 {
+        return (0..sub_count)
+            .map(|i| c_length(gdal_sys::OGR_G_GetGeometryRef(c_geom, i), segment_length))
+            .sum();
+    }
+
+    let point = |i| (gdal_sys::OGR_G_GetX(c_geom, i), gdal_sys::OGR_G_GetY(c_geom, i));
+    (1..gdal_sys::OGR_G_GetPointCount(c_geom))
+        .map(|i| segment_length(point(i - 1), point(i)))
+        .sum()
+}
+
+/// Test whether each geometry in a series lies entirely within a bounding box (as `[xmin, ymin, xmax, ymax]`). Null and empty geometries give null.
+///
+/// Only the bounding box of each geometry is compared, which is cheap and is exact for points.
+pub fn within_bbox(
+    series: &Series,
+    format: GeometryFormat,
+    bbox: [f64; 4],
+) -> Result<BooleanChunked, Error> {
+    let (geometries, format) = geometry_series(series, format)?;
+    let geometries = geometries.rechunk();
+    let mut within: BooleanChunked = geometries
+        .iter()
+        .map(|value| {
+            if let AnyValue::Null = value {
+                return Ok(None);
+            }
+            let geometry = crate::polars_anyvalue_to_gdal_geometry(&value, format, series.name())?;
+            if geometry.is_empty() {
+                return Ok(None);
+            }
+            let envelope = geometry.envelope();
+            Ok(Some(
+                envelope.MinX >= bbox[0]
+                    && envelope.MinY >= bbox[1]
+                    && envelope.MaxX <= bbox[2]
+                    && envelope.MaxY <= bbox[3],
+            ))
+        })
+        .collect::<Result<_, Error>>()?;
+    within.rename(series.name());
+
+    Ok(within)
+}
+
+/// Compute a value for every geometry in a series, keeping the series name. Null geometries give nulls.
+fn map_geometries(
+    series: &Series,
+    format: GeometryFormat,
+    mut f: impl FnMut(&Geometry) -> Result<f64, Error>,
+) -> Result<Float64Chunked, Error> {
+    let (geometries, format) = geometry_series(series, format)?;
+    let geometries = geometries.rechunk();
+    let mut values: Float64Chunked = geometries
+        .iter()
+        .map(|value| {
+            if let AnyValue::Null = value {
+                return Ok(None);
+            }
+            let geometry = crate::polars_anyvalue_to_gdal_geometry(&value, format, series.name())?;
+            f(&geometry).map(Some)
+        })
+        .collect::<Result<_, Error>>()?;
+    values.rename(series.name());
+
+    Ok(values)
+}
+
+/// Compute the distance between the geometries in the same row of two series. Rows where either geometry is null have a null distance.
+///
+/// If `geodesic` is true, geometries are taken to be WGS84 longitude / latitude coordinates and distances are in meters.
+/// Distances between points are great-circle distances, and other distances are measured on an azimuthal equidistant projection
+/// centered between the two geometries, which is accurate for geometries up to a few hundred kilometers apart.
+/// Otherwise distances are planar, in the units of the geometries' coordinates.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::geom::distances;
+///
+/// let offsets = distances(df.column("reported")?, df.column("surveyed")?, GeometryFormat::WKB, true)?;
+/// ```
+pub fn distances(
+    left: &Series,
+    right: &Series,
+    format: GeometryFormat,
+    geodesic: bool,
+) -> Result<Float64Chunked, Error> {
+    if left.len() != right.len() {
+        return Err(PolarsError::ShapeMisMatch(
+            format!(
+                "cannot compute distances between series of length {} and {}",
+                left.len(),
+                right.len()
+            )
+            .into(),
+        )
+        .into());
+    }
+
+    let measure = if geodesic {
+        Measure::for_srs(Some(&SpatialRef::from_epsg(4326)?))?
+    } else {
+        Measure::Planar { meters_per_unit: 1.0 }
+    };
+    let (left_geometries, left_format) = geometry_series(left, format)?;
+    let (right_geometries, right_format) = geometry_series(right, format)?;
+    let (left_geometries, right_geometries) =
+        (left_geometries.rechunk(), right_geometries.rechunk());
+
+    let mut values: Float64Chunked = left_geometries
+        .iter()
+        .zip(right_geometries.iter())
+        .map(|(left_value, right_value)| {
+            if let (AnyValue::Null, _) | (_, AnyValue::Null) = (&left_value, &right_value) {
+                return Ok(None);
+            }
+            let left_geometry =
+                crate::polars_anyvalue_to_gdal_geometry(&left_value, left_format, left.name())?;
+            let right_geometry =
+                crate::polars_anyvalue_to_gdal_geometry(&right_value, right_format, right.name())?;
+            let distance = match &measure {
+                Measure::Planar { .. } => geometry_distance(&left_geometry, &right_geometry),
+                Measure::Geodesic { srs, radius } => {
+                    geodesic_distance(&left_geometry, &right_geometry, srs, *radius)?
+                }
+            };
+            Ok(Some(distance))
+        })
+        .collect::<Result<_, Error>>()?;
+    values.rename(left.name());
+
+    Ok(values)
+}
+
+/// The planar distance between two geometries, in the units of their coordinates.
+fn geometry_distance(left: &Geometry, right: &Geometry) -> f64 {
+    unsafe { gdal_sys::OGR_G_Distance(left.c_geometry(), right.c_geometry()) }
+}
+
+/// The distance in meters between two geometries in a geographic SRS with longitude / latitude axis order.
+fn geodesic_distance(
+    left: &Geometry,
+    right: &Geometry,
+    srs: &SpatialRef,
+    radius: f64,
+) -> Result<f64, Error> {
+    let point = gdal::vector::OGRwkbGeometryType::wkbPoint;
+    if left.geometry_type() == point && right.geometry_type() == point {
+        let (x1, y1, _) = left.get_point(0);
+        let (x2, y2, _) = right.get_point(0);
+        return Ok(great_circle_distance(radius, (x1, y1), (x2, y2)));
+    }
+
+    let (left_envelope, right_envelope) = (left.envelope(), right.envelope());
+    let lon = (left_envelope.MinX + left_envelope.MaxX + right_envelope.MinX + right_envelope.MaxX)
+        / 4.0;
+    let lat = (left_envelope.MinY + left_envelope.MaxY + right_envelope.MinY + right_envelope.MaxY)
+        / 4.0;
+    let equidistant = SpatialRef::from_proj4(&format!(
+        "+proj=aeqd +lat_0={} +lon_0={} +x_0=0 +y_0=0 +units=m +no_defs {}",
+        lat,
+        lon,
+        ellipsoid_proj4(srs)
+    ))?;
+    let transform = CoordTransform::new(srs, &equidistant)?;
+
+    Ok(geometry_distance(
+        &left.transform(&transform)?,
+        &right.transform(&transform)?,
+    ))
+}
+
+/// The maximum number of children of each node of a [`SpatialIndex`].
+const RTREE_NODE_CAPACITY: usize = 16;
+
+/// A bounding box as `[xmin, ymin, xmax, ymax]`.
+type Bbox = [f64; 4];
+
+/// A static R-tree over the geometries of a series, built by [`build_rtree`].
+///
+/// Queries return the row indices of matching geometries, so the same index can be used for any number of lookups against a loaded DataFrame.
+/// The index keeps its own copy of the geometries, and doesn't change if the series does.
+pub struct SpatialIndex {
+    /// The levels of the tree from the leaves up, the last holding the root. Each node has a bounding box and the range of its children
+    /// in the level below, or in `entries` for the leaves.
+    levels: Vec<Vec<(Bbox, std::ops::Range<usize>)>>,
+
+    /// The bounding boxes and row indices of the non-empty geometries, in leaf order.
+    entries: Vec<(Bbox, usize)>,
+
+    /// The geometries by row, used to test candidates exactly.
+    geometries: Vec<Option<Geometry>>,
+}
+
+impl SpatialIndex {
+    /// The indices of the rows whose bounding box intersects `bbox` (as `[xmin, ymin, xmax, ymax]`), in ascending order.
+    pub fn query_bbox(&self, bbox: [f64; 4]) -> Vec<usize> {
+        let mut rows = vec![];
+        let top = match self.levels.last() {
+            Some(top) => top,
+            None => return rows,
+        };
+
+        let mut stack: Vec<(usize, usize)> = (0..top.len())
+            .map(|idx| (self.levels.len() - 1, idx))
+            .collect();
+        while let Some((level, idx)) = stack.pop() {
+            let (node_bbox, children) = &self.levels[level][idx];
+            if !bboxes_intersect(node_bbox, &bbox) {
+                continue;
+            }
+            if level == 0 {
+                rows.extend(
+                    self.entries[children.clone()]
+                        .iter()
+                        .filter(|(entry_bbox, _)| bboxes_intersect(entry_bbox, &bbox))
+                        .map(|(_, row)| *row),
+                );
+            } else {
+                stack.extend(children.clone().map(|child| (level - 1, child)));
+            }
+        }
+        rows.sort_unstable();
+
+        rows
+    }
+
+    /// The indices of the rows whose geometry intersects `geometry`, in ascending order.
+    pub fn query_geometry(&self, geometry: &Geometry) -> Vec<usize> {
+        let envelope = geometry.envelope();
+        self.query_bbox([envelope.MinX, envelope.MinY, envelope.MaxX, envelope.MaxY])
+            .into_iter()
+            .filter(|row| {
+                self.geometries[*row]
+                    .as_ref()
+                    .map_or(false, |candidate| candidate.intersects(geometry))
+            })
+            .collect()
+    }
+}
+
+/// Build a [`SpatialIndex`] over the geometries of a series, so repeated spatial lookups don't rebuild indexes or round-trip through GDAL layers.
+///
+/// The tree is packed with the Sort-Tile-Recursive algorithm. Null and empty geometries are never returned by queries.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::geom::build_rtree;
+///
+/// let index = build_rtree(parcels.column("geometry")?, GeometryFormat::WKB)?;
+/// for point in points {
+///     let rows = index.query_geometry(&point);
+/// }
+/// ```
+pub fn build_rtree(series: &Series, format: GeometryFormat) -> Result<SpatialIndex, Error> {
+    let (geometries, format) = geometry_series(series, format)?;
+    let geometries = geometries.rechunk();
+    let geometries: Vec<Option<Geometry>> = geometries
+        .iter()
+        .map(|value| match value {
+            AnyValue::Null => Ok(None),
+            value => {
+                crate::polars_anyvalue_to_gdal_geometry(&value, format, series.name()).map(Some)
+            }
+        })
+        .collect::<Result<_, Error>>()?;
+
+    let mut entries: Vec<(Bbox, usize)> = geometries
+        .iter()
+        .enumerate()
+        .filter_map(|(row, geometry)| {
+            let envelope = geometry.as_ref().filter(|g| !g.is_empty())?.envelope();
+            Some((
+                [envelope.MinX, envelope.MinY, envelope.MaxX, envelope.MaxY],
+                row,
+            ))
+        })
+        .collect();
+    sort_tile_recursive(&mut entries);
+
+    let mut levels = vec![];
+    let mut nodes = pack_nodes(&entries);
+    while nodes.len() > 1 {
+        sort_tile_recursive(&mut nodes);
+        let parents = pack_nodes(&nodes);
+        levels.push(nodes);
+        nodes = parents;
+    }
+    levels.push(nodes);
+
+    Ok(SpatialIndex {
+        levels,
+        entries,
+        geometries,
+    })
+}
+
+/// Order items so that runs of [`RTREE_NODE_CAPACITY`] items are spatially compact: sort by x center into vertical slices, then each slice by y center.
+fn sort_tile_recursive<T>(items: &mut [(Bbox, T)]) {
+    let center = |bbox: &Bbox, axis: usize| bbox[axis] + bbox[axis + 2];
+    let node_count = (items.len() + RTREE_NODE_CAPACITY - 1) / RTREE_NODE_CAPACITY;
+    let slice_len = ((node_count as f64).sqrt().ceil() as usize).max(1) * RTREE_NODE_CAPACITY;
+
+    items.sort_by(|(a, _), (b, _)| center(a, 0).total_cmp(&center(b, 0)));
+    for slice in items.chunks_mut(slice_len) {
+        slice.sort_by(|(a, _), (b, _)| center(a, 1).total_cmp(&center(b, 1)));
+    }
+}
+
+/// Group consecutive items into nodes of up to [`RTREE_NODE_CAPACITY`] children.
+fn pack_nodes<T>(items: &[(Bbox, T)]) -> Vec<(Bbox, std::ops::Range<usize>)> {
+    items
+        .chunks(RTREE_NODE_CAPACITY)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let mut bbox = [
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ];
+            for (child_bbox, _) in chunk {
+                bbox[0] = bbox[0].min(child_bbox[0]);
+                bbox[1] = bbox[1].min(child_bbox[1]);
+                bbox[2] = bbox[2].max(child_bbox[2]);
+                bbox[3] = bbox[3].max(child_bbox[3]);
+            }
+            let start = idx * RTREE_NODE_CAPACITY;
+            (bbox, start..start + chunk.len())
+        })
+        .collect()
+}
+
+/// Whether two bounding boxes overlap or touch.
+fn bboxes_intersect(a: &Bbox, b: &Bbox) -> bool {
+    a[0] <= b[2] && b[0] <= a[2] && a[1] <= b[3] && b[1] <= a[3]
+}
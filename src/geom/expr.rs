@@ -0,0 +1,118 @@
+//! Geometry helpers as Polars expressions, for use inside lazy pipelines (eg `LazyFrame::with_columns`).
+//!
+//! Each expression applies the eager helper of the same name in [`crate::geom`] to the column `geometry` evaluates to.
+//! Requires the `lazy` feature.
+//!
+//! # Example
+//! ```rust # ignore
+//! use polars::prelude::*;
+//! use polars_gdal::geom::{expr, AreaUnit};
+//!
+//! let df = df
+//!     .lazy()
+//!     .with_columns([expr::area(col("geometry"), GeometryFormat::WKB, None, AreaUnit::SquareMeters)?.alias("area")])
+//!     .filter(expr::within_bbox(col("geometry"), GeometryFormat::WKB, [0.0, 0.0, 10.0, 10.0]))
+//!     .collect()?;
+//! ```
+
+use crate::geom::{AreaUnit, LengthUnit};
+use crate::{Error, GeometryFormat};
+use gdal::spatial_ref::SpatialRef;
+use polars::prelude::*;
+
+/// The area of each geometry, as with [`crate::geom::area`].
+pub fn area(
+    geometry: Expr,
+    format: GeometryFormat,
+    srs: Option<&SpatialRef>,
+    unit: AreaUnit,
+) -> Result<Expr, Error> {
+    let srs = SendableSrs::new(srs)?;
+    Ok(geometry.map(
+        move |series| {
+            let srs = srs.to_srs().map_err(to_polars_error)?;
+            crate::geom::area(&series, format, srs.as_ref(), unit)
+                .map(|area| area.into_series())
+                .map_err(to_polars_error)
+        },
+        GetOutput::from_type(DataType::Float64),
+    ))
+}
+
+/// The length of each geometry, as with [`crate::geom::length`].
+pub fn length(
+    geometry: Expr,
+    format: GeometryFormat,
+    srs: Option<&SpatialRef>,
+    unit: LengthUnit,
+) -> Result<Expr, Error> {
+    let srs = SendableSrs::new(srs)?;
+    Ok(geometry.map(
+        move |series| {
+            let srs = srs.to_srs().map_err(to_polars_error)?;
+            crate::geom::length(&series, format, srs.as_ref(), unit)
+                .map(|length| length.into_series())
+                .map_err(to_polars_error)
+        },
+        GetOutput::from_type(DataType::Float64),
+    ))
+}
+
+/// Whether each geometry lies entirely within a bounding box (as `[xmin, ymin, xmax, ymax]`), as with [`crate::geom::within_bbox`].
+pub fn within_bbox(geometry: Expr, format: GeometryFormat, bbox: [f64; 4]) -> Expr {
+    geometry.map(
+        move |series| {
+            crate::geom::within_bbox(&series, format, bbox)
+                .map(|within| within.into_series())
+                .map_err(to_polars_error)
+        },
+        GetOutput::from_type(DataType::Boolean),
+    )
+}
+
+/// Convert WKB geometries to WKT, as with [`crate::geom::wkb_to_wkt`].
+pub fn to_wkt(geometry: Expr) -> Expr {
+    geometry.map(
+        |series| crate::geom::wkb_to_wkt(&series).map_err(to_polars_error),
+        GetOutput::from_type(DataType::Utf8),
+    )
+}
+
+/// Convert WKT geometries to little-endian WKB, as with [`crate::geom::wkt_to_wkb`].
+pub fn to_wkb(geometry: Expr) -> Expr {
+    geometry.map(
+        |series| crate::geom::wkt_to_wkb(&series).map_err(to_polars_error),
+        GetOutput::from_type(DataType::Binary),
+    )
+}
+
+/// An SRS as WKT and axis mapping strategy, since expressions must be `Send + Sync` and GDAL SRS handles aren't.
+struct SendableSrs(Option<(String, gdal_sys::OSRAxisMappingStrategy::Type)>);
+
+impl SendableSrs {
+    fn new(srs: Option<&SpatialRef>) -> Result<Self, Error> {
+        let srs = srs
+            .map(|srs| -> Result<_, Error> {
+                let strategy = unsafe { gdal_sys::OSRGetAxisMappingStrategy(srs.to_c_hsrs()) };
+                Ok((srs.to_wkt()?, strategy))
+            })
+            .transpose()?;
+        Ok(Self(srs))
+    }
+
+    fn to_srs(&self) -> Result<Option<SpatialRef>, Error> {
+        self.0
+            .as_ref()
+            .map(|(wkt, strategy)| -> Result<_, Error> {
+                let srs = SpatialRef::from_wkt(wkt)?;
+                unsafe { gdal_sys::OSRSetAxisMappingStrategy(srs.to_c_hsrs(), *strategy) };
+                Ok(srs)
+            })
+            .transpose()
+    }
+}
+
+/// Report an error from inside an expression.
+fn to_polars_error(e: Error) -> PolarsError {
+    PolarsError::ComputeError(e.to_string().into())
+}
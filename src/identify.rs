@@ -0,0 +1,59 @@
+use crate::{DriverInfo, Error};
+use gdal_sys::{VSIFCloseL, VSIFileFromMemBuffer, VSIUnlink};
+use std::ffi::CString;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Identify the vector driver that would open a buffer, without opening it.
+///
+/// Only the start of the data is inspected, so this is cheap enough to validate uploads before reading them with
+/// [`df_from_bytes`](crate::df_from_bytes). Some drivers (eg CSV) only recognize files by their extension, which is
+/// taken from `filename_hint`. Fails with [`Error::UnrecognizedFormat`] if no vector driver recognizes the data.
+///
+/// # Example
+/// ```rust # ignore
+/// let driver = polars_gdal::identify_bytes(&upload, Some(&upload_filename))?;
+/// if driver.name != "GPKG" {
+///     return Err(format!("expected a GeoPackage, got {}", driver.long_name));
+/// }
+/// ```
+pub fn identify_bytes(data: &[u8], filename_hint: Option<&str>) -> Result<DriverInfo, Error> {
+    if data.is_empty() {
+        return Err(Error::EmptyData);
+    }
+
+    static IDENTIFY_BYTES_MEM_FILE_INCREMENTOR: AtomicU64 = AtomicU64::new(0);
+    let input_mem_path = format!(
+        "/vsimem/polars_gdal/identify_bytes/{}/{}/{}",
+        std::process::id(),
+        IDENTIFY_BYTES_MEM_FILE_INCREMENTOR.fetch_add(1, Ordering::SeqCst),
+        filename_hint.unwrap_or("layer")
+    );
+
+    // SAFETY: GDAL doesn't take ownership of the buffer, and only reads it until the file is unlinked before returning
+    let path = CString::new(input_mem_path.as_bytes()).unwrap();
+    let ptr = data.as_ptr() as *mut u8;
+    let handle =
+        unsafe { VSIFileFromMemBuffer(path.as_ptr(), ptr, data.len() as u64, false as i32) };
+    if handle.is_null() {
+        return Err(crate::_last_null_pointer_err("VSIFileFromMemBuffer").into());
+    }
+
+    let c_driver = unsafe {
+        gdal_sys::GDALIdentifyDriverEx(
+            path.as_ptr(),
+            gdal::GdalOpenFlags::GDAL_OF_VECTOR.bits(),
+            std::ptr::null(),
+            std::ptr::null(),
+        )
+    };
+    unsafe {
+        VSIFCloseL(handle);
+        VSIUnlink(path.as_ptr());
+    }
+    if c_driver.is_null() {
+        return Err(Error::UnrecognizedFormat);
+    }
+
+    let driver = unsafe { gdal::Driver::from_c_driver(c_driver) };
+    Ok(DriverInfo::of(&driver))
+}
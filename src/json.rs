@@ -0,0 +1,396 @@
+use crate::Error;
+use polars::prelude::*;
+
+/// Escape a string for use inside a JSON string literal.
+pub(crate) fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Serialize every value of a series to a JSON string, returning a Utf8 series of the same name.
+///
+/// Structs become objects and lists become arrays. Temporal values are written as strings.
+pub(crate) fn series_to_json(series: &Series) -> Result<Series, Error> {
+    let values = json_values(series)?;
+    Ok(Series::new(series.name(), values))
+}
+
+/// The JSON encoding of each value of a series, with nulls encoded as `null`.
+fn json_values(series: &Series) -> Result<Vec<String>, Error> {
+    let values = match series.dtype() {
+        DataType::Struct(_) => {
+            let fields = series.struct_()?.fields();
+            let field_values = fields
+                .iter()
+                .map(json_values)
+                .collect::<Result<Vec<_>, _>>()?;
+            (0..series.len())
+                .map(|i| {
+                    let members: Vec<String> = fields
+                        .iter()
+                        .zip(&field_values)
+                        .map(|(field, values)| {
+                            format!("\"{}\":{}", json_escape(field.name()), values[i])
+                        })
+                        .collect();
+                    format!("{{{}}}", members.join(","))
+                })
+                .collect()
+        }
+        DataType::List(_) => series
+            .list()?
+            .into_iter()
+            .map(|v| match v {
+                Some(v) => Ok(format!("[{}]", json_values(&v)?.join(","))),
+                None => Ok("null".to_owned()),
+            })
+            .collect::<Result<Vec<_>, Error>>()?,
+        DataType::Boolean => series
+            .bool()?
+            .into_iter()
+            .map(|v| v.map_or("null".to_owned(), |v| v.to_string()))
+            .collect(),
+        dtype if dtype.is_float() => series
+            .cast(&DataType::Float64)?
+            .f64()?
+            .into_iter()
+            .map(|v| match v {
+                Some(v) if v.is_finite() => v.to_string(),
+                _ => "null".to_owned(),
+            })
+            .collect(),
+        dtype if dtype.is_numeric() => series
+            .cast(&DataType::Int64)?
+            .i64()?
+            .into_iter()
+            .map(|v| v.map_or("null".to_owned(), |v| v.to_string()))
+            .collect(),
+        _ => series
+            .cast(&DataType::Utf8)?
+            .utf8()?
+            .into_iter()
+            .map(|v| v.map_or("null".to_owned(), |v| format!("\"{}\"", json_escape(v))))
+            .collect(),
+    };
+
+    Ok(values)
+}
+
+/// A parsed JSON value. Object members keep their order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// Parse a JSON document, returning `None` if it isn't valid JSON.
+pub(crate) fn parse_json(s: &str) -> Option<JsonValue> {
+    let mut parser = JsonParser {
+        chars: s.chars().peekable(),
+    };
+    let value = parser.value()?;
+    parser.whitespace();
+    match parser.chars.next() {
+        None => Some(value),
+        Some(_) => None,
+    }
+}
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> JsonParser<'a> {
+    fn whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Option<()> {
+        for c in literal.chars() {
+            if self.chars.next()? != c {
+                return None;
+            }
+        }
+        Some(())
+    }
+
+    fn value(&mut self) -> Option<JsonValue> {
+        self.whitespace();
+        match *self.chars.peek()? {
+            'n' => self.expect("null").map(|_| JsonValue::Null),
+            't' => self.expect("true").map(|_| JsonValue::Bool(true)),
+            'f' => self.expect("false").map(|_| JsonValue::Bool(false)),
+            '"' => self.string().map(JsonValue::String),
+            '[' => {
+                self.chars.next();
+                let mut values = vec![];
+                self.whitespace();
+                if self.chars.peek() == Some(&']') {
+                    self.chars.next();
+                    return Some(JsonValue::Array(values));
+                }
+                loop {
+                    values.push(self.value()?);
+                    self.whitespace();
+                    match self.chars.next()? {
+                        ',' => continue,
+                        ']' => return Some(JsonValue::Array(values)),
+                        _ => return None,
+                    }
+                }
+            }
+            '{' => {
+                self.chars.next();
+                let mut members = vec![];
+                self.whitespace();
+                if self.chars.peek() == Some(&'}') {
+                    self.chars.next();
+                    return Some(JsonValue::Object(members));
+                }
+                loop {
+                    self.whitespace();
+                    let key = self.string()?;
+                    self.whitespace();
+                    self.expect(":")?;
+                    members.push((key, self.value()?));
+                    self.whitespace();
+                    match self.chars.next()? {
+                        ',' => continue,
+                        '}' => return Some(JsonValue::Object(members)),
+                        _ => return None,
+                    }
+                }
+            }
+            _ => self.number(),
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect("\"")?;
+        let mut s = String::new();
+        loop {
+            match self.chars.next()? {
+                '"' => return Some(s),
+                '\\' => match self.chars.next()? {
+                    '"' => s.push('"'),
+                    '\\' => s.push('\\'),
+                    '/' => s.push('/'),
+                    'b' => s.push('\u{8}'),
+                    'f' => s.push('\u{c}'),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'u' => {
+                        let high = self.hex4()?;
+                        let c = if (0xD800..0xDC00).contains(&high) {
+                            self.expect("\\u")?;
+                            let low = self.hex4()?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.checked_sub(0xDC00)?)
+                        } else {
+                            high
+                        };
+                        s.push(char::from_u32(c)?);
+                    }
+                    _ => return None,
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..4 {
+            value = value * 16 + self.chars.next()?.to_digit(16)?;
+        }
+        Some(value)
+    }
+
+    fn number(&mut self) -> Option<JsonValue> {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                s.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match s.parse::<i64>() {
+            Ok(i) => Some(JsonValue::Integer(i)),
+            Err(_) => s.parse::<f64>().ok().map(JsonValue::Real),
+        }
+    }
+}
+
+/// Parse a Utf8 series of JSON documents into a Struct, List or scalar series, inferring the dtype from every value.
+///
+/// Returns `None` if any value isn't valid JSON, or if values don't share a common structure (eg a number in one row and an object in another).
+pub(crate) fn parse_json_series(series: &Series) -> Result<Option<Series>, Error> {
+    let mut values = Vec::with_capacity(series.len());
+    for value in series.utf8()?.into_iter() {
+        match value.map(parse_json) {
+            Some(Some(value)) => values.push(value),
+            Some(None) => return Ok(None),
+            None => values.push(JsonValue::Null),
+        }
+    }
+
+    Ok(json_series(series.name(), &values))
+}
+
+/// The dtype shared by a set of JSON values, ignoring nulls. Integers widen to floats.
+fn json_dtype<'v>(values: impl Iterator<Item = &'v JsonValue>) -> Option<JsonDtype> {
+    let mut dtype = JsonDtype::Null;
+    for value in values {
+        let value_dtype = match value {
+            JsonValue::Null => continue,
+            JsonValue::Bool(_) => JsonDtype::Bool,
+            JsonValue::Integer(_) => JsonDtype::Integer,
+            JsonValue::Real(_) => JsonDtype::Real,
+            JsonValue::String(_) => JsonDtype::String,
+            JsonValue::Array(_) => JsonDtype::Array,
+            JsonValue::Object(_) => JsonDtype::Object,
+        };
+        dtype = match (dtype, value_dtype) {
+            (JsonDtype::Null, d) => d,
+            (d, v) if d == v => d,
+            (JsonDtype::Integer, JsonDtype::Real) | (JsonDtype::Real, JsonDtype::Integer) => {
+                JsonDtype::Real
+            }
+            _ => return None,
+        };
+    }
+    Some(dtype)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonDtype {
+    Null,
+    Bool,
+    Integer,
+    Real,
+    String,
+    Array,
+    Object,
+}
+
+/// Build a series from JSON values sharing a common structure, or `None` if they don't.
+fn json_series(name: &str, values: &[JsonValue]) -> Option<Series> {
+    let series = match json_dtype(values.iter())? {
+        JsonDtype::Null => Series::full_null(name, values.len(), &DataType::Utf8),
+        JsonDtype::Bool => values
+            .iter()
+            .map(|v| match v {
+                JsonValue::Bool(b) => Some(*b),
+                _ => None,
+            })
+            .collect::<BooleanChunked>()
+            .into_series(),
+        JsonDtype::Integer => values
+            .iter()
+            .map(|v| match v {
+                JsonValue::Integer(i) => Some(*i),
+                _ => None,
+            })
+            .collect::<Int64Chunked>()
+            .into_series(),
+        JsonDtype::Real => values
+            .iter()
+            .map(|v| match v {
+                JsonValue::Integer(i) => Some(*i as f64),
+                JsonValue::Real(f) => Some(*f),
+                _ => None,
+            })
+            .collect::<Float64Chunked>()
+            .into_series(),
+        JsonDtype::String => values
+            .iter()
+            .map(|v| match v {
+                JsonValue::String(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect::<Utf8Chunked>()
+            .into_series(),
+        JsonDtype::Array => {
+            // Parse every element together so that all rows share the inner dtype, then split them back into rows
+            let elements: Vec<JsonValue> = values
+                .iter()
+                .flat_map(|v| match v {
+                    JsonValue::Array(elements) => elements.clone(),
+                    _ => vec![],
+                })
+                .collect();
+            let inner = json_series("", &elements)?;
+            let mut offset = 0;
+            values
+                .iter()
+                .map(|v| match v {
+                    JsonValue::Array(elements) => {
+                        let row = inner.slice(offset as i64, elements.len());
+                        offset += elements.len();
+                        Some(row)
+                    }
+                    _ => None,
+                })
+                .collect::<ListChunked>()
+                .into_series()
+        }
+        JsonDtype::Object => {
+            // Fields are the union of the keys of every object, in the order they are first seen
+            let mut keys: Vec<&str> = vec![];
+            for value in values {
+                if let JsonValue::Object(members) = value {
+                    for (key, _) in members {
+                        if !keys.contains(&key.as_str()) {
+                            keys.push(key);
+                        }
+                    }
+                }
+            }
+            let fields = keys
+                .iter()
+                .map(|key| {
+                    let field_values: Vec<JsonValue> = values
+                        .iter()
+                        .map(|v| match v {
+                            JsonValue::Object(members) => members
+                                .iter()
+                                .find(|(k, _)| k == key)
+                                .map(|(_, v)| v.clone())
+                                .unwrap_or(JsonValue::Null),
+                            _ => JsonValue::Null,
+                        })
+                        .collect();
+                    json_series(key, &field_values)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            if fields.is_empty() {
+                return None;
+            }
+            StructChunked::new(name, &fields).ok()?.into_series()
+        }
+    };
+
+    let mut series = series;
+    series.rename(name);
+    Some(series)
+}
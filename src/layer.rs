@@ -0,0 +1,297 @@
+use crate::Error;
+use gdal::errors::GdalError;
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::LayerAccess;
+use gdal::vector::OGRFieldType;
+use gdal::vector::OGRwkbGeometryType;
+use std::ffi::c_char;
+use std::ffi::CStr;
+use std::ffi::CString;
+
+/// Tells GDAL to skip decoding a set of fields on a layer. The fields ignored before are restored when dropped.
+///
+/// This holds the raw layer handle rather than a borrow so that the layer can still be iterated while the guard is alive.
+pub(crate) struct IgnoredFields {
+    c_layer: gdal_sys::OGRLayerH,
+
+    /// The fields ignored before the guard was created, eg by the caller of `df_from_layer`.
+    previous: Vec<CString>,
+}
+
+impl IgnoredFields {
+    /// Set the ignored fields on a layer, replacing any fields ignored until now.
+    pub(crate) fn set<L: LayerAccess>(layer: &L, fields: &[String]) -> Result<Self, Error> {
+        let c_layer = unsafe { layer.c_layer() };
+
+        let c_fields = fields
+            .iter()
+            .map(|f| CString::new(f.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(GdalError::from)?;
+        let previous = ignored_fields(c_layer);
+
+        let rv = set_ignored_fields(c_layer, &c_fields);
+        if rv != gdal_sys::OGRErr::OGRERR_NONE {
+            return Err(GdalError::OgrError {
+                err: rv,
+                method_name: "OGR_L_SetIgnoredFields",
+            }
+            .into());
+        }
+
+        Ok(Self { c_layer, previous })
+    }
+}
+
+impl Drop for IgnoredFields {
+    fn drop(&mut self) {
+        set_ignored_fields(self.c_layer, &self.previous);
+    }
+}
+
+/// The fields currently ignored on a layer, using the special `OGR_GEOMETRY` and `OGR_STYLE` names accepted by `OGR_L_SetIgnoredFields`.
+///
+/// GDAL has no getter for the ignored fields, so they are read from the flags of the field definitions.
+fn ignored_fields(c_layer: gdal_sys::OGRLayerH) -> Vec<CString> {
+    let mut ignored = vec![];
+    unsafe {
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(c_layer);
+        for idx in 0..gdal_sys::OGR_FD_GetFieldCount(c_defn) {
+            let c_field_defn = gdal_sys::OGR_FD_GetFieldDefn(c_defn, idx);
+            if gdal_sys::OGR_Fld_IsIgnored(c_field_defn) != 0 {
+                ignored.push(CStr::from_ptr(gdal_sys::OGR_Fld_GetNameRef(c_field_defn)).to_owned());
+            }
+        }
+        for idx in 0..gdal_sys::OGR_FD_GetGeomFieldCount(c_defn) {
+            let c_geom_field_defn = gdal_sys::OGR_FD_GetGeomFieldDefn(c_defn, idx);
+            if gdal_sys::OGR_GFld_IsIgnored(c_geom_field_defn) != 0 {
+                let name = CStr::from_ptr(gdal_sys::OGR_GFld_GetNameRef(c_geom_field_defn));
+                ignored.push(if name.to_bytes().is_empty() {
+                    CString::new("OGR_GEOMETRY").unwrap()
+                } else {
+                    name.to_owned()
+                });
+            }
+        }
+        if gdal_sys::OGR_FD_IsStyleIgnored(c_defn) != 0 {
+            ignored.push(CString::new("OGR_STYLE").unwrap());
+        }
+    }
+
+    ignored
+}
+
+/// Set the ignored fields of a layer. An empty list decodes every field again.
+fn set_ignored_fields(c_layer: gdal_sys::OGRLayerH, fields: &[CString]) -> gdal_sys::OGRErr::Type {
+    let mut c_ptrs: Vec<*const c_char> = fields.iter().map(|f| f.as_ptr()).collect();
+    c_ptrs.push(std::ptr::null());
+
+    unsafe { gdal_sys::OGR_L_SetIgnoredFields(c_layer, c_ptrs.as_mut_ptr()) }
+}
+
+/// Read a field as raw bytes, returning `None` if the field doesn't exist or is null.
+///
+/// The gdal crate doesn't expose OFTBinary field values, so this reads them with `OGR_F_GetFieldAsBinary`.
+pub(crate) fn field_as_binary(feature: &gdal::vector::Feature, field_name: &str) -> Option<Vec<u8>> {
+    let c_name = CString::new(field_name).ok()?;
+    let c_feature = unsafe { feature.c_feature() };
+
+    let idx = unsafe { gdal_sys::OGR_F_GetFieldIndex(c_feature, c_name.as_ptr()) };
+    if idx < 0 {
+        return None;
+    }
+
+    field_as_binary_by_index(feature, idx)
+}
+
+/// Read a field as raw bytes by its index, returning `None` if the field is null.
+pub(crate) fn field_as_binary_by_index(
+    feature: &gdal::vector::Feature,
+    field_idx: i32,
+) -> Option<Vec<u8>> {
+    let c_feature = unsafe { feature.c_feature() };
+    if unsafe { gdal_sys::OGR_F_IsFieldSetAndNotNull(c_feature, field_idx) } == 0 {
+        return None;
+    }
+
+    let mut len = 0;
+    let ptr = unsafe { gdal_sys::OGR_F_GetFieldAsBinary(c_feature, field_idx, &mut len) };
+    if ptr.is_null() || len <= 0 {
+        return Some(vec![]);
+    }
+
+    Some(unsafe { std::slice::from_raw_parts(ptr, len as usize) }.to_vec())
+}
+
+/// Set a field to raw bytes, since the gdal crate can't set OFTBinary field values.
+pub(crate) fn set_field_binary(
+    feature: &gdal::vector::Feature,
+    field_name: &str,
+    value: &[u8],
+) -> Result<(), Error> {
+    let c_name = CString::new(field_name).map_err(GdalError::from)?;
+    let c_feature = unsafe { feature.c_feature() };
+
+    let idx = unsafe { gdal_sys::OGR_F_GetFieldIndex(c_feature, c_name.as_ptr()) };
+    if idx < 0 {
+        return Err(GdalError::InvalidFieldName {
+            field_name: field_name.to_owned(),
+            method_name: "OGR_F_GetFieldIndex",
+        }
+        .into());
+    }
+
+    unsafe {
+        gdal_sys::OGR_F_SetFieldBinary(
+            c_feature,
+            idx,
+            value.len() as i32,
+            value.as_ptr() as *const std::ffi::c_void,
+        )
+    };
+
+    Ok(())
+}
+
+/// Read the OGR style string of a feature, returning `None` if the feature has no style.
+pub(crate) fn style_string(feature: &gdal::vector::Feature) -> Option<String> {
+    let c_style = unsafe { gdal_sys::OGR_F_GetStyleString(feature.c_feature()) };
+    if c_style.is_null() {
+        return None;
+    }
+
+    let style = crate::_string(c_style);
+    if style.is_empty() {
+        None
+    } else {
+        Some(style)
+    }
+}
+
+/// Read an OFTTime field as nanoseconds since midnight, returning `None` if the field is null.
+///
+/// The gdal crate doesn't expose OFTTime field values, so this reads them with `OGR_F_GetFieldAsDateTimeEx`.
+pub(crate) fn field_as_time(feature: &gdal::vector::Feature, field_idx: i32) -> Option<i64> {
+    let c_feature = unsafe { feature.c_feature() };
+    if unsafe { gdal_sys::OGR_F_IsFieldSetAndNotNull(c_feature, field_idx) } == 0 {
+        return None;
+    }
+
+    let (mut year, mut month, mut day, mut hour, mut minute, mut tz_flag) = (0, 0, 0, 0, 0, 0);
+    let mut second = 0.0f32;
+    let rv = unsafe {
+        gdal_sys::OGR_F_GetFieldAsDateTimeEx(
+            c_feature,
+            field_idx,
+            &mut year,
+            &mut month,
+            &mut day,
+            &mut hour,
+            &mut minute,
+            &mut second,
+            &mut tz_flag,
+        )
+    };
+    if rv == 0 {
+        return None;
+    }
+
+    // OGR keeps times to the millisecond, so round away the f32 error in the seconds
+    let millis = (hour as i64 * 60 + minute as i64) * 60_000 + (second as f64 * 1000.0).round() as i64;
+    Some(millis * 1_000_000)
+}
+
+/// Create a field with a subtype and field domain, which the gdal crate can't set.
+pub(crate) fn create_field<L: LayerAccess>(
+    layer: &L,
+    field_name: &str,
+    field_type: OGRFieldType::Type,
+    field_subtype: gdal_sys::OGRFieldSubType::Type,
+    domain_name: Option<&str>,
+) -> Result<(), Error> {
+    let c_name = CString::new(field_name).map_err(GdalError::from)?;
+    let c_domain_name = domain_name
+        .map(CString::new)
+        .transpose()
+        .map_err(GdalError::from)?;
+
+    let rv = unsafe {
+        let c_field_defn = gdal_sys::OGR_Fld_Create(c_name.as_ptr(), field_type);
+        gdal_sys::OGR_Fld_SetSubType(c_field_defn, field_subtype);
+        if let Some(c_domain_name) = &c_domain_name {
+            gdal_sys::OGR_Fld_SetDomainName(c_field_defn, c_domain_name.as_ptr());
+        }
+        let rv = gdal_sys::OGR_L_CreateField(layer.c_layer(), c_field_defn, 1);
+        gdal_sys::OGR_Fld_Destroy(c_field_defn);
+        rv
+    };
+    if rv != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalError::OgrError {
+            err: rv,
+            method_name: "OGR_L_CreateField",
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Add a geometry field to a layer, for drivers supporting several geometry fields per layer.
+pub(crate) fn create_geometry_field<L: LayerAccess>(
+    layer: &L,
+    field_name: &str,
+    geometry_type: OGRwkbGeometryType::Type,
+    srs: Option<&SpatialRef>,
+) -> Result<(), Error> {
+    let c_name = CString::new(field_name).map_err(GdalError::from)?;
+
+    let rv = unsafe {
+        let c_geom_field_defn = gdal_sys::OGR_GFld_Create(c_name.as_ptr(), geometry_type);
+        if let Some(srs) = srs {
+            gdal_sys::OGR_GFld_SetSpatialRef(c_geom_field_defn, srs.to_c_hsrs());
+        }
+        let rv = gdal_sys::OGR_L_CreateGeomField(layer.c_layer(), c_geom_field_defn, 1);
+        gdal_sys::OGR_GFld_Destroy(c_geom_field_defn);
+        rv
+    };
+    if rv != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalError::OgrError {
+            err: rv,
+            method_name: "OGR_L_CreateGeomField",
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The subtype of a field of a layer (eg Boolean or JSON), which the gdal crate doesn't expose.
+pub(crate) fn field_subtype<L: LayerAccess>(
+    layer: &L,
+    field_idx: i32,
+) -> gdal_sys::OGRFieldSubType::Type {
+    unsafe {
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+        let c_field_defn = gdal_sys::OGR_FD_GetFieldDefn(c_defn, field_idx);
+        if c_field_defn.is_null() {
+            return gdal_sys::OGRFieldSubType::OFSTNone;
+        }
+        gdal_sys::OGR_Fld_GetSubType(c_field_defn)
+    }
+}
+
+/// The names of the geometry fields of a layer, in order. File formats usually have a single, unnamed geometry field.
+pub(crate) fn geometry_field_names<L: LayerAccess>(layer: &L) -> Vec<String> {
+    unsafe {
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+        (0..gdal_sys::OGR_FD_GetGeomFieldCount(c_defn))
+            .map(|idx| {
+                let c_geom_field_defn = gdal_sys::OGR_FD_GetGeomFieldDefn(c_defn, idx);
+                if c_geom_field_defn.is_null() {
+                    return String::new();
+                }
+                crate::_string(gdal_sys::OGR_GFld_GetNameRef(c_geom_field_defn))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,75 @@
+use crate::Error;
+use gdal::errors::GdalError;
+use gdal::vector::LayerAccess;
+use gdal::Dataset;
+use std::ffi::CString;
+
+/// The name prefix of layers written to temporarily, eg while a layer is being replaced.
+///
+/// Layers with this prefix left behind by an interrupted write are removed by [`list_and_drop_temp_layers`].
+pub const TEMP_LAYER_PREFIX: &str = "_polars_gdal_tmp_";
+
+/// Delete a layer of a dataset by name.
+///
+/// The dataset must be opened in update mode, and its driver must support deleting layers (eg GeoPackage or SQLite).
+///
+/// # Example
+/// ```rust # ignore
+/// let mut dataset = gdal::Dataset::open_ex("outputs.gpkg", DatasetOptions { open_flags: GdalOpenFlags::GDAL_OF_UPDATE, ..Default::default() })?;
+/// polars_gdal::delete_layer(&mut dataset, "stale_results")?;
+/// ```
+pub fn delete_layer(dataset: &mut Dataset, layer_name: &str) -> Result<(), Error> {
+    let layer_idx = dataset
+        .layers()
+        .position(|layer| layer.name() == layer_name)
+        .ok_or_else(|| Error::LayerNotFound(layer_name.to_owned()))?;
+
+    let rv = unsafe { gdal_sys::GDALDatasetDeleteLayer(dataset.c_dataset(), layer_idx as i32) };
+    if rv != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalError::OgrError {
+            err: rv,
+            method_name: "GDALDatasetDeleteLayer",
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Rename a layer of a dataset.
+///
+/// The dataset must be opened in update mode, and its driver must support renaming layers (eg GeoPackage, Shapefile or FlatGeobuf).
+pub fn rename_layer(dataset: &mut Dataset, layer_name: &str, new_name: &str) -> Result<(), Error> {
+    let layer = dataset
+        .layer_by_name(layer_name)
+        .map_err(|_| Error::LayerNotFound(layer_name.to_owned()))?;
+    let c_new_name = CString::new(new_name).map_err(GdalError::from)?;
+
+    let rv = unsafe { gdal_sys::OGR_L_Rename(layer.c_layer(), c_new_name.as_ptr()) };
+    if rv != gdal_sys::OGRErr::OGRERR_NONE {
+        return Err(GdalError::OgrError {
+            err: rv,
+            method_name: "OGR_L_Rename",
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Delete every layer whose name starts with [`TEMP_LAYER_PREFIX`], returning the names of the deleted layers.
+///
+/// Run this on a dataset before writing to it to clean up after writes that were interrupted.
+pub fn list_and_drop_temp_layers(dataset: &mut Dataset) -> Result<Vec<String>, Error> {
+    let temp_layers: Vec<String> = dataset
+        .layers()
+        .map(|layer| layer.name())
+        .filter(|name| name.starts_with(TEMP_LAYER_PREFIX))
+        .collect();
+
+    for name in &temp_layers {
+        delete_layer(dataset, name)?;
+    }
+
+    Ok(temp_layers)
+}
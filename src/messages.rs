@@ -0,0 +1,135 @@
+use gdal_sys::{CPLErr, CPLErrorNum};
+use std::cell::RefCell;
+use std::ffi::c_char;
+
+/// A warning or non-fatal error emitted by GDAL (eg a ring with the wrong orientation, or a field value that didn't fit its field).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GdalMessage {
+    /// The severity of the message.
+    pub level: GdalMessageLevel,
+
+    /// The GDAL error number, eg `CPLE_AppDefined` (1) or `CPLE_NotSupported` (6).
+    pub error_number: i32,
+
+    /// The message text.
+    pub message: String,
+}
+
+/// The severity of a [`GdalMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GdalMessageLevel {
+    /// A `CE_Warning` message.
+    Warning,
+
+    /// A `CE_Failure` message. Failures that GDAL recovered from (eg a feature it couldn't read) don't fail the read.
+    Failure,
+}
+
+/// Run `f` and collect the warnings and failures GDAL emits on this thread meanwhile.
+///
+/// GDAL error handlers are per-thread, so messages emitted by other threads (including other reads) aren't collected.
+/// Errors are still recorded as GDAL's last error, so they are still turned into [`crate::Error::Gdal`].
+pub(crate) fn capture_messages<T>(f: impl FnOnce() -> T) -> (T, Vec<GdalMessage>) {
+    let handler = ScopedHandler::enter();
+    SCOPE.with(|scope| scope.borrow_mut().captures.push(vec![]));
+    let result = f();
+    let messages = SCOPE.with(|scope| scope.borrow_mut().captures.pop().unwrap_or_default());
+    drop(handler);
+
+    (result, messages)
+}
+
+/// Forward the messages GDAL emits on this thread to the `log` crate until the returned guard is dropped.
+///
+/// Does nothing without the `log` feature, leaving messages to GDAL's default handler (which prints them to stderr).
+pub(crate) fn log_scope() -> Option<ScopedHandler> {
+    cfg!(feature = "log").then(ScopedHandler::enter)
+}
+
+thread_local! {
+    static SCOPE: RefCell<Scope> = RefCell::new(Scope::default());
+}
+
+/// The state of this crate's GDAL error handler on the current thread.
+#[derive(Default)]
+struct Scope {
+    /// The number of nested [`ScopedHandler`]s. The handler is installed while this is non-zero.
+    depth: usize,
+
+    /// The buffers of the nested [`capture_messages`] calls. Messages go to the innermost one.
+    captures: Vec<Vec<GdalMessage>>,
+}
+
+/// This crate's error handler, pushed on GDAL's handler stack for the current thread by the outermost guard and popped when it's dropped
+/// (including when unwinding).
+pub(crate) struct ScopedHandler {
+    /// The number of capture buffers when the guard was created, so that a buffer left by a panic is discarded.
+    captures: usize,
+}
+
+impl ScopedHandler {
+    fn enter() -> Self {
+        SCOPE.with(|scope| {
+            let mut scope = scope.borrow_mut();
+            if scope.depth == 0 {
+                unsafe {
+                    gdal_sys::CPLPushErrorHandlerEx(Some(handle_message), std::ptr::null_mut());
+                    gdal_sys::CPLSetCurrentErrorHandlerCatchDebug(cfg!(feature = "log") as i32);
+                }
+            }
+            scope.depth += 1;
+            Self {
+                captures: scope.captures.len(),
+            }
+        })
+    }
+}
+
+impl Drop for ScopedHandler {
+    fn drop(&mut self) {
+        SCOPE.with(|scope| {
+            let mut scope = scope.borrow_mut();
+            scope.captures.truncate(self.captures);
+            scope.depth -= 1;
+            if scope.depth == 0 {
+                unsafe { gdal_sys::CPLPopErrorHandler() };
+            }
+        });
+    }
+}
+
+unsafe extern "C" fn handle_message(
+    class: CPLErr::Type,
+    error_number: CPLErrorNum,
+    message: *const c_char,
+) {
+    if message.is_null() {
+        return;
+    }
+    let message = crate::_string(message);
+
+    #[cfg(feature = "log")]
+    match class {
+        CPLErr::CE_Debug => log::debug!(target: "gdal", "{}", message),
+        CPLErr::CE_Warning => log::warn!(target: "gdal", "{}", message),
+        CPLErr::CE_Failure | CPLErr::CE_Fatal => log::error!(target: "gdal", "{}", message),
+        _ => {}
+    }
+
+    let level = match class {
+        CPLErr::CE_Warning => GdalMessageLevel::Warning,
+        CPLErr::CE_Failure | CPLErr::CE_Fatal => GdalMessageLevel::Failure,
+        _ => return,
+    };
+    let _ = SCOPE.try_with(|scope| {
+        if let Ok(mut scope) = scope.try_borrow_mut() {
+            if let Some(messages) = scope.captures.last_mut() {
+                messages.push(GdalMessage {
+                    level,
+                    error_number,
+                    message,
+                });
+            }
+        }
+    });
+}
@@ -0,0 +1,429 @@
+use crate::driver::open_dataset;
+use crate::{layer_from_params, resolve_read_path, Error, ReadParams};
+use gdal::errors::GdalError;
+use gdal::vector::sql::Dialect;
+use gdal::vector::{LayerAccess, OGRFieldType, OGRwkbGeometryType};
+use gdal::Metadata;
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+
+/// The table QGIS stores layer styles in, in GeoPackage and SQLite datasets.
+const LAYER_STYLES_TABLE: &str = "layer_styles";
+
+/// Layer-level metadata, including the UX hints QGIS attaches to a layer.
+///
+/// DataFrames only carry feature data, so reading a layer into a DataFrame and writing it back out loses anything attached to the layer itself.
+/// Read this alongside the DataFrame with [`layer_metadata_from_resource`] and pass it to `WriteParams::layer_metadata` to preserve it.
+///
+/// The QGIS hints are read from and written to the layer's default style in the `layer_styles` table, which is where QGIS loads
+/// them from. Only GeoPackage and SQLite datasets have this table, so the hints aren't written to other formats.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerMetadata {
+    /// The expression QGIS uses to name features in identify results and forms.
+    pub display_expression: Option<String>,
+
+    /// Fields hidden from QGIS attribute forms and identify results, with the `Hidden` edit widget.
+    pub hidden_fields: Vec<String>,
+
+    /// Fields left out of the identify results of QGIS Server (WMS `GetFeatureInfo`), with the `HideFromWms` field flag.
+    pub identify_excluded_fields: Vec<String>,
+
+    /// The metadata items in the layer's default metadata domain.
+    pub items: Vec<(String, String)>,
+}
+
+impl LayerMetadata {
+    /// Whether any of the QGIS hints is set.
+    fn has_qgis_hints(&self) -> bool {
+        self.display_expression.is_some()
+            || !self.hidden_fields.is_empty()
+            || !self.identify_excluded_fields.is_empty()
+    }
+}
+
+/// Read the layer-level metadata of a resource.
+///
+/// The layer is selected with `ReadParams::layer_name` or `ReadParams::layer_index`.
+/// For GeoPackages and SQLite databases, the QGIS hints are read from the layer's default QGIS style stored in the `layer_styles` table.
+pub fn layer_metadata_from_resource<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<LayerMetadata, Error> {
+    let params = params.unwrap_or_default();
+    let gdal_options: gdal::DatasetOptions = (&params).into();
+
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset(&path, gdal_options)?;
+    let layer = layer_from_params(&dataset, &params)?;
+
+    let mut metadata = LayerMetadata::default();
+    for item in layer.metadata_domain("").unwrap_or_default() {
+        if let Some((key, value)) = item.split_once('=') {
+            metadata.items.push((key.to_owned(), value.to_owned()));
+        }
+    }
+
+    // Querying a missing table would emit a GDAL error, which shows up in read reports and strict-mode captures
+    let driver_name = dataset.driver().short_name();
+    if !matches!(driver_name.as_str(), "GPKG" | "SQLite")
+        || dataset.layer_by_name(LAYER_STYLES_TABLE).is_err()
+    {
+        return Ok(metadata);
+    }
+
+    // The most recent default style wins, as in QGIS
+    let query = format!(
+        "SELECT styleQML FROM {} WHERE f_table_name = '{}' ORDER BY useAsDefault DESC, update_time DESC LIMIT 1",
+        LAYER_STYLES_TABLE,
+        layer.name().replace('\'', "''")
+    );
+    if let Ok(Some(mut result_set)) = dataset.execute_sql(&query, None, Dialect::DEFAULT) {
+        let qml = result_set
+            .features()
+            .next()
+            .and_then(|feature| feature.field("styleQML").ok()?)
+            .and_then(|value| value.into_string());
+        if let Some(qml) = qml {
+            metadata.display_expression = qml_display_expression(&qml);
+            let fields = qml_fields(&qml);
+            metadata.hidden_fields = fields
+                .iter()
+                .filter(|field| field.body.contains("<editWidget type=\"Hidden\""))
+                .map(|field| field.name.clone())
+                .collect();
+            metadata.identify_excluded_fields = qml_wms_excluded_fields(&qml, &fields);
+        }
+    }
+
+    Ok(metadata)
+}
+
+/// Write the metadata items of a newly created layer.
+pub(crate) fn set_layer_metadata<L: Metadata>(
+    layer: &mut L,
+    metadata: &LayerMetadata,
+) -> Result<(), Error> {
+    for (key, value) in &metadata.items {
+        layer.set_metadata_item(key, value, "")?;
+    }
+
+    Ok(())
+}
+
+/// Write the QGIS hints of `metadata` as the default QGIS style of a layer, adding a row to the `layer_styles` table.
+///
+/// The layer's previous styles are kept, but are no longer the default. Datasets other than GeoPackage and SQLite are left untouched.
+pub(crate) fn write_qgis_style(
+    c_dataset: gdal_sys::GDALDatasetH,
+    c_layer: gdal_sys::OGRLayerH,
+    metadata: &LayerMetadata,
+) -> Result<(), Error> {
+    if !metadata.has_qgis_hints() {
+        return Ok(());
+    }
+    let driver_name = crate::_string(unsafe {
+        gdal_sys::GDALGetDriverShortName(gdal_sys::GDALGetDatasetDriver(c_dataset))
+    });
+    if driver_name != "GPKG" && driver_name != "SQLite" {
+        return Ok(());
+    }
+
+    let table_name = crate::_string(unsafe { gdal_sys::OGR_L_GetName(c_layer) });
+    let geometry_column = crate::_string(unsafe { gdal_sys::OGR_L_GetGeometryColumn(c_layer) });
+    let c_styles_name = CString::new(LAYER_STYLES_TABLE).unwrap();
+    let mut c_styles =
+        unsafe { gdal_sys::GDALDatasetGetLayerByName(c_dataset, c_styles_name.as_ptr()) };
+    if c_styles.is_null() {
+        c_styles = create_layer_styles_table(c_dataset, &c_styles_name)?;
+    } else {
+        let update = format!(
+            "UPDATE {} SET useAsDefault = 0 WHERE f_table_name = '{}' AND f_geometry_column = '{}'",
+            LAYER_STYLES_TABLE,
+            table_name.replace('\'', "''"),
+            geometry_column.replace('\'', "''")
+        );
+        let c_update = CString::new(update).map_err(GdalError::from)?;
+        let c_result_set = unsafe {
+            gdal_sys::GDALDatasetExecuteSQL(
+                c_dataset,
+                c_update.as_ptr(),
+                std::ptr::null_mut(),
+                std::ptr::null(),
+            )
+        };
+        if !c_result_set.is_null() {
+            unsafe { gdal_sys::GDALDatasetReleaseResultSet(c_dataset, c_result_set) };
+        }
+    }
+
+    let qml = qgis_style_qml(metadata);
+    let values = [
+        ("f_table_catalog", ""),
+        ("f_table_schema", ""),
+        ("f_table_name", table_name.as_str()),
+        ("f_geometry_column", geometry_column.as_str()),
+        ("styleName", table_name.as_str()),
+        ("styleQML", qml.as_str()),
+        ("styleSLD", ""),
+        ("useAsDefault", "1"),
+        ("description", ""),
+        ("owner", ""),
+        ("ui", ""),
+    ];
+    let c_values = values
+        .iter()
+        .map(|(name, value)| Ok((CString::new(*name).unwrap(), CString::new(*value)?)))
+        .collect::<Result<Vec<_>, std::ffi::NulError>>()
+        .map_err(GdalError::from)?;
+    unsafe {
+        let c_feature = gdal_sys::OGR_F_Create(gdal_sys::OGR_L_GetLayerDefn(c_styles));
+        for (c_name, c_value) in &c_values {
+            let idx = gdal_sys::OGR_F_GetFieldIndex(c_feature, c_name.as_ptr());
+            if idx >= 0 {
+                gdal_sys::OGR_F_SetFieldString(c_feature, idx, c_value.as_ptr());
+            }
+        }
+        gdal_sys::OGR_F_FillUnsetWithDefault(c_feature, 0, std::ptr::null_mut());
+        let rv = gdal_sys::OGR_L_CreateFeature(c_styles, c_feature);
+        gdal_sys::OGR_F_Destroy(c_feature);
+        if rv != gdal_sys::OGRErr::OGRERR_NONE {
+            return Err(GdalError::OgrError {
+                err: rv,
+                method_name: "OGR_L_CreateFeature",
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Create the `layer_styles` table with the fields QGIS uses.
+fn create_layer_styles_table(
+    c_dataset: gdal_sys::GDALDatasetH,
+    c_name: &CStr,
+) -> Result<gdal_sys::OGRLayerH, Error> {
+    let c_fid_option = CString::new("FID=id").unwrap();
+    let mut c_options = [c_fid_option.as_ptr() as *mut c_char, std::ptr::null_mut()];
+    let c_styles = unsafe {
+        gdal_sys::GDALDatasetCreateLayer(
+            c_dataset,
+            c_name.as_ptr(),
+            std::ptr::null_mut(),
+            OGRwkbGeometryType::wkbNone,
+            c_options.as_mut_ptr(),
+        )
+    };
+    if c_styles.is_null() {
+        return Err(crate::_last_null_pointer_err("GDALDatasetCreateLayer").into());
+    }
+
+    let fields = [
+        ("f_table_catalog", OGRFieldType::OFTString, None),
+        ("f_table_schema", OGRFieldType::OFTString, None),
+        ("f_table_name", OGRFieldType::OFTString, None),
+        ("f_geometry_column", OGRFieldType::OFTString, None),
+        ("styleName", OGRFieldType::OFTString, None),
+        ("styleQML", OGRFieldType::OFTString, None),
+        ("styleSLD", OGRFieldType::OFTString, None),
+        ("useAsDefault", OGRFieldType::OFTInteger, None),
+        ("description", OGRFieldType::OFTString, None),
+        ("owner", OGRFieldType::OFTString, None),
+        ("ui", OGRFieldType::OFTString, None),
+        (
+            "update_time",
+            OGRFieldType::OFTDateTime,
+            Some("CURRENT_TIMESTAMP"),
+        ),
+    ];
+    for (name, field_type, default) in fields {
+        let c_name = CString::new(name).unwrap();
+        let c_default = default.map(|default| CString::new(default).unwrap());
+        let rv = unsafe {
+            let c_field_defn = gdal_sys::OGR_Fld_Create(c_name.as_ptr(), field_type);
+            if field_type == OGRFieldType::OFTInteger {
+                gdal_sys::OGR_Fld_SetSubType(c_field_defn, gdal_sys::OGRFieldSubType::OFSTBoolean);
+            }
+            if let Some(c_default) = &c_default {
+                gdal_sys::OGR_Fld_SetDefault(c_field_defn, c_default.as_ptr());
+            }
+            let rv = gdal_sys::OGR_L_CreateField(c_styles, c_field_defn, 1);
+            gdal_sys::OGR_Fld_Destroy(c_field_defn);
+            rv
+        };
+        if rv != gdal_sys::OGRErr::OGRERR_NONE {
+            return Err(GdalError::OgrError {
+                err: rv,
+                method_name: "OGR_L_CreateField",
+            }
+            .into());
+        }
+    }
+
+    Ok(c_styles)
+}
+
+/// A QGIS QML style holding the QGIS hints of `metadata`.
+fn qgis_style_qml(metadata: &LayerMetadata) -> String {
+    let mut field_names: Vec<&String> = metadata.hidden_fields.iter().collect();
+    for name in &metadata.identify_excluded_fields {
+        if !field_names.contains(&name) {
+            field_names.push(name);
+        }
+    }
+
+    let mut qml = String::from(
+        "<!DOCTYPE qgis PUBLIC 'http://mrcc.com/qgis.dtd' 'SYSTEM'>\n<qgis version=\"3.28.0\" styleCategories=\"Fields\">\n",
+    );
+    qml.push_str("  <fieldConfiguration>\n");
+    for name in field_names {
+        let flags = if metadata.identify_excluded_fields.contains(name) {
+            "HideFromWms"
+        } else {
+            "None"
+        };
+        qml.push_str(&format!(
+            "    <field name=\"{}\" configurationFlags=\"{}\">\n",
+            xml_escape(name),
+            flags
+        ));
+        if metadata.hidden_fields.contains(name) {
+            qml.push_str("      <editWidget type=\"Hidden\">\n        <config>\n          <Option/>\n        </config>\n      </editWidget>\n");
+        }
+        qml.push_str("    </field>\n");
+    }
+    qml.push_str("  </fieldConfiguration>\n");
+    if let Some(display_expression) = &metadata.display_expression {
+        qml.push_str(&format!(
+            "  <previewExpression>{}</previewExpression>\n",
+            xml_escape(display_expression)
+        ));
+    }
+    qml.push_str("</qgis>\n");
+
+    qml
+}
+
+/// Extract the `<previewExpression>` from a QGIS QML style.
+fn qml_display_expression(qml: &str) -> Option<String> {
+    let start = qml.find("<previewExpression>")? + "<previewExpression>".len();
+    let end = start + qml[start..].find("</previewExpression>")?;
+    let expression = xml_unescape(qml[start..end].trim());
+
+    if expression.is_empty() {
+        None
+    } else {
+        Some(expression)
+    }
+}
+
+/// A `<field>` element of the `<fieldConfiguration>` of a QGIS QML style.
+struct QmlField<'q> {
+    name: String,
+
+    /// The opening tag, with the field's attributes.
+    tag: &'q str,
+
+    /// The content between the opening and closing tags.
+    body: &'q str,
+}
+
+/// The fields configured in a QGIS QML style.
+///
+/// Only the `<fieldConfiguration>` section is scanned, since other sections (eg `<editable>`) also hold `<field>` elements.
+fn qml_fields(qml: &str) -> Vec<QmlField<'_>> {
+    let section = match xml_element_content(qml, "fieldConfiguration") {
+        Some(section) => section,
+        None => return vec![],
+    };
+
+    let mut fields = vec![];
+    let mut rest = section;
+    while let Some(start) = rest.find("<field ") {
+        rest = &rest[start..];
+        let tag_end = match rest.find('>') {
+            Some(i) => i + 1,
+            None => break,
+        };
+        let tag = &rest[..tag_end];
+        let body = if tag.ends_with("/>") {
+            ""
+        } else {
+            let body_end = rest.find("</field>").unwrap_or(rest.len());
+            &rest[tag_end..body_end.max(tag_end)]
+        };
+        rest = &rest[tag_end + body.len()..];
+
+        if let Some(name) = xml_attribute(tag, "name") {
+            fields.push(QmlField { name, tag, body });
+        }
+    }
+
+    fields
+}
+
+/// Extract the fields left out of WMS `GetFeatureInfo` responses, from the `HideFromWms` field flag of QGIS >= 3.16 or
+/// the `<excludeAttributesWMS>` list of older versions.
+fn qml_wms_excluded_fields(qml: &str, fields: &[QmlField]) -> Vec<String> {
+    let mut excluded: Vec<String> = fields
+        .iter()
+        .filter(|field| {
+            xml_attribute(field.tag, "configurationFlags").map_or(false, |flags| {
+                flags.split('|').any(|flag| flag == "HideFromWms")
+            })
+        })
+        .map(|field| field.name.clone())
+        .collect();
+
+    if let Some(section) = xml_element_content(qml, "excludeAttributesWMS") {
+        let mut rest = section;
+        while let Some(start) = rest.find("<attribute>") {
+            rest = &rest[start + "<attribute>".len()..];
+            let end = match rest.find("</attribute>") {
+                Some(end) => end,
+                None => break,
+            };
+            let name = xml_unescape(rest[..end].trim());
+            if !excluded.contains(&name) {
+                excluded.push(name);
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    excluded
+}
+
+/// The content of the first `<name>` element of an XML document.
+fn xml_element_content<'x>(xml: &'x str, name: &str) -> Option<&'x str> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    Some(&xml[start..end])
+}
+
+/// The unescaped value of an attribute of an XML opening tag.
+fn xml_attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!(" {}=\"", name);
+    let start = tag.find(&pattern)? + pattern.len();
+    let end = start + tag[start..].find('"')?;
+    Some(xml_unescape(&tag[start..end]))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
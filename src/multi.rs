@@ -0,0 +1,93 @@
+use crate::concurrent::merge_frames;
+use crate::geom::geometry_series;
+use crate::{df_from_resource, Error, ReadParams};
+use polars::prelude::*;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// How [`df_from_resources`] identifies the same feature read from more than one resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deduplicate {
+    /// Keep every feature.
+    None,
+
+    /// Features with the same FID are the same feature. Suits sources with stable identifiers across files, such as OSM extracts.
+    Fid,
+
+    /// Features with identical geometries are the same feature. Suits gridded deliveries that repeat features along tile edges.
+    Geometry,
+}
+
+impl Default for Deduplicate {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// Read several resources with the same schema (eg the tiles of a tiled delivery) into a single DataFrame.
+///
+/// Rows are concatenated in the order of `paths`. When tiles overlap, features repeated in more than one tile are dropped according to `deduplicate`,
+/// keeping the first occurrence. Features with a null FID or null geometry are never dropped.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{df_from_resources, Deduplicate};
+///
+/// let df = df_from_resources(&["tile_0_0.gpkg", "tile_0_1.gpkg", "tile_1_0.gpkg"], None, Deduplicate::Geometry)?;
+/// ```
+pub fn df_from_resources<P: AsRef<Path>>(
+    paths: &[P],
+    params: Option<ReadParams>,
+    deduplicate: Deduplicate,
+) -> Result<DataFrame, Error> {
+    const DEDUPLICATE_FID_COLUMN: &str = "__polars_gdal_deduplicate_fid";
+
+    let mut params = params.unwrap_or_default();
+    let drop_fid_column = deduplicate == Deduplicate::Fid && params.fid_column_name.is_none();
+    if drop_fid_column {
+        params.fid_column_name = Some(DEDUPLICATE_FID_COLUMN);
+    }
+
+    let frames = paths
+        .iter()
+        .map(|path| df_from_resource(path, Some(params.clone())))
+        .collect::<Result<Vec<_>, _>>()?;
+    let df = merge_frames(frames)?;
+
+    let keep = match deduplicate {
+        Deduplicate::None => return Ok(df),
+        Deduplicate::Fid => {
+            let mut seen = HashSet::new();
+            let fids = df.column(params.fid_column_name.unwrap_or(DEDUPLICATE_FID_COLUMN))?;
+            fids.u64()?
+                .into_iter()
+                .map(|fid| fid.map_or(true, |fid| seen.insert(fid)))
+                .collect::<BooleanChunked>()
+        }
+        Deduplicate::Geometry => {
+            let geometry_column_name = params.geometry_column_name.unwrap_or("geometry");
+            let (geometries, _) =
+                geometry_series(df.column(geometry_column_name)?, params.geometry_format)?;
+            let mut seen = HashSet::new();
+            match geometries.dtype() {
+                DataType::Utf8 => geometries
+                    .utf8()?
+                    .into_iter()
+                    .map(|geometry| geometry.map_or(true, |geometry| seen.insert(geometry.as_bytes())))
+                    .collect::<BooleanChunked>(),
+                _ => geometries
+                    .binary()?
+                    .into_iter()
+                    .map(|geometry| geometry.map_or(true, |geometry| seen.insert(geometry)))
+                    .collect::<BooleanChunked>(),
+            }
+        }
+    };
+
+    let mut df = df.filter(&keep)?;
+    if drop_fid_column {
+        df = df.drop(DEDUPLICATE_FID_COLUMN)?;
+    }
+
+    Ok(df)
+}
@@ -0,0 +1,111 @@
+use crate::Error;
+use polars::prelude::*;
+
+/// A value that stands in for null in formats that can't represent nulls, such as Shapefile (DBF) numbers and dates.
+///
+/// Sentinels are given per column as `(column name, sentinel)` pairs. A column may be listed more than once.
+///
+/// # Example
+/// ```
+/// use polars_gdal::NullSentinel;
+///
+/// let sentinels = [("elevation", NullSentinel::Integer(-9999)), ("name", NullSentinel::Blank)];
+/// let mut params = polars_gdal::ReadParams::default();
+/// params.null_sentinels = Some(&sentinels);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullSentinel<'a> {
+    /// Strings that are empty or only whitespace, such as padded DBF fields. Written as an empty string.
+    Blank,
+
+    /// Dates and datetimes at the Unix epoch (`1970-01-01`). Written as the Unix epoch.
+    EpochDate,
+
+    /// Numbers equal to this integer, eg `-9999`.
+    Integer(i64),
+
+    /// Numbers equal to this float.
+    Real(f64),
+
+    /// Strings equal to this string, eg `"N/A"`.
+    String(&'a str),
+}
+
+/// Replace the sentinel values of a series read from GDAL with nulls.
+pub(crate) fn nullify_sentinels(
+    series: Series,
+    sentinels: &[(&str, NullSentinel)],
+) -> Result<Series, Error> {
+    let name = series.name().to_owned();
+    let mut series = series;
+    for (_, sentinel) in sentinels.iter().filter(|(n, _)| *n == name) {
+        let mask = sentinel_mask(&series, *sentinel)?;
+        let nulls = Series::full_null(series.name(), series.len(), series.dtype());
+        series = series.zip_with(&!&mask, &nulls)?;
+    }
+
+    Ok(series)
+}
+
+/// Replace the nulls of the listed columns with their sentinel values, so they can be written to formats without nulls.
+pub(crate) fn fill_null_sentinels(
+    df: &DataFrame,
+    sentinels: &[(&str, NullSentinel)],
+) -> Result<DataFrame, Error> {
+    let mut df = df.clone();
+    for (name, sentinel) in sentinels {
+        let series = df.column(name)?;
+        let fill = sentinel_series(series.name(), series.len(), *sentinel).cast(series.dtype())?;
+        let filled = series.zip_with(&series.is_not_null(), &fill)?;
+        df.with_column(filled)?;
+    }
+
+    Ok(df)
+}
+
+/// Which values of a series are the sentinel. Nulls are never the sentinel.
+fn sentinel_mask(series: &Series, sentinel: NullSentinel) -> Result<BooleanChunked, Error> {
+    let mask = match sentinel {
+        NullSentinel::Blank => series
+            .utf8()?
+            .into_iter()
+            .map(|v| v.map_or(false, |v| v.trim().is_empty()))
+            .collect(),
+        NullSentinel::String(sentinel) => series
+            .utf8()?
+            .into_iter()
+            .map(|v| v == Some(sentinel))
+            .collect(),
+        NullSentinel::EpochDate | NullSentinel::Integer(_) => {
+            let sentinel = match sentinel {
+                NullSentinel::Integer(sentinel) => sentinel,
+                _ => 0,
+            };
+            series
+                .cast(&DataType::Int64)?
+                .i64()?
+                .into_iter()
+                .map(|v| v == Some(sentinel))
+                .collect()
+        }
+        NullSentinel::Real(sentinel) => series
+            .cast(&DataType::Float64)?
+            .f64()?
+            .into_iter()
+            .map(|v| v == Some(sentinel))
+            .collect(),
+    };
+
+    Ok(mask)
+}
+
+/// A series of `len` sentinel values, to be cast to the dtype of the column being filled.
+fn sentinel_series(name: &str, len: usize, sentinel: NullSentinel) -> Series {
+    match sentinel {
+        NullSentinel::Blank => Utf8Chunked::full(name, "", len).into_series(),
+        NullSentinel::String(sentinel) => Utf8Chunked::full(name, sentinel, len).into_series(),
+        NullSentinel::EpochDate => Int32Chunked::full(name, 0, len).into_series(),
+        NullSentinel::Integer(sentinel) => Int64Chunked::full(name, sentinel, len).into_series(),
+        NullSentinel::Real(sentinel) => Float64Chunked::full(name, sentinel, len).into_series(),
+    }
+}
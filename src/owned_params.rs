@@ -0,0 +1,717 @@
+use crate::{
+    Error, GeometryFormat, GeometryTypePolicy, HttpOptions, LayerMetadata, MixedTypePolicy,
+    NullGeometryPolicy, NullSentinel, OutputCompression, Progress, ReadParams,
+    SpreadsheetFieldTypes, SpreadsheetHeaders, SpreadsheetOptions, TimeFallback, WriteParams,
+};
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::Geometry;
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// [`ReadParams`] without borrowed fields, so they can be built dynamically, stored, and sent between threads or async tasks.
+///
+/// GDAL objects can't be sent between threads, so the spatial filter is kept as WKT and the target SRS as a [`SrsDefinition`].
+/// They are rebuilt by [`ReadParamsOwned::with_params`] for each read.
+///
+/// With the `serde` feature, the params can be deserialized from a job definition (eg JSON or YAML). Missing fields take their
+/// default, `open_flags` is the bits of the flags, and `progress` and `cancel` are skipped.
+///
+/// # Example
+/// ```rust # ignore
+/// let mut params = polars_gdal::ReadParamsOwned::default();
+/// params.layer_name = Some(requested_layer.to_string());
+/// params.columns = Some(vec!["name".to_string(), "population".to_string()]);
+///
+/// let df = params.with_params(|params| polars_gdal::df_from_resource("cities.gpkg", Some(params)))?;
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReadParamsOwned {
+    /// See [`ReadParams::open_flags`].
+    #[cfg_attr(feature = "serde", serde(with = "open_flags"))]
+    pub open_flags: gdal::GdalOpenFlags,
+
+    /// See [`ReadParams::allowed_drivers`].
+    pub allowed_drivers: Option<Vec<String>>,
+
+    /// See [`ReadParams::open_options`].
+    pub open_options: Option<Vec<String>>,
+
+    /// See [`ReadParams::config_options`].
+    pub config_options: Option<Vec<(String, String)>>,
+
+    /// See [`ReadParams::http_options`].
+    pub http_options: Option<HttpOptions>,
+
+    /// See [`ReadParams::csv_column_types`].
+    pub csv_column_types: Option<Vec<(String, String)>>,
+
+    /// See [`ReadParams::spreadsheet_options`].
+    pub spreadsheet_options: Option<SpreadsheetOptionsOwned>,
+
+    /// See [`ReadParams::sibling_files`].
+    pub sibling_files: Option<Vec<String>>,
+
+    /// See [`ReadParams::layer_name`].
+    pub layer_name: Option<String>,
+
+    /// See [`ReadParams::layer_index`].
+    pub layer_index: Option<usize>,
+
+    /// See [`ReadParams::fid_column_name`].
+    pub fid_column_name: Option<String>,
+
+    /// See [`ReadParams::geometry_column_name`].
+    pub geometry_column_name: Option<String>,
+
+    /// See [`ReadParams::geometry_format`].
+    pub geometry_format: GeometryFormat,
+
+    /// See [`ReadParams::all_geometry_fields`].
+    pub all_geometry_fields: bool,
+
+    /// See [`ReadParams::style_column_name`].
+    pub style_column_name: Option<String>,
+
+    /// See [`ReadParams::truncating_limit`].
+    pub truncating_limit: Option<usize>,
+
+    /// See [`ReadParams::erroring_limit`].
+    pub erroring_limit: Option<usize>,
+
+    /// See [`ReadParams::offset`].
+    pub offset: Option<usize>,
+
+    /// See [`ReadParams::attribute_filter`].
+    pub attribute_filter: Option<String>,
+
+    /// The geometry of [`ReadParams::spatial_filter`] as WKT.
+    pub spatial_filter_wkt: Option<String>,
+
+    /// See [`ReadParams::bbox`].
+    pub bbox: Option<[f64; 4]>,
+
+    /// See [`ReadParams::fids`].
+    pub fids: Option<Vec<u64>>,
+
+    /// See [`ReadParams::columns`].
+    pub columns: Option<Vec<String>>,
+
+    /// See [`ReadParams::exclude_columns`].
+    pub exclude_columns: Option<Vec<String>>,
+
+    /// See [`ReadParams::geometry_only`].
+    pub geometry_only: bool,
+
+    /// See [`ReadParams::skip_geometry`].
+    pub skip_geometry: bool,
+
+    /// See [`ReadParams::max_input_bytes`].
+    pub max_input_bytes: Option<usize>,
+
+    /// See [`ReadParams::max_columns`].
+    pub max_columns: Option<usize>,
+
+    /// See [`ReadParams::max_string_length`].
+    pub max_string_length: Option<usize>,
+
+    /// See [`ReadParams::max_geometry_vertices`].
+    pub max_geometry_vertices: Option<usize>,
+
+    /// See [`ReadParams::sample_every_n`].
+    pub sample_every_n: Option<usize>,
+
+    /// See [`ReadParams::sample_fraction`].
+    pub sample_fraction: Option<f64>,
+
+    /// See [`ReadParams::sample_seed`].
+    pub sample_seed: Option<u64>,
+
+    /// See [`ReadParams::expected_schema`].
+    pub expected_schema: Option<Schema>,
+
+    /// See [`ReadParams::schema_overrides`].
+    pub schema_overrides: Option<HashMap<String, DataType>>,
+
+    /// See [`ReadParams::null_sentinels`].
+    pub null_sentinels: Option<Vec<(String, NullSentinelOwned)>>,
+
+    /// See [`ReadParams::parse_json_fields`].
+    pub parse_json_fields: bool,
+
+    /// See [`ReadParams::utc_datetimes`].
+    pub utc_datetimes: bool,
+
+    /// See [`ReadParams::mixed_type_policy`].
+    pub mixed_type_policy: MixedTypePolicy,
+
+    /// See [`ReadParams::null_geometry_policy`].
+    pub null_geometry_policy: NullGeometryPolicy,
+
+    /// See [`ReadParams::expected_geometry_type`].
+    pub expected_geometry_type: Option<gdal::vector::OGRwkbGeometryType::Type>,
+
+    /// See [`ReadParams::target_srs`].
+    pub target_srs: Option<SrsDefinition>,
+
+    /// See [`ReadParams::segmentize_max_length`].
+    pub segmentize_max_length: Option<f64>,
+
+    /// See [`ReadParams::snap_to_grid`].
+    pub snap_to_grid: Option<f64>,
+
+    /// See [`ReadParams::force_2d`].
+    pub force_2d: bool,
+
+    /// See [`ReadParams::force_multi`].
+    pub force_multi: bool,
+
+    /// See [`ReadParams::progress`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub progress: Option<ProgressOwned>,
+
+    /// See [`ReadParams::cancel`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl ReadParamsOwned {
+    /// Call `f` with [`ReadParams`] borrowing from these params, eg to pass them to [`df_from_resource`](crate::df_from_resource).
+    ///
+    /// Fails if the spatial filter or target SRS can't be rebuilt from their definitions.
+    pub fn with_params<T>(
+        &self,
+        f: impl FnOnce(ReadParams<'_>) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let allowed_drivers = str_vec(&self.allowed_drivers);
+        let open_options = str_vec(&self.open_options);
+        let config_options = str_pairs(&self.config_options);
+        let csv_column_types = str_pairs(&self.csv_column_types);
+        let sibling_files = str_vec(&self.sibling_files);
+        let columns = str_vec(&self.columns);
+        let exclude_columns = str_vec(&self.exclude_columns);
+        let schema_overrides: Option<HashMap<&str, DataType>> =
+            self.schema_overrides.as_ref().map(|overrides| {
+                overrides
+                    .iter()
+                    .map(|(name, dtype)| (name.as_str(), dtype.clone()))
+                    .collect()
+            });
+        let null_sentinels = sentinels(&self.null_sentinels);
+        let spatial_filter = self
+            .spatial_filter_wkt
+            .as_deref()
+            .map(Geometry::from_wkt)
+            .transpose()?;
+        let target_srs = self
+            .target_srs
+            .as_ref()
+            .map(SrsDefinition::to_srs)
+            .transpose()?;
+
+        f(ReadParams {
+            open_flags: self.open_flags,
+            allowed_drivers: allowed_drivers.as_deref(),
+            open_options: open_options.as_deref(),
+            config_options: config_options.as_deref(),
+            http_options: self.http_options.as_ref(),
+            csv_column_types: csv_column_types.as_deref(),
+            spreadsheet_options: self
+                .spreadsheet_options
+                .as_ref()
+                .map(SpreadsheetOptionsOwned::to_borrowed),
+            sibling_files: sibling_files.as_deref(),
+            layer_name: self.layer_name.as_deref(),
+            layer_index: self.layer_index,
+            fid_column_name: self.fid_column_name.as_deref(),
+            geometry_column_name: self.geometry_column_name.as_deref(),
+            geometry_format: self.geometry_format,
+            all_geometry_fields: self.all_geometry_fields,
+            style_column_name: self.style_column_name.as_deref(),
+            truncating_limit: self.truncating_limit,
+            erroring_limit: self.erroring_limit,
+            offset: self.offset,
+            attribute_filter: self.attribute_filter.as_deref(),
+            spatial_filter: spatial_filter.as_ref(),
+            bbox: self.bbox,
+            fids: self.fids.as_deref(),
+            columns: columns.as_deref(),
+            exclude_columns: exclude_columns.as_deref(),
+            geometry_only: self.geometry_only,
+            skip_geometry: self.skip_geometry,
+            max_input_bytes: self.max_input_bytes,
+            max_columns: self.max_columns,
+            max_string_length: self.max_string_length,
+            max_geometry_vertices: self.max_geometry_vertices,
+            sample_every_n: self.sample_every_n,
+            sample_fraction: self.sample_fraction,
+            sample_seed: self.sample_seed,
+            expected_schema: self.expected_schema.as_ref(),
+            schema_overrides: schema_overrides.as_ref(),
+            null_sentinels: null_sentinels.as_deref(),
+            parse_json_fields: self.parse_json_fields,
+            utc_datetimes: self.utc_datetimes,
+            mixed_type_policy: self.mixed_type_policy,
+            null_geometry_policy: self.null_geometry_policy,
+            expected_geometry_type: self.expected_geometry_type,
+            target_srs: target_srs.as_ref(),
+            segmentize_max_length: self.segmentize_max_length,
+            snap_to_grid: self.snap_to_grid,
+            force_2d: self.force_2d,
+            force_multi: self.force_multi,
+            progress: self.progress.as_ref().map(ProgressOwned::to_borrowed),
+            cancel: self.cancel.as_deref(),
+        })
+    }
+}
+
+impl TryFrom<&ReadParams<'_>> for ReadParamsOwned {
+    type Error = Error;
+
+    /// Copy borrowed params. `progress` and `cancel` are left unset, since they can't be shared beyond their borrow.
+    fn try_from(params: &ReadParams<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            open_flags: params.open_flags,
+            allowed_drivers: string_vec(params.allowed_drivers),
+            open_options: string_vec(params.open_options),
+            config_options: string_pairs(params.config_options),
+            http_options: params.http_options.cloned(),
+            csv_column_types: string_pairs(params.csv_column_types),
+            spreadsheet_options: params.spreadsheet_options.as_ref().map(Into::into),
+            sibling_files: string_vec(params.sibling_files),
+            layer_name: params.layer_name.map(str::to_owned),
+            layer_index: params.layer_index,
+            fid_column_name: params.fid_column_name.map(str::to_owned),
+            geometry_column_name: params.geometry_column_name.map(str::to_owned),
+            geometry_format: params.geometry_format,
+            all_geometry_fields: params.all_geometry_fields,
+            style_column_name: params.style_column_name.map(str::to_owned),
+            truncating_limit: params.truncating_limit,
+            erroring_limit: params.erroring_limit,
+            offset: params.offset,
+            attribute_filter: params.attribute_filter.map(str::to_owned),
+            spatial_filter_wkt: params.spatial_filter.map(|g| g.wkt()).transpose()?,
+            bbox: params.bbox,
+            fids: params.fids.map(<[u64]>::to_vec),
+            columns: string_vec(params.columns),
+            exclude_columns: string_vec(params.exclude_columns),
+            geometry_only: params.geometry_only,
+            skip_geometry: params.skip_geometry,
+            max_input_bytes: params.max_input_bytes,
+            max_columns: params.max_columns,
+            max_string_length: params.max_string_length,
+            max_geometry_vertices: params.max_geometry_vertices,
+            sample_every_n: params.sample_every_n,
+            sample_fraction: params.sample_fraction,
+            sample_seed: params.sample_seed,
+            expected_schema: params.expected_schema.cloned(),
+            schema_overrides: params.schema_overrides.map(|overrides| {
+                overrides
+                    .iter()
+                    .map(|(name, dtype)| ((*name).to_owned(), dtype.clone()))
+                    .collect()
+            }),
+            null_sentinels: owned_sentinels(params.null_sentinels),
+            parse_json_fields: params.parse_json_fields,
+            utc_datetimes: params.utc_datetimes,
+            mixed_type_policy: params.mixed_type_policy,
+            null_geometry_policy: params.null_geometry_policy,
+            expected_geometry_type: params.expected_geometry_type,
+            target_srs: params.target_srs.map(SrsDefinition::from_srs).transpose()?,
+            segmentize_max_length: params.segmentize_max_length,
+            snap_to_grid: params.snap_to_grid,
+            force_2d: params.force_2d,
+            force_multi: params.force_multi,
+            progress: None,
+            cancel: None,
+        })
+    }
+}
+
+/// [`WriteParams`] without borrowed fields, so they can be built dynamically, stored, and sent between threads or async tasks.
+///
+/// GDAL objects can't be sent between threads, so SRSs are kept as [`SrsDefinition`]s, which are rebuilt by
+/// [`WriteParamsOwned::with_params`] for each write.
+///
+/// With the `serde` feature, the params can be deserialized from a job definition (eg JSON or YAML). Missing fields take their
+/// default, and `progress` and `cancel` are skipped.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct WriteParamsOwned {
+    /// See [`WriteParams::layer_name`].
+    pub layer_name: Option<String>,
+
+    /// See [`WriteParams::geometry_column_name`].
+    pub geometry_column_name: Option<String>,
+
+    /// See [`WriteParams::geometry_format`].
+    pub geometry_format: GeometryFormat,
+
+    /// See [`WriteParams::null_geometry_policy`].
+    pub null_geometry_policy: NullGeometryPolicy,
+
+    /// See [`WriteParams::extra_geometry_columns`].
+    pub extra_geometry_columns: Option<Vec<(String, String)>>,
+
+    /// See [`WriteParams::fid_column_name`].
+    pub fid_column_name: Option<String>,
+
+    /// See [`WriteParams::srs`].
+    pub srs: Option<SrsDefinition>,
+
+    /// See [`WriteParams::source_srs`].
+    pub source_srs: Option<SrsDefinition>,
+
+    /// See [`WriteParams::target_srs`].
+    pub target_srs: Option<SrsDefinition>,
+
+    /// See [`WriteParams::geometry_type`].
+    pub geometry_type: Option<gdal::vector::OGRwkbGeometryType::Type>,
+
+    /// See [`WriteParams::geometry_type_policy`].
+    pub geometry_type_policy: GeometryTypePolicy,
+
+    /// See [`WriteParams::options`].
+    pub options: Option<Vec<String>>,
+
+    /// See [`WriteParams::segmentize_max_length`].
+    pub segmentize_max_length: Option<f64>,
+
+    /// See [`WriteParams::snap_to_grid`].
+    pub snap_to_grid: Option<f64>,
+
+    /// See [`WriteParams::force_2d`].
+    pub force_2d: bool,
+
+    /// See [`WriteParams::force_multi`].
+    pub force_multi: bool,
+
+    /// See [`WriteParams::layer_metadata`].
+    pub layer_metadata: Option<LayerMetadata>,
+
+    /// See [`WriteParams::time_fallback`].
+    pub time_fallback: TimeFallback,
+
+    /// See [`WriteParams::null_sentinels`].
+    pub null_sentinels: Option<Vec<(String, NullSentinelOwned)>>,
+
+    /// See [`WriteParams::struct_as_json`].
+    pub struct_as_json: bool,
+
+    /// See [`WriteParams::categorical_as_domain`].
+    pub categorical_as_domain: bool,
+
+    /// See [`WriteParams::id_column`].
+    pub id_column: Option<String>,
+
+    /// See [`WriteParams::strict`].
+    pub strict: bool,
+
+    /// See [`WriteParams::compression`].
+    pub compression: OutputCompression,
+
+    /// See [`WriteParams::progress`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub progress: Option<ProgressOwned>,
+
+    /// See [`WriteParams::cancel`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl WriteParamsOwned {
+    /// Call `f` with [`WriteParams`] borrowing from these params, eg to pass them to [`gdal_bytes_from_df`](crate::gdal_bytes_from_df).
+    ///
+    /// Fails if an SRS can't be rebuilt from its definition.
+    pub fn with_params<T>(
+        &self,
+        f: impl FnOnce(WriteParams<'_>) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let extra_geometry_columns = str_pairs(&self.extra_geometry_columns);
+        let options = str_vec(&self.options);
+        let null_sentinels = sentinels(&self.null_sentinels);
+        let srs = self.srs.as_ref().map(SrsDefinition::to_srs).transpose()?;
+        let source_srs = self
+            .source_srs
+            .as_ref()
+            .map(SrsDefinition::to_srs)
+            .transpose()?;
+        let target_srs = self
+            .target_srs
+            .as_ref()
+            .map(SrsDefinition::to_srs)
+            .transpose()?;
+
+        f(WriteParams {
+            layer_name: self.layer_name.as_deref(),
+            geometry_column_name: self.geometry_column_name.as_deref(),
+            geometry_format: self.geometry_format,
+            null_geometry_policy: self.null_geometry_policy,
+            extra_geometry_columns: extra_geometry_columns.as_deref(),
+            fid_column_name: self.fid_column_name.as_deref(),
+            srs: srs.as_ref(),
+            source_srs: source_srs.as_ref(),
+            target_srs: target_srs.as_ref(),
+            geometry_type: self.geometry_type,
+            geometry_type_policy: self.geometry_type_policy,
+            options: options.as_deref(),
+            segmentize_max_length: self.segmentize_max_length,
+            snap_to_grid: self.snap_to_grid,
+            force_2d: self.force_2d,
+            force_multi: self.force_multi,
+            layer_metadata: self.layer_metadata.as_ref(),
+            time_fallback: self.time_fallback,
+            null_sentinels: null_sentinels.as_deref(),
+            struct_as_json: self.struct_as_json,
+            categorical_as_domain: self.categorical_as_domain,
+            id_column: self.id_column.as_deref(),
+            strict: self.strict,
+            compression: self.compression,
+            progress: self.progress.as_ref().map(ProgressOwned::to_borrowed),
+            cancel: self.cancel.as_deref(),
+        })
+    }
+}
+
+impl TryFrom<&WriteParams<'_>> for WriteParamsOwned {
+    type Error = Error;
+
+    /// Copy borrowed params. `progress` and `cancel` are left unset, since they can't be shared beyond their borrow.
+    fn try_from(params: &WriteParams<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            layer_name: params.layer_name.map(str::to_owned),
+            geometry_column_name: params.geometry_column_name.map(str::to_owned),
+            geometry_format: params.geometry_format,
+            null_geometry_policy: params.null_geometry_policy,
+            extra_geometry_columns: string_pairs(params.extra_geometry_columns),
+            fid_column_name: params.fid_column_name.map(str::to_owned),
+            srs: params.srs.map(SrsDefinition::from_srs).transpose()?,
+            source_srs: params.source_srs.map(SrsDefinition::from_srs).transpose()?,
+            target_srs: params.target_srs.map(SrsDefinition::from_srs).transpose()?,
+            geometry_type: params.geometry_type,
+            geometry_type_policy: params.geometry_type_policy,
+            options: string_vec(params.options),
+            segmentize_max_length: params.segmentize_max_length,
+            snap_to_grid: params.snap_to_grid,
+            force_2d: params.force_2d,
+            force_multi: params.force_multi,
+            layer_metadata: params.layer_metadata.cloned(),
+            time_fallback: params.time_fallback,
+            null_sentinels: owned_sentinels(params.null_sentinels),
+            struct_as_json: params.struct_as_json,
+            categorical_as_domain: params.categorical_as_domain,
+            id_column: params.id_column.map(str::to_owned),
+            strict: params.strict,
+            compression: params.compression,
+            progress: None,
+            cancel: None,
+        })
+    }
+}
+
+/// A spatial reference as WKT and axis mapping strategy, which unlike [`SpatialRef`] can be sent between threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SrsDefinition {
+    /// The SRS as WKT.
+    pub wkt: String,
+
+    /// The axis mapping strategy, eg `OAMS_TRADITIONAL_GIS_ORDER` for longitude / latitude order in geographic SRSs.
+    pub axis_mapping_strategy: gdal_sys::OSRAxisMappingStrategy::Type,
+}
+
+impl SrsDefinition {
+    /// The definition of an SRS.
+    pub fn from_srs(srs: &SpatialRef) -> Result<Self, Error> {
+        Ok(Self {
+            wkt: srs.to_wkt()?,
+            axis_mapping_strategy: unsafe { gdal_sys::OSRGetAxisMappingStrategy(srs.to_c_hsrs()) },
+        })
+    }
+
+    /// The definition of an EPSG SRS, with longitude / latitude (or easting / northing) axis order.
+    pub fn from_epsg(code: u32) -> Result<Self, Error> {
+        Ok(Self {
+            wkt: SpatialRef::from_epsg(code)?.to_wkt()?,
+            axis_mapping_strategy: gdal_sys::OSRAxisMappingStrategy::OAMS_TRADITIONAL_GIS_ORDER,
+        })
+    }
+
+    /// Build the SRS.
+    pub fn to_srs(&self) -> Result<SpatialRef, Error> {
+        let srs = SpatialRef::from_wkt(&self.wkt)?;
+        unsafe { gdal_sys::OSRSetAxisMappingStrategy(srs.to_c_hsrs(), self.axis_mapping_strategy) };
+        Ok(srs)
+    }
+}
+
+/// [`SpreadsheetOptions`] without borrowed fields.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SpreadsheetOptionsOwned {
+    /// See [`SpreadsheetOptions::sheet_name`].
+    pub sheet_name: Option<String>,
+
+    /// See [`SpreadsheetOptions::headers`].
+    pub headers: SpreadsheetHeaders,
+
+    /// See [`SpreadsheetOptions::field_types`].
+    pub field_types: SpreadsheetFieldTypes,
+}
+
+impl SpreadsheetOptionsOwned {
+    fn to_borrowed(&self) -> SpreadsheetOptions<'_> {
+        SpreadsheetOptions {
+            sheet_name: self.sheet_name.as_deref(),
+            headers: self.headers,
+            field_types: self.field_types,
+        }
+    }
+}
+
+impl From<&SpreadsheetOptions<'_>> for SpreadsheetOptionsOwned {
+    fn from(options: &SpreadsheetOptions<'_>) -> Self {
+        Self {
+            sheet_name: options.sheet_name.map(str::to_owned),
+            headers: options.headers,
+            field_types: options.field_types,
+        }
+    }
+}
+
+/// [`NullSentinel`] without borrowed fields.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NullSentinelOwned {
+    /// See [`NullSentinel::Blank`].
+    Blank,
+
+    /// See [`NullSentinel::EpochDate`].
+    EpochDate,
+
+    /// See [`NullSentinel::Integer`].
+    Integer(i64),
+
+    /// See [`NullSentinel::Real`].
+    Real(f64),
+
+    /// See [`NullSentinel::String`].
+    String(String),
+}
+
+impl NullSentinelOwned {
+    fn to_borrowed(&self) -> NullSentinel<'_> {
+        match self {
+            Self::Blank => NullSentinel::Blank,
+            Self::EpochDate => NullSentinel::EpochDate,
+            Self::Integer(value) => NullSentinel::Integer(*value),
+            Self::Real(value) => NullSentinel::Real(*value),
+            Self::String(value) => NullSentinel::String(value),
+        }
+    }
+}
+
+impl From<NullSentinel<'_>> for NullSentinelOwned {
+    fn from(sentinel: NullSentinel<'_>) -> Self {
+        match sentinel {
+            NullSentinel::Blank => Self::Blank,
+            NullSentinel::EpochDate => Self::EpochDate,
+            NullSentinel::Integer(value) => Self::Integer(value),
+            NullSentinel::Real(value) => Self::Real(value),
+            NullSentinel::String(value) => Self::String(value.to_owned()),
+        }
+    }
+}
+
+/// A [`Progress`] callback that can be stored and sent between threads.
+#[derive(Clone)]
+pub struct ProgressOwned(pub Arc<dyn Fn(u64, Option<u64>) + Send + Sync>);
+
+impl ProgressOwned {
+    fn to_borrowed(&self) -> Progress<'_> {
+        Progress(&*self.0)
+    }
+}
+
+impl std::fmt::Debug for ProgressOwned {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressOwned(..)")
+    }
+}
+
+/// (De)serialize open flags as their bits.
+#[cfg(feature = "serde")]
+mod open_flags {
+    use gdal::GdalOpenFlags;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        flags: &GdalOpenFlags,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(flags.bits())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<GdalOpenFlags, D::Error> {
+        let bits = u32::deserialize(deserializer)?;
+        GdalOpenFlags::from_bits(bits)
+            .ok_or_else(|| D::Error::custom(format!("invalid open flags: {:#x}", bits)))
+    }
+}
+
+fn str_vec(strings: &Option<Vec<String>>) -> Option<Vec<&str>> {
+    strings
+        .as_ref()
+        .map(|strings| strings.iter().map(String::as_str).collect())
+}
+
+fn str_pairs(pairs: &Option<Vec<(String, String)>>) -> Option<Vec<(&str, &str)>> {
+    pairs.as_ref().map(|pairs| {
+        pairs
+            .iter()
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+            .collect()
+    })
+}
+
+fn sentinels(
+    sentinels: &Option<Vec<(String, NullSentinelOwned)>>,
+) -> Option<Vec<(&str, NullSentinel<'_>)>> {
+    sentinels.as_ref().map(|sentinels| {
+        sentinels
+            .iter()
+            .map(|(name, sentinel)| (name.as_str(), sentinel.to_borrowed()))
+            .collect()
+    })
+}
+
+fn string_vec(strings: Option<&[&str]>) -> Option<Vec<String>> {
+    strings.map(|strings| strings.iter().map(|s| (*s).to_owned()).collect())
+}
+
+fn string_pairs(pairs: Option<&[(&str, &str)]>) -> Option<Vec<(String, String)>> {
+    pairs.map(|pairs| {
+        pairs
+            .iter()
+            .map(|(a, b)| ((*a).to_owned(), (*b).to_owned()))
+            .collect()
+    })
+}
+
+fn owned_sentinels(
+    sentinels: Option<&[(&str, NullSentinel)]>,
+) -> Option<Vec<(String, NullSentinelOwned)>> {
+    sentinels.map(|sentinels| {
+        sentinels
+            .iter()
+            .map(|(name, sentinel)| ((*name).to_owned(), (*sentinel).into()))
+            .collect()
+    })
+}
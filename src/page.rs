@@ -0,0 +1,131 @@
+use crate::driver::open_dataset_for_read;
+use crate::{layer_from_params, read_layer, resolve_read_path, Error, ReadParams};
+use gdal::vector::sql::Dialect;
+use gdal::vector::{Geometry, LayerAccess};
+use polars::prelude::*;
+use std::path::Path;
+
+/// A page of features to read with [`page_from_resource`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PageRequest<'a> {
+    /// An attribute query in restricted SQL WHERE format (eg `"population > 10000"`).
+    pub filter: Option<&'a str>,
+
+    /// Only return features intersecting this bounding box, as `[xmin, ymin, xmax, ymax]` in the layer's SRS.
+    pub bbox: Option<[f64; 4]>,
+
+    /// Sort features by these columns, in order of precedence. If empty, features are returned in the layer's natural order.
+    pub sort: &'a [SortKey<'a>],
+
+    /// The zero-based page number.
+    pub page: usize,
+
+    /// The number of features in each page.
+    pub page_size: usize,
+}
+
+/// A column to sort by in a [`PageRequest`].
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey<'a> {
+    /// The column to sort by.
+    pub column: &'a str,
+
+    /// Sort from largest to smallest.
+    pub descending: bool,
+}
+
+/// Read one page of the features of a resource matching a filter, along with the total number of matching features.
+///
+/// The filter, bounding box, sort and page are combined into a single SQL query, so drivers with native SQL support
+/// (eg GeoPackage or PostgreSQL) sort and skip features in the database rather than in this crate.
+/// This is the shape needed to back a paginated OGC API Features-style endpoint.
+///
+/// `ReadParams::attribute_filter`, `ReadParams::spatial_filter`, `ReadParams::offset` and the feature limits are replaced by the page request.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{page_from_resource, PageRequest, SortKey};
+///
+/// let request = PageRequest {
+///     filter: Some("population > 10000"),
+///     bbox: Some([-10.0, 35.0, 30.0, 60.0]),
+///     sort: &[SortKey { column: "population", descending: true }],
+///     page: 2,
+///     page_size: 50,
+/// };
+/// let (df, total_count) = page_from_resource("cities.gpkg", None, request)?;
+/// ```
+pub fn page_from_resource<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+    page_request: PageRequest,
+) -> Result<(DataFrame, u64), Error> {
+    let params = params.unwrap_or_default();
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset_for_read(&path, &params)?;
+
+    let bbox = page_request
+        .bbox
+        .map(|[xmin, ymin, xmax, ymax]| {
+            Geometry::from_wkt(&format!(
+                "POLYGON (({0} {1}, {2} {1}, {2} {3}, {0} {3}, {0} {1}))",
+                xmin, ymin, xmax, ymax
+            ))
+        })
+        .transpose()?;
+
+    // Count every matching feature, using the layer's own filters so that drivers can count without reading features
+    let mut layer = layer_from_params(&dataset, &params)?;
+    let layer_name = layer.name();
+    if let Some(filter) = page_request.filter {
+        layer.set_attribute_filter(filter)?;
+    }
+    if let Some(bbox) = &bbox {
+        layer.set_spatial_filter(bbox);
+    }
+    let total_count = layer.feature_count();
+    layer.clear_attribute_filter();
+    layer.clear_spatial_filter();
+
+    let mut query = format!("SELECT * FROM {}", quote_identifier(&layer_name));
+    if let Some(filter) = page_request.filter {
+        query.push_str(&format!(" WHERE {}", filter));
+    }
+    if !page_request.sort.is_empty() {
+        let sort: Vec<String> = page_request
+            .sort
+            .iter()
+            .map(|key| {
+                let direction = if key.descending { "DESC" } else { "ASC" };
+                format!("{} {}", quote_identifier(key.column), direction)
+            })
+            .collect();
+        query.push_str(&format!(" ORDER BY {}", sort.join(", ")));
+    }
+    query.push_str(&format!(
+        " LIMIT {} OFFSET {}",
+        page_request.page_size,
+        page_request.page * page_request.page_size
+    ));
+
+    let mut result_set = dataset
+        .execute_sql(&query, bbox.as_ref(), Dialect::DEFAULT)?
+        .ok_or_else(|| Error::NoResultSet(query.clone()))?;
+
+    let params = ReadParams {
+        attribute_filter: None,
+        spatial_filter: None,
+        offset: None,
+        truncating_limit: None,
+        erroring_limit: None,
+        ..params
+    };
+    let df = read_layer(&mut result_set, Some(&dataset), params, None)?;
+
+    Ok((df, total_count))
+}
+
+/// Quote an identifier for use in a SQL query.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
@@ -0,0 +1,160 @@
+use crate::{
+    column_geometry_type, field_types, polars_type_id_to_gdal_subtype, sanitize_field_names,
+    time_field_type, Error, FieldRename, GeometryTypePolicy, SkippedColumn, ToGdalDriver,
+    WriteParams,
+};
+use gdal::vector::{OGRFieldType, OGRwkbGeometryType};
+use polars::prelude::*;
+
+/// What a write would create, as computed by [`plan_write`] without creating a dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WritePlan {
+    /// The short name of the driver written to.
+    pub driver: String,
+
+    /// The geometry type of the layer.
+    pub geometry_type: OGRwkbGeometryType::Type,
+
+    /// The extra geometry fields of the layer as `(field name, geometry type)`, for `WriteParams::extra_geometry_columns`.
+    pub extra_geometry_fields: Vec<(String, OGRwkbGeometryType::Type)>,
+
+    /// The attribute fields of the layer, in column order.
+    pub fields: Vec<PlannedField>,
+
+    /// The fields the driver is expected to create under another name than their column, as predicted by [`sanitize_field_names`].
+    pub field_renames: Vec<FieldRename>,
+
+    /// The columns that wouldn't be written, in column order.
+    pub skipped_columns: Vec<SkippedColumn>,
+}
+
+/// An attribute field a write would create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedField {
+    /// The column the field is written from.
+    pub column: String,
+
+    /// The name of the field, after any rename predicted for the driver.
+    pub name: String,
+
+    /// The OGR type of the field.
+    pub field_type: OGRFieldType::Type,
+
+    /// The OGR subtype of the field, eg `OFSTBoolean` for Boolean columns.
+    pub field_subtype: gdal_sys::OGRFieldSubType::Type,
+}
+
+/// Compute the layer a write of a DataFrame would create, without creating a dataset or converting any values.
+///
+/// Use this to validate an export before running it. The plan fails with the errors the write would fail with up front,
+/// eg a missing geometry column, malformed geometries, or a column rejected by `WriteParams::strict`.
+/// Errors found while writing rows (eg a value out of range for its field) are not detected.
+///
+/// # Example
+/// ```rust # ignore
+/// let plan = polars_gdal::plan_write(&df, &VectorDriver::Shapefile, None)?;
+/// for rename in &plan.field_renames {
+///     println!("{} would be written as {}", rename.original, rename.renamed);
+/// }
+/// ```
+pub fn plan_write<D: ToGdalDriver + ?Sized>(
+    df: &DataFrame,
+    driver: &D,
+    params: Option<WriteParams>,
+) -> Result<WritePlan, Error> {
+    let params = params.unwrap_or_default();
+    let driver = driver.to_gdal_driver()?;
+    let driver_name = driver.short_name();
+
+    let geometry_column_name = params.geometry_column_name.unwrap_or("geometry");
+    let extra_geometry_columns = params.extra_geometry_columns.unwrap_or_default();
+    let geometry_columns: Vec<&str> = std::iter::once(geometry_column_name)
+        .chain(extra_geometry_columns.iter().map(|(column, _)| *column))
+        .collect();
+    for name in &geometry_columns {
+        df.column(name)
+            .map_err(|_| Error::CannotFindGeometryColumn((*name).to_owned()))?;
+    }
+    if df.height() == 0 {
+        return Err(Error::EmptyDataframe);
+    }
+
+    // The dtypes columns are written from: Struct columns are serialized to JSON and Categorical columns cast to strings
+    let columns = df.get_columns().iter().map(|c| {
+        let dtype = match c.dtype() {
+            DataType::Struct(_) if params.struct_as_json => DataType::Utf8,
+            DataType::Categorical(_) => DataType::Utf8,
+            dtype => dtype.clone(),
+        };
+        (c.name(), dtype)
+    });
+    let time_field_type = time_field_type(&driver, &params);
+    let (props, skipped_columns) =
+        field_types(columns, &geometry_columns, &params, time_field_type)?;
+
+    let (_, renames) = sanitize_field_names(df, &driver_name, geometry_column_name)?;
+    let field_renames: Vec<FieldRename> = renames
+        .into_iter()
+        .filter(|rename| props.iter().any(|(_, n, _)| *n == rename.original))
+        .collect();
+    if params.strict {
+        if let Some(rename) = field_renames.first() {
+            return Err(Error::StrictWrite(format!(
+                "field `{}` would be renamed to `{}` by the driver",
+                rename.original, rename.renamed
+            )));
+        }
+    }
+
+    let fields = props
+        .iter()
+        .map(|(i, n, t)| {
+            let column = &df.get_columns()[*i];
+            let field_subtype = match column.dtype() {
+                DataType::Struct(_) if params.struct_as_json => gdal_sys::OGRFieldSubType::OFSTJSON,
+                dtype => polars_type_id_to_gdal_subtype(dtype),
+            };
+            let name = match field_renames.iter().find(|r| r.original == *n) {
+                Some(rename) => rename.renamed.clone(),
+                None => (*n).to_owned(),
+            };
+            PlannedField {
+                column: (*n).to_owned(),
+                name,
+                field_type: *t,
+                field_subtype,
+            }
+        })
+        .collect();
+
+    let geometry_type = match params.geometry_type {
+        Some(geometry_type) => geometry_type,
+        None => column_geometry_type(
+            df.column(geometry_column_name)?,
+            params.geometry_format,
+            &params,
+            params.geometry_type_policy,
+        )?,
+    };
+    let extra_geometry_fields = extra_geometry_columns
+        .iter()
+        .map(|(column, field_name)| -> Result<_, Error> {
+            let geometry_type = column_geometry_type(
+                df.column(column)?,
+                params.geometry_format,
+                &params,
+                GeometryTypePolicy::ConvertToGeneric,
+            )?;
+            Ok(((*field_name).to_owned(), geometry_type))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(WritePlan {
+        driver: driver_name,
+        geometry_type,
+        extra_geometry_fields,
+        fields,
+        field_renames,
+        skipped_columns,
+    })
+}
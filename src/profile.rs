@@ -0,0 +1,120 @@
+use crate::{
+    gdal_bytes_from_df, gdal_resource_from_df, sanitize_field_names, Error, FieldRename,
+    GeometryFormat, ToGdalDriver, VectorDriver, WriteParams,
+};
+use gdal::Dataset;
+use polars::prelude::*;
+use std::path::Path;
+
+/// Write presets bundling a driver with creation options, geometry format, precision and field naming known to work for a common use.
+///
+/// Use a profile instead of choosing driver options by hand. The [`WriteParams`] of a profile can still be adjusted before writing,
+/// eg to set the SRS.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{gdal_bytes_from_df_with_profile, WriteProfile};
+///
+/// let (geojson_bytes, renames) = gdal_bytes_from_df_with_profile(&df, WriteProfile::GeoJsonWeb, None)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WriteProfile {
+    /// GeoPackage with a spatial index, for desktop GIS and local analysis.
+    GpkgAnalytics,
+
+    /// RFC 7946 GeoJSON for web maps, with coordinates rounded to 7 decimal places (about 1cm) and no bounding boxes.
+    ///
+    /// RFC 7946 requires WGS84 coordinates, so GDAL reprojects geometries into WGS84 if the layer SRS is set with `WriteParams::srs`.
+    GeoJsonWeb,
+
+    /// A Shapefile readable by older tools, with a UTF-8 `.cpg` encoding file and field names truncated to 10 characters.
+    ShapefileLegacy,
+
+    /// GeoParquet for cloud object storage, with ZSTD compression, WKB geometries and row groups sized for ranged reads.
+    ///
+    /// Requires GDAL to be built against Apache Parquet.
+    ParquetCloud,
+}
+
+impl WriteProfile {
+    /// The driver written to.
+    pub const fn driver(&self) -> VectorDriver {
+        match self {
+            Self::GpkgAnalytics => VectorDriver::Gpkg,
+            Self::GeoJsonWeb => VectorDriver::GeoJson,
+            Self::ShapefileLegacy => VectorDriver::Shapefile,
+            Self::ParquetCloud => VectorDriver::Parquet,
+        }
+    }
+
+    /// The layer creation options passed to the driver, in the form `name=value`.
+    pub const fn options(&self) -> &'static [&'static str] {
+        match self {
+            Self::GpkgAnalytics => &["SPATIAL_INDEX=YES"],
+            Self::GeoJsonWeb => &["RFC7946=YES", "COORDINATE_PRECISION=7", "WRITE_BBOX=NO"],
+            Self::ShapefileLegacy => &["ENCODING=UTF-8", "SPATIAL_INDEX=NO"],
+            Self::ParquetCloud => &[
+                "COMPRESSION=ZSTD",
+                "GEOMETRY_ENCODING=WKB",
+                "ROW_GROUP_SIZE=65536",
+            ],
+        }
+    }
+
+    /// The write parameters of the profile. Every profile writes WKB geometries from the `geometry` column.
+    pub fn write_params(&self) -> WriteParams<'static> {
+        WriteParams {
+            geometry_format: GeometryFormat::WKB,
+            options: Some(self.options()),
+            ..Default::default()
+        }
+    }
+
+    /// Rename the columns of a DataFrame so they can be written with this profile, as with [`sanitize_field_names`].
+    pub fn prepare(
+        &self,
+        df: &DataFrame,
+        geometry_column_name: &str,
+    ) -> Result<(DataFrame, Vec<FieldRename>), Error> {
+        sanitize_field_names(df, self.driver().name(), geometry_column_name)
+    }
+}
+
+impl ToGdalDriver for WriteProfile {
+    fn to_gdal_driver(&self) -> Result<gdal::Driver, Error> {
+        self.driver().to_gdal_driver()
+    }
+}
+
+/// Write a DataFrame to bytes with a [`WriteProfile`], returning the bytes and the columns renamed to fit the format.
+///
+/// If `params` is None, the profile's [`WriteProfile::write_params`] are used.
+pub fn gdal_bytes_from_df_with_profile(
+    df: &DataFrame,
+    profile: WriteProfile,
+    params: Option<WriteParams>,
+) -> Result<(Vec<u8>, Vec<FieldRename>), Error> {
+    let params = params.unwrap_or_else(|| profile.write_params());
+    let (df, renames) = profile.prepare(df, params.geometry_column_name.unwrap_or("geometry"))?;
+
+    Ok((gdal_bytes_from_df(&df, &profile, Some(params))?, renames))
+}
+
+/// Write a DataFrame to a GDAL resource path with a [`WriteProfile`], returning the dataset and the columns renamed to fit the format.
+///
+/// If `params` is None, the profile's [`WriteProfile::write_params`] are used.
+pub fn gdal_resource_from_df_with_profile<P: AsRef<Path>>(
+    df: &DataFrame,
+    profile: WriteProfile,
+    path: P,
+    params: Option<WriteParams>,
+) -> Result<(Dataset, Vec<FieldRename>), Error> {
+    let params = params.unwrap_or_else(|| profile.write_params());
+    let (df, renames) = profile.prepare(df, params.geometry_column_name.unwrap_or("geometry"))?;
+
+    Ok((
+        gdal_resource_from_df(&df, &profile, path, Some(params))?,
+        renames,
+    ))
+}
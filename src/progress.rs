@@ -0,0 +1,44 @@
+/// The number of features between two calls of a [`Progress`] callback.
+const PROGRESS_INTERVAL: u64 = 1024;
+
+/// A callback reporting the progress of a read or write, as `(features processed, total features if known)`.
+///
+/// The callback is called every 1024 features, and once more when the last feature has been processed.
+/// It must be `Sync` since [`df_from_resource_concurrent`](crate::df_from_resource_concurrent) calls it from its reader threads.
+///
+/// Only vector reads and writes report progress. This crate has no raster operations (warp, translate, rasterize or grid), so
+/// there is no `GDALProgressFunc` to bridge to this callback yet; raster operations should report through it once they exist.
+///
+/// # Example
+/// ```rust # ignore
+/// let report = |processed, total: Option<u64>| match total {
+///     Some(total) => eprintln!("{} / {} features", processed, total),
+///     None => eprintln!("{} features", processed),
+/// };
+/// let mut params = polars_gdal::ReadParams::default();
+/// params.progress = Some(polars_gdal::Progress(&report));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Progress<'a>(pub &'a (dyn Fn(u64, Option<u64>) + Sync));
+
+impl Progress<'_> {
+    /// Report progress if `processed` is a multiple of the interval.
+    pub(crate) fn update(&self, processed: u64, total: Option<u64>) {
+        if processed % PROGRESS_INTERVAL == 0 {
+            (self.0)(processed, total);
+        }
+    }
+
+    /// Report the final progress.
+    pub(crate) fn finish(&self, processed: u64, total: Option<u64>) {
+        if processed % PROGRESS_INTERVAL != 0 || processed == 0 {
+            (self.0)(processed, total);
+        }
+    }
+}
+
+impl std::fmt::Debug for Progress<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Progress(..)")
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    capture_messages, df_from_bytes, df_from_layer, df_from_resource, write_bytes, write_layer,
+    write_resource, Error, FieldRename, GdalMessage, ReadParams, ToGdalDriver, WriteParams,
+};
+use gdal::vector::OGRwkbGeometryType;
+use gdal::Dataset;
+use polars::prelude::*;
+use std::path::Path;
+
+/// What was actually written by a write, to detect silent data loss.
+///
+/// Returned by [`gdal_layer_from_df_with_report`], [`gdal_bytes_from_df_with_report`] and [`gdal_resource_from_df_with_report`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteReport {
+    /// The number of rows written as features.
+    pub rows_written: usize,
+
+    /// The columns that weren't written, in column order.
+    pub skipped_columns: Vec<SkippedColumn>,
+
+    /// The fields the driver created under another name than their column, eg Shapefile field names truncated to 10 characters.
+    pub field_renames: Vec<FieldRename>,
+
+    /// The number of nulls written as their sentinel value, per column listed in `WriteParams::null_sentinels`.
+    pub null_sentinel_substitutions: Vec<(String, usize)>,
+
+    /// Rows that were skipped, or written with a value missing, in row order.
+    pub warnings: Vec<WriteWarning>,
+}
+
+impl WriteReport {
+    /// Whether every row and every value of every column was written, under its own name.
+    pub fn is_lossless(&self) -> bool {
+        self.skipped_columns.is_empty() && self.field_renames.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// A column that wasn't written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedColumn {
+    /// The column name.
+    pub name: String,
+
+    /// Why the column wasn't written.
+    pub reason: SkipReason,
+}
+
+/// Why a column wasn't written.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// GDAL has no field type for the dtype of the column (eg nested lists).
+    UnsupportedDtype(DataType),
+}
+
+/// A row that was skipped, or written with a value missing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WriteWarning {
+    /// The row was skipped since its geometry is null, with [`NullGeometryPolicy::Skip`](crate::NullGeometryPolicy::Skip).
+    NullGeometrySkipped { row: usize },
+
+    /// The row was skipped since its geometry doesn't fit the layer type, with [`GeometryTypePolicy::SkipMismatched`](crate::GeometryTypePolicy::SkipMismatched).
+    GeometryTypeSkipped {
+        row: usize,
+        geometry_type: OGRwkbGeometryType::Type,
+    },
+
+    /// A value couldn't be converted to a GDAL field value (eg a date out of range, or a list with null elements), so the field was left null.
+    ValueNotWritten { row: usize, column: String },
+}
+
+/// Given a dataframe, create a GDAL layer and report what was written.
+///
+/// See [`gdal_layer_from_df`](crate::gdal_layer_from_df) and [`WriteReport`].
+///
+/// # Example
+/// ```rust # ignore
+/// let (layer, report) = polars_gdal::gdal_layer_from_df_with_report(&df, &mut dataset, None)?;
+/// for column in &report.skipped_columns {
+///     println!("{} was not written: {:?}", column.name, column.reason);
+/// }
+/// ```
+pub fn gdal_layer_from_df_with_report<'a>(
+    df: &DataFrame,
+    dataset: &'a mut Dataset,
+    params: Option<WriteParams>,
+) -> Result<(gdal::vector::Layer<'a>, WriteReport), Error> {
+    write_layer(df, dataset, params.unwrap_or_default())
+}
+
+/// Given a dataframe, get bytes in a GDAL geospatial format and report what was written.
+///
+/// See [`gdal_bytes_from_df`](crate::gdal_bytes_from_df) and [`WriteReport`].
+pub fn gdal_bytes_from_df_with_report<D: ToGdalDriver + ?Sized>(
+    df: &DataFrame,
+    driver: &D,
+    params: Option<WriteParams>,
+) -> Result<(Vec<u8>, WriteReport), Error> {
+    write_bytes(df, &driver.to_gdal_driver()?, params)
+}
+
+/// Given a dataframe, write to a GDAL resource path, returning the dataset and a report of what was written.
+///
+/// See [`gdal_resource_from_df`](crate::gdal_resource_from_df) and [`WriteReport`].
+pub fn gdal_resource_from_df_with_report<D: ToGdalDriver + ?Sized, P: AsRef<Path>>(
+    df: &DataFrame,
+    driver: &D,
+    path: P,
+    params: Option<WriteParams>,
+) -> Result<(Dataset, WriteReport), Error> {
+    write_resource(df, &driver.to_gdal_driver()?, path.as_ref(), params)
+}
+
+/// The GDAL messages emitted while reading a DataFrame, which GDAL would otherwise only print to stderr.
+///
+/// Returned by [`df_from_resource_with_report`], [`df_from_bytes_with_report`] and [`df_from_layer_with_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadReport {
+    /// The warnings and recovered failures GDAL emitted while opening and reading the resource, in the order they were emitted.
+    pub warnings: Vec<GdalMessage>,
+}
+
+/// Given a filepath or a URI, read the resource into a dataframe and report the warnings GDAL emitted.
+///
+/// See [`df_from_resource`](crate::df_from_resource) and [`ReadReport`].
+///
+/// # Example
+/// ```rust # ignore
+/// let (df, report) = polars_gdal::df_from_resource_with_report("my_shapefile.shp", None)?;
+/// for warning in &report.warnings {
+///     eprintln!("GDAL: {}", warning.message);
+/// }
+/// ```
+pub fn df_from_resource_with_report<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<(DataFrame, ReadReport), Error> {
+    let (df, warnings) = capture_messages(|| df_from_resource(path, params));
+    Ok((df?, ReadReport { warnings }))
+}
+
+/// Given some raw bytes, create a dataframe and report the warnings GDAL emitted.
+///
+/// See [`df_from_bytes`](crate::df_from_bytes) and [`ReadReport`].
+pub fn df_from_bytes_with_report(
+    data: &[u8],
+    filename_hint: Option<&str>,
+    params: Option<ReadParams>,
+) -> Result<(DataFrame, ReadReport), Error> {
+    let (df, warnings) = capture_messages(|| df_from_bytes(data, filename_hint, params));
+    Ok((df?, ReadReport { warnings }))
+}
+
+/// Given a GDAL layer, create a dataframe and report the warnings GDAL emitted.
+///
+/// See [`df_from_layer`](crate::df_from_layer) and [`ReadReport`].
+pub fn df_from_layer_with_report(
+    layer: &mut gdal::vector::Layer,
+    params: Option<ReadParams>,
+) -> Result<(DataFrame, ReadReport), Error> {
+    let (df, warnings) = capture_messages(|| df_from_layer(layer, params));
+    Ok((df?, ReadReport { warnings }))
+}
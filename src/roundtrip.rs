@@ -0,0 +1,179 @@
+use crate::driver::open_dataset_for_read;
+use crate::{
+    gdal_layer_from_df, layer_from_params, read_layer, sanitize_field_names, Error,
+    ReadParams, ToGdalDriver, WriteParams,
+};
+use polars::prelude::*;
+use std::ffi::CString;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The differences between a DataFrame and the same DataFrame after being written to a format and read back, as reported by [`roundtrip_check`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffReport {
+    /// The number of rows in the original DataFrame.
+    pub original_row_count: usize,
+
+    /// The number of rows read back.
+    pub roundtrip_row_count: usize,
+
+    /// Every column that didn't survive the round-trip unchanged, in the order of the original DataFrame, followed by any added columns.
+    pub differences: Vec<ColumnDifference>,
+}
+
+impl DiffReport {
+    /// Whether the DataFrame survived the round-trip without any differences.
+    pub fn is_lossless(&self) -> bool {
+        self.original_row_count == self.roundtrip_row_count && self.differences.is_empty()
+    }
+}
+
+/// A difference in one column between a DataFrame and the same DataFrame after a round-trip.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnDifference {
+    /// The column wasn't written, eg because its dtype isn't supported by GDAL.
+    Missing { name: String },
+
+    /// A column that wasn't in the original DataFrame was read back, eg a column generated by the driver.
+    Added { name: String },
+
+    /// The column was renamed by the driver, eg Shapefile field names are truncated to 10 characters.
+    Renamed { original: String, roundtrip: String },
+
+    /// The column was read back with a different dtype.
+    TypeChanged {
+        name: String,
+        original: DataType,
+        roundtrip: DataType,
+    },
+
+    /// Some values were read back differently, eg because of precision loss. Values are compared after casting back to the original dtype.
+    ValuesChanged {
+        name: String,
+        count: usize,
+        first_row: usize,
+    },
+}
+
+/// Write a DataFrame to a format in memory, read it back, and report every difference between the two.
+///
+/// Use this to check that a format is lossless for your data before committing to it. Values are only compared when the
+/// row count is unchanged. Renames are detected using the same rules as [`sanitize_field_names`].
+///
+/// # Example
+/// ```rust # ignore
+/// let report = polars_gdal::roundtrip_check(&df, &VectorDriver::Shapefile, None)?;
+/// if !report.is_lossless() {
+///     for difference in report.differences {
+///         println!("{:?}", difference);
+///     }
+/// }
+/// ```
+pub fn roundtrip_check<D: ToGdalDriver + ?Sized>(
+    df: &DataFrame,
+    driver: &D,
+    params: Option<WriteParams>,
+) -> Result<DiffReport, Error> {
+    let driver = driver.to_gdal_driver()?;
+    let params = params.unwrap_or_default();
+    let geometry_column_name = params.geometry_column_name.unwrap_or("geometry");
+    let read_params = ReadParams {
+        geometry_column_name: Some(geometry_column_name),
+        geometry_format: params.geometry_format,
+        ..Default::default()
+    };
+
+    // Generate a safe path to the data that is exclusive to this process-id. Multi-file formats write into the directory.
+    static ROUNDTRIP_MEM_DIR_INCREMENTOR: AtomicU64 = AtomicU64::new(0);
+    let mem_dir = format!(
+        "/vsimem/polars_gdal/roundtrip_check/{}/{}",
+        std::process::id(),
+        ROUNDTRIP_MEM_DIR_INCREMENTOR.fetch_add(1, Ordering::SeqCst),
+    );
+    let mem_path = format!("{}/layer", mem_dir);
+
+    let roundtrip = (|| -> Result<DataFrame, Error> {
+        let mut dataset = driver.create_vector_only(&mem_path)?;
+        gdal_layer_from_df(df, &mut dataset, Some(params))?;
+        dataset.flush_cache();
+        drop(dataset);
+
+        let dataset = open_dataset_for_read(&mem_path, &read_params)?;
+        let mut layer = layer_from_params(&dataset, &read_params)?;
+        read_layer(&mut layer, Some(&dataset), read_params, None)
+    })();
+
+    // Release the in-memory files, even if the round-trip failed
+    let c_mem_dir = CString::new(mem_dir).unwrap();
+    unsafe { gdal_sys::VSIRmdirRecursive(c_mem_dir.as_ptr()) };
+
+    let roundtrip = roundtrip?;
+    let (_, renames) = sanitize_field_names(df, &driver.short_name(), geometry_column_name)?;
+
+    let mut differences = vec![];
+    let mut matched = vec![];
+    for original in df.get_columns() {
+        let name = original.name();
+        let roundtrip_name = match roundtrip.column(name) {
+            Ok(_) => name.to_owned(),
+            Err(_) => match renames.iter().find(|r| r.original == name) {
+                Some(rename) if roundtrip.column(&rename.renamed).is_ok() => {
+                    differences.push(ColumnDifference::Renamed {
+                        original: name.to_owned(),
+                        roundtrip: rename.renamed.clone(),
+                    });
+                    rename.renamed.clone()
+                }
+                _ => {
+                    differences.push(ColumnDifference::Missing {
+                        name: name.to_owned(),
+                    });
+                    continue;
+                }
+            },
+        };
+        let read_back = roundtrip.column(&roundtrip_name)?;
+        matched.push(roundtrip_name);
+
+        let read_back = if read_back.dtype() != original.dtype() {
+            differences.push(ColumnDifference::TypeChanged {
+                name: name.to_owned(),
+                original: original.dtype().clone(),
+                roundtrip: read_back.dtype().clone(),
+            });
+            match read_back.cast(original.dtype()) {
+                Ok(read_back) => read_back,
+                Err(_) => continue,
+            }
+        } else {
+            read_back.clone()
+        };
+
+        if df.height() != roundtrip.height() || read_back.series_equal_missing(original) {
+            continue;
+        }
+        let changed: Vec<usize> = (0..df.height())
+            .filter(|&i| original.get(i) != read_back.get(i))
+            .collect();
+        if let Some(&first_row) = changed.first() {
+            differences.push(ColumnDifference::ValuesChanged {
+                name: name.to_owned(),
+                count: changed.len(),
+                first_row,
+            });
+        }
+    }
+
+    for name in roundtrip.get_column_names() {
+        if !matched.iter().any(|m| m == name) {
+            differences.push(ColumnDifference::Added {
+                name: name.to_owned(),
+            });
+        }
+    }
+
+    Ok(DiffReport {
+        original_row_count: df.height(),
+        roundtrip_row_count: roundtrip.height(),
+        differences,
+    })
+}
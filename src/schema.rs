@@ -0,0 +1,161 @@
+use crate::driver::open_dataset_for_read;
+use crate::{describe, field_domain};
+use crate::{
+    gdal_subtype_to_polars_type_id, gdal_type_id_to_polars_type_id, geometry_column_names, layer,
+    layer_from_params, resolve_read_path, Error, ReadParams,
+};
+use gdal::vector::{LayerAccess, OGRwkbGeometryType};
+use gdal::Dataset;
+use polars::prelude::*;
+use std::path::Path;
+
+/// Get the schema of the DataFrame that [`df_from_resource`](crate::df_from_resource) would return, without reading any features.
+///
+/// The schema is built from the layer's field definitions, so it's cheap even for very large layers.
+/// Use it to plan pipelines or to validate a source before reading it.
+///
+/// # Example
+/// ```rust # ignore
+/// let schema = polars_gdal::schema_from_resource("my_geopackage.gpkg", None)?;
+/// assert_eq!(schema.get("geometry"), Some(&DataType::Binary));
+/// ```
+pub fn schema_from_resource<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<Schema, Error> {
+    let params = params.unwrap_or_default();
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset_for_read(&path, &params)?;
+
+    let layer = layer_from_params(&dataset, &params)?;
+
+    Ok(schema_of_layer(&layer, Some(&dataset), params))
+}
+
+/// The geometry type of a layer, as returned by [`geometry_type_from_resource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeometryTypeInfo {
+    /// The geometry type declared by the layer.
+    pub declared: OGRwkbGeometryType::Type,
+
+    /// Whether the declared geometry type can be relied on. This is false if the layer declares `wkbUnknown`, or if a scan found features of another type.
+    pub honored: bool,
+
+    /// The geometry type found by scanning every feature, if a scan was requested and the layer has non-empty geometries.
+    ///
+    /// Mixed single and multi geometries of the same kind (eg Polygon and MultiPolygon) give the multi type, and other mixes give `wkbUnknown`.
+    pub scanned: Option<OGRwkbGeometryType::Type>,
+}
+
+/// Get the geometry type of a layer of a resource, to go along with [`schema_from_resource`].
+///
+/// Many drivers (eg GeoJSON or CSV) declare `wkbUnknown`, and some declare a type that features don't match (eg Shapefile polygon layers holding MultiPolygons).
+/// With `scan` set every feature is read to find the true geometry type, which is what `WriteParams::geometry_type` should be set to when re-exporting.
+///
+/// # Example
+/// ```rust # ignore
+/// let geometry_type = polars_gdal::geometry_type_from_resource("my_geojson.json", None, true)?;
+/// let mut params = polars_gdal::WriteParams::default();
+/// params.geometry_type = geometry_type.scanned;
+/// ```
+pub fn geometry_type_from_resource<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+    scan: bool,
+) -> Result<GeometryTypeInfo, Error> {
+    let params = params.unwrap_or_default();
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset_for_read(&path, &params)?;
+    let mut layer = layer_from_params(&dataset, &params)?;
+
+    let declared = unsafe { gdal_sys::OGR_L_GetGeomType(layer.c_layer()) };
+    let scanned = if scan {
+        describe::scan_geometry_type(&mut layer)
+    } else {
+        None
+    };
+
+    Ok(GeometryTypeInfo {
+        declared,
+        honored: describe::geometry_type_honored(declared, scanned),
+        scanned,
+    })
+}
+
+/// Get the schema of the DataFrame that [`df_from_layer`](crate::df_from_layer) would return, without reading any features.
+///
+/// Fields with types that can't be read into a DataFrame are left out.
+pub fn schema_from_layer<L: LayerAccess>(layer: &L, params: Option<ReadParams>) -> Schema {
+    schema_of_layer(layer, None, params.unwrap_or_default())
+}
+
+/// The schema of a layer. Fields with a coded-value domain in `dataset` are Categorical.
+fn schema_of_layer<L: LayerAccess>(layer: &L, dataset: Option<&Dataset>, params: ReadParams) -> Schema {
+    let geometry_column_names = geometry_column_names(layer, &params);
+    let geometry_column_name = geometry_column_names[0].as_str();
+
+    let mut fields = vec![];
+    if let Some(fid_column_name) = params.fid_column_name {
+        fields.push(Field::new(fid_column_name, DataType::UInt64));
+    }
+    if let Some(style_column_name) = params.style_column_name {
+        fields.push(Field::new(style_column_name, DataType::Utf8));
+    }
+
+    // Fields with the same name as a generated column are renamed, the same as when reading
+    let reserved = [
+        (!params.skip_geometry).then_some(geometry_column_name),
+        params.fid_column_name,
+        params.style_column_name,
+    ];
+    for (field_idx, field) in layer.defn().fields().enumerate() {
+        let name = field.name();
+        if !params.field_selected(&name) {
+            continue;
+        }
+        let dtype = match gdal_type_id_to_polars_type_id(field.field_type()) {
+            Some(dtype) => dtype,
+            None => continue,
+        };
+        let subtype = layer::field_subtype(layer, field_idx as i32);
+        let dtype = gdal_subtype_to_polars_type_id(field.field_type(), subtype).unwrap_or(dtype);
+        let dtype = match dtype {
+            DataType::Datetime(unit, None) if params.utc_datetimes => {
+                DataType::Datetime(unit, Some("UTC".to_owned()))
+            }
+            dtype => dtype,
+        };
+        let has_coded_values = dataset.map_or(false, |dataset| {
+            field_domain::field_domain_name(layer, field_idx as i32)
+                .and_then(|domain_name| field_domain::coded_values(dataset, &domain_name))
+                .is_some()
+        });
+        let dtype = if has_coded_values {
+            DataType::Categorical(None)
+        } else {
+            dtype
+        };
+        let name = if reserved.contains(&Some(name.as_str())) {
+            format!("{}_original", name)
+        } else {
+            name
+        };
+        let dtype = params
+            .schema_overrides
+            .and_then(|overrides| overrides.get(name.as_str()))
+            .cloned()
+            .unwrap_or(dtype);
+        fields.push(Field::new(&name, dtype));
+    }
+
+    if !params.skip_geometry {
+        for geometry_column_name in &geometry_column_names {
+            fields.push(Field::new(
+                geometry_column_name,
+                params.geometry_format.dtype(),
+            ));
+        }
+    }
+
+    Schema::from_iter(fields)
+}
@@ -0,0 +1,72 @@
+/// Options for reading Excel (XLSX) and OpenDocument (ODS) spreadsheets.
+///
+/// Each sheet of a spreadsheet is read as a separate layer.
+///
+/// # Example
+/// ```
+/// use polars_gdal::{SpreadsheetHeaders, SpreadsheetOptions};
+///
+/// let mut params = polars_gdal::ReadParams::default();
+/// params.spreadsheet_options = Some(SpreadsheetOptions {
+///     sheet_name: Some("Stations"),
+///     headers: SpreadsheetHeaders::Force,
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpreadsheetOptions<'a> {
+    /// The sheet to read. If None, the first sheet is read.
+    ///
+    /// This has no effect if `ReadParams::layer_name` is set.
+    pub sheet_name: Option<&'a str>,
+
+    /// Whether the first row of the sheet holds the column names.
+    pub headers: SpreadsheetHeaders,
+
+    /// How column types are determined.
+    pub field_types: SpreadsheetFieldTypes,
+}
+
+/// Whether the first row of a spreadsheet holds the column names.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpreadsheetHeaders {
+    /// Treat the first row as column names if it only holds strings and the second row doesn't.
+    #[default]
+    Auto,
+
+    /// Always treat the first row as column names.
+    Force,
+
+    /// Never treat the first row as column names. Columns are named `Field1`, `Field2`, etc.
+    Disable,
+}
+
+/// How the column types of a spreadsheet are determined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpreadsheetFieldTypes {
+    /// Detect column types from the cell values.
+    #[default]
+    Auto,
+
+    /// Read every column as a string.
+    String,
+}
+
+impl<'a> SpreadsheetOptions<'a> {
+    /// The XLSX / ODS driver open options for these options.
+    pub(crate) fn open_options(&self) -> [&'static str; 2] {
+        let headers = match self.headers {
+            SpreadsheetHeaders::Auto => "HEADERS=AUTO",
+            SpreadsheetHeaders::Force => "HEADERS=FORCE",
+            SpreadsheetHeaders::Disable => "HEADERS=DISABLE",
+        };
+        let field_types = match self.field_types {
+            SpreadsheetFieldTypes::Auto => "FIELD_TYPES=AUTO",
+            SpreadsheetFieldTypes::String => "FIELD_TYPES=STRING",
+        };
+
+        [headers, field_types]
+    }
+}
@@ -0,0 +1,118 @@
+use crate::driver::open_dataset_for_read;
+use crate::{layer_from_params, read_layer, resolve_read_path, Error, ReadParams};
+use gdal::Dataset;
+use polars::prelude::*;
+use std::path::Path;
+
+/// Statistics about a DataFrame, collected while reading it.
+///
+/// Catalog and metadata systems usually need these for every ingested file. Collecting them alongside the read saves a second pass over the data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadStats {
+    /// The number of rows read.
+    pub row_count: usize,
+
+    /// The extent of all geometries read as `[xmin, ymin, xmax, ymax]`, or `None` if no geometries were read.
+    pub extent: Option<[f64; 4]>,
+
+    /// Statistics for every column except the geometry column, in column order.
+    pub columns: Vec<ColumnStats>,
+}
+
+/// Statistics about a single column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    /// The column name.
+    pub name: String,
+
+    /// The number of null values.
+    pub null_count: usize,
+
+    /// The smallest value, or `None` if the column is all-null or its dtype can't be ordered.
+    pub min: Option<AnyValue<'static>>,
+
+    /// The largest value, or `None` if the column is all-null or its dtype can't be ordered.
+    pub max: Option<AnyValue<'static>>,
+}
+
+/// Given some params and a path to a resource, read the layer into a DataFrame and collect statistics about it.
+///
+/// See [`df_from_resource`](crate::df_from_resource) and [`ReadStats`].
+///
+/// # Example
+/// ```rust # ignore
+/// let (df, stats) = polars_gdal::df_from_resource_with_stats("my_shapefile.shp", None)?;
+/// println!("{} rows within {:?}", stats.row_count, stats.extent);
+/// ```
+pub fn df_from_resource_with_stats<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+) -> Result<(DataFrame, ReadStats), Error> {
+    let params = params.unwrap_or_default();
+    let path = resolve_read_path(&path.as_ref().to_string_lossy(), &params)?;
+    let dataset = open_dataset_for_read(&path, &params)?;
+
+    let mut layer = layer_from_params(&dataset, &params)?;
+
+    read_layer_with_stats(&mut layer, Some(&dataset), params)
+}
+
+/// Given a GDAL layer, create a dataframe and collect statistics about it.
+///
+/// See [`df_from_layer`](crate::df_from_layer) and [`ReadStats`].
+pub fn df_from_layer_with_stats<'l>(
+    layer: &mut gdal::vector::Layer<'l>,
+    params: Option<ReadParams>,
+) -> Result<(DataFrame, ReadStats), Error> {
+    read_layer_with_stats(layer, None, params.unwrap_or_default())
+}
+
+fn read_layer_with_stats(
+    layer: &mut gdal::vector::Layer,
+    dataset: Option<&Dataset>,
+    params: ReadParams,
+) -> Result<(DataFrame, ReadStats), Error> {
+    let geometry_column_name = params.geometry_column_name.unwrap_or("geometry").to_owned();
+
+    let mut extent = None;
+    let df = read_layer(layer, dataset, params, Some(&mut extent))?;
+
+    let columns = df
+        .get_columns()
+        .iter()
+        .filter(|series| series.name() != geometry_column_name)
+        .map(column_stats)
+        .collect::<Result<_, Error>>()?;
+
+    let stats = ReadStats {
+        row_count: df.height(),
+        extent,
+        columns,
+    };
+
+    Ok((df, stats))
+}
+
+fn column_stats(series: &Series) -> Result<ColumnStats, Error> {
+    let orderable = series.dtype().is_numeric()
+        || matches!(
+            series.dtype(),
+            DataType::Utf8 | DataType::Date | DataType::Datetime(_, _) | DataType::Time
+        );
+
+    let (min, max) = if orderable && series.null_count() < series.len() {
+        (
+            Some(series.min_as_series().get(0).into_static()?),
+            Some(series.max_as_series().get(0).into_static()?),
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(ColumnStats {
+        name: series.name().to_owned(),
+        null_count: series.null_count(),
+        min,
+        max,
+    })
+}
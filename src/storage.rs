@@ -0,0 +1,112 @@
+use crate::geom::{spatial_sort, SpaceFillingCurve};
+use crate::{Error, GeometryFormat};
+use polars::prelude::*;
+
+/// Identifies the geometry column of a dataframe and how its geometries are encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct GeometryColumnSpec<'a> {
+    /// The geometry column name.
+    pub name: &'a str,
+
+    /// The geometry format of the column.
+    pub format: GeometryFormat,
+}
+
+impl<'a> Default for GeometryColumnSpec<'a> {
+    fn default() -> Self {
+        Self {
+            name: "geometry",
+            format: GeometryFormat::default(),
+        }
+    }
+}
+
+/// String columns with at most this ratio of unique values to rows are dictionary-encoded.
+const CATEGORICAL_MAX_UNIQUE_RATIO: f64 = 0.5;
+
+/// Post-process a dataframe so it compresses well when handed off to Parquet or IPC storage.
+///
+/// - Low-cardinality string columns are dictionary-encoded as Categorical columns.
+/// - Integer columns are downcast to the smallest integer type that holds all their values.
+/// - Rows are sorted along a Hilbert curve through the geometry centers, so spatially close features are stored together.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{df_from_resource, optimize_for_storage, GeometryColumnSpec};
+///
+/// let df = df_from_resource("my_shapefile.shp", None)?;
+/// let mut df = optimize_for_storage(&df, GeometryColumnSpec::default())?;
+/// ParquetWriter::new(file).finish(&mut df)?;
+/// ```
+pub fn optimize_for_storage(
+    df: &DataFrame,
+    geometry_column: GeometryColumnSpec,
+) -> Result<DataFrame, Error> {
+    let mut columns = Vec::with_capacity(df.width());
+    for column in df.get_columns() {
+        if column.name() == geometry_column.name {
+            columns.push(column.clone());
+            continue;
+        }
+        columns.push(match column.dtype() {
+            DataType::Utf8 => dictionary_encode(column)?,
+            DataType::Int16 | DataType::Int32 | DataType::Int64 => downcast_signed(column)?,
+            DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => downcast_unsigned(column)?,
+            _ => column.clone(),
+        });
+    }
+    let df = DataFrame::new(columns)?;
+
+    spatial_sort(&df, geometry_column, SpaceFillingCurve::Hilbert, None)
+}
+
+fn dictionary_encode(column: &Series) -> Result<Series, Error> {
+    if column.is_empty() {
+        return Ok(column.clone());
+    }
+
+    let unique_ratio = column.n_unique()? as f64 / column.len() as f64;
+    if unique_ratio <= CATEGORICAL_MAX_UNIQUE_RATIO {
+        Ok(column.cast(&DataType::Categorical(None))?)
+    } else {
+        Ok(column.clone())
+    }
+}
+
+fn downcast_signed(column: &Series) -> Result<Series, Error> {
+    let (min, max) = match (column.min::<i64>(), column.max::<i64>()) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return Ok(column.clone()),
+    };
+
+    let dtype = if min >= i8::MIN as i64 && max <= i8::MAX as i64 {
+        DataType::Int8
+    } else if min >= i16::MIN as i64 && max <= i16::MAX as i64 {
+        DataType::Int16
+    } else if min >= i32::MIN as i64 && max <= i32::MAX as i64 {
+        DataType::Int32
+    } else {
+        DataType::Int64
+    };
+
+    Ok(column.cast(&dtype)?)
+}
+
+fn downcast_unsigned(column: &Series) -> Result<Series, Error> {
+    let max = match column.max::<u64>() {
+        Some(max) => max,
+        None => return Ok(column.clone()),
+    };
+
+    let dtype = if max <= u8::MAX as u64 {
+        DataType::UInt8
+    } else if max <= u16::MAX as u64 {
+        DataType::UInt16
+    } else if max <= u32::MAX as u64 {
+        DataType::UInt32
+    } else {
+        DataType::UInt64
+    };
+
+    Ok(column.cast(&dtype)?)
+}
@@ -157,4 +157,2936 @@ fn test_gdal_bytes_from_df() {
     // println!("{}", String::from_utf8(_geojson_bytes).unwrap());
 }
 
+#[test]
+fn test_attribute_filter() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"name":"bar"},"geometry":{"type":"Point","coordinates":[3,4]}}]}"#.as_bytes();
+
+    let mut params = crate::ReadParams::default();
+    params.attribute_filter = Some("name = 'bar'");
+    let df = df_from_bytes(geojson, None, Some(params)).unwrap();
+    assert_eq!(df.height(), 1);
+}
+
+#[test]
+fn test_archive_paths() {
+    let members = list_archive_members("test_data/stations_shapefile.shp.zip").unwrap();
+    assert!(members.contains(&"stations.shp".to_owned()));
+
+    let _df = df_from_resource("test_data/stations_shapefile.shp.zip!stations.shp", None).unwrap();
+}
+
+#[test]
+fn test_column_selection() {
+    let mut params = crate::ReadParams::default();
+    params.columns = Some(&["name"]);
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+    assert_eq!(df.get_column_names(), &["name", "geometry"]);
+}
+
+#[test]
+fn test_geometry_only() {
+    let mut params = crate::ReadParams::default();
+    params.geometry_only = true;
+    params.fid_column_name = Some("fid");
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+    assert_eq!(df.get_column_names(), &["fid", "geometry"]);
+}
+
+#[test]
+fn test_skip_geometry() {
+    let mut params = crate::ReadParams::default();
+    params.skip_geometry = true;
+    params.columns = Some(&["name"]);
+    let schema = schema_from_resource("test_data/stations.shp", Some(params.clone())).unwrap();
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+    assert_eq!(df.get_column_names(), &["name"]);
+    assert_eq!(schema.len(), 1);
+}
+
+#[test]
+fn test_normalize_wkb() {
+    use crate::geom::{normalize_wkb, ByteOrder};
+
+    // POINT (1 2), constructed explicitly so the test doesn't depend on the platform's native byte order
+    let mut big_endian = vec![0u8];
+    big_endian.extend_from_slice(&1u32.to_be_bytes());
+    big_endian.extend_from_slice(&1f64.to_be_bytes());
+    big_endian.extend_from_slice(&2f64.to_be_bytes());
+
+    let mut little_endian = vec![1u8];
+    little_endian.extend_from_slice(&1u32.to_le_bytes());
+    little_endian.extend_from_slice(&1f64.to_le_bytes());
+    little_endian.extend_from_slice(&2f64.to_le_bytes());
+
+    let ca: BinaryChunked = vec![Some(big_endian.clone()), Some(little_endian.clone())]
+        .into_iter()
+        .collect();
+    let series = ca.into_series();
+
+    let normalized = normalize_wkb(&series, ByteOrder::LittleEndian).unwrap();
+    let normalized = normalized.binary().unwrap();
+    assert_eq!(normalized.get(0), Some(little_endian.as_slice()));
+    assert_eq!(normalized.get(1), Some(little_endian.as_slice()));
+
+    let normalized = normalize_wkb(&series, ByteOrder::BigEndian).unwrap();
+    let normalized = normalized.binary().unwrap();
+    assert_eq!(normalized.get(0), Some(big_endian.as_slice()));
+    assert_eq!(normalized.get(1), Some(big_endian.as_slice()));
+
+    // The read path always emits little-endian WKB
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#.as_bytes();
+    let df = df_from_bytes(geojson, None, None).unwrap();
+    let geometry = df.column("geometry").unwrap().binary().unwrap();
+    assert_eq!(geometry.get(0), Some(little_endian.as_slice()));
+}
+
+#[test]
+fn test_df_from_sql() {
+    let df = df_from_sql(
+        "test_data/stations.shp",
+        "SELECT name FROM stations",
+        gdal::vector::sql::Dialect::DEFAULT,
+        None,
+    )
+    .unwrap();
+    assert_eq!(df.get_column_names(), &["name", "geometry"]);
+}
+
+#[test]
+fn test_read_fids() {
+    let mut params = crate::ReadParams::default();
+    params.fids = Some(&[2, 0]);
+    params.fid_column_name = Some("fid");
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+    assert_eq!(df.height(), 2);
+
+    let fids: Vec<Option<u64>> = df.column("fid").unwrap().u64().unwrap().into_iter().collect();
+    assert_eq!(fids, vec![Some(2), Some(0)]);
+}
+
+#[test]
+fn test_expected_schema() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#.as_bytes();
+
+    let schema = Schema::from_iter([
+        Field::new("name", DataType::Utf8),
+        Field::new("geometry", DataType::Binary),
+    ]);
+    let mut params = crate::ReadParams::default();
+    params.expected_schema = Some(&schema);
+    df_from_bytes(geojson, None, Some(params)).unwrap();
+
+    let schema = Schema::from_iter([
+        Field::new("name", DataType::Int64),
+        Field::new("population", DataType::Int64),
+        Field::new("geometry", DataType::Binary),
+    ]);
+    let mut params = crate::ReadParams::default();
+    params.expected_schema = Some(&schema);
+    match df_from_bytes(geojson, None, Some(params)) {
+        Err(Error::SchemaMismatch(mismatches)) => assert_eq!(mismatches.len(), 2),
+        other => panic!("Expected a schema mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_sampling() {
+    let mut params = crate::ReadParams::default();
+    params.sample_every_n = Some(10);
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+    assert_eq!(df.height(), 9);
+
+    let mut params = crate::ReadParams::default();
+    params.sample_fraction = Some(0.5);
+    params.sample_seed = Some(42);
+    let first = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+
+    let mut params = crate::ReadParams::default();
+    params.sample_fraction = Some(0.5);
+    params.sample_seed = Some(42);
+    let second = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+
+    assert!(first.height() > 0 && first.height() < 86);
+    assert!(first
+        .column("geometry")
+        .unwrap()
+        .series_equal(second.column("geometry").unwrap()));
+}
+
+#[test]
+fn test_reproject_on_read() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#.as_bytes();
+
+    let web_mercator = gdal::spatial_ref::SpatialRef::from_epsg(3857).unwrap();
+    let mut params = crate::ReadParams::default();
+    params.target_srs = Some(&web_mercator);
+    params.geometry_format = GeometryFormat::WKT;
+    let df = df_from_bytes(geojson, None, Some(params)).unwrap();
+
+    let wkt = df.column("geometry").unwrap().utf8().unwrap().get(0).unwrap().to_owned();
+    assert!(wkt.starts_with("POINT (111319.49"));
+}
+
+#[test]
+fn test_spatial_sort() {
+    use crate::geom::{spatial_sort, SpaceFillingCurve};
+
+    let df = df_from_resource("test_data/stations.shp", None).unwrap();
+    for curve in [SpaceFillingCurve::Hilbert, SpaceFillingCurve::Morton] {
+        let sorted = spatial_sort(&df, GeometryColumnSpec::default(), curve, Some("key")).unwrap();
+        assert_eq!(sorted.height(), df.height());
+
+        let keys: Vec<u64> = sorted.column("key").unwrap().u64().unwrap().into_no_null_iter().collect();
+        assert!(keys.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
+
+#[test]
+fn test_wkb_with_bbox() {
+    let mut params = crate::ReadParams::default();
+    params.geometry_format = GeometryFormat::WKBWithBbox;
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+
+    let geometry = df.column("geometry").unwrap().struct_().unwrap();
+    let names: Vec<&str> = geometry.fields().iter().map(|f| f.name()).collect();
+    assert_eq!(names, ["wkb", "xmin", "ymin", "xmax", "ymax"]);
+
+    let xmin = geometry.field_by_name("xmin").unwrap();
+    let xmax = geometry.field_by_name("xmax").unwrap();
+    assert!(xmin.lt_eq(&xmax).unwrap().all());
+
+    let mut params = crate::WriteParams::default();
+    params.geometry_format = GeometryFormat::WKBWithBbox;
+    let json_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdal_bytes_from_df(&df, &json_driver, Some(params)).unwrap();
+
+    // The geometries are written from the `wkb` field of the struct
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    assert_eq!(roundtrip.height(), df.height());
+    let written = roundtrip.column("geometry").unwrap().binary().unwrap();
+    let wkb = geometry.field_by_name("wkb").unwrap();
+    for (written, wkb) in written.into_iter().zip(wkb.binary().unwrap().into_iter()) {
+        let written = gdal::vector::Geometry::from_wkb(written.unwrap())
+            .unwrap()
+            .get_point(0);
+        let expected = gdal::vector::Geometry::from_wkb(wkb.unwrap())
+            .unwrap()
+            .get_point(0);
+        assert!((written.0 - expected.0).abs() < 1e-9 && (written.1 - expected.1).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_df_from_resource_full() {
+    let resource = df_from_resource_full("test_data/stations.shp", None).unwrap();
+    assert_eq!(resource.layer_name, "stations");
+    assert_eq!(resource.geometry_column_name, "geometry");
+    assert_eq!(resource.df.height(), 86);
+    assert!(resource.srs_wkt.is_some());
+}
+
+#[test]
+fn test_gdal_dataframe() {
+    let gdf = GdalDataFrame::from_resource("test_data/stations.shp", None).unwrap();
+    assert!(gdf.srs().is_some());
+    assert_eq!(gdf.height(), 86);
+
+    let json_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdf.to_gdal_bytes(&json_driver, None).unwrap();
+
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    assert_eq!(roundtrip.height(), gdf.height());
+}
+
+#[test]
+fn test_mapinfo_styles() {
+    let mut params = crate::ReadParams::default();
+    params.style_column_name = Some("style");
+    let resource = df_from_resource_full("test_data/mapinfo_styles.mif", Some(params)).unwrap();
+    assert_eq!(resource.srs_epsg, Some(4326));
+
+    let styles: Vec<&str> = resource.df.column("style").unwrap().utf8().unwrap().into_no_null_iter().collect();
+    assert!(styles[0].starts_with("SYMBOL("));
+    assert!(styles[1].starts_with("PEN("));
+}
+
+#[test]
+fn test_describe_resource() {
+    let layers = describe_resource("test_data/stations.shp", None).unwrap();
+    assert_eq!(layers.len(), 1);
+
+    let stations = &layers[0];
+    assert_eq!(stations.name, "stations");
+    assert_eq!(stations.feature_count, 86);
+    assert_eq!(stations.geometry_type, gdal::vector::OGRwkbGeometryType::wkbPoint);
+    assert!(stations.srs_wkt.is_some());
+    assert!(stations.extent.is_some());
+
+    let field_names: Vec<&str> = stations.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(field_names, ["name", "marker-col", "marker-sym", "line"]);
+}
+
+#[test]
+fn test_csv_column_types() {
+    let csv = "name,population\nfoo,100\nbar,200\n".as_bytes();
+    let mut params = crate::ReadParams::default();
+    params.csv_column_types = Some(&[("population", "Integer64")]);
+    let result = df_from_bytes(csv, Some("cities.csv"), Some(params));
+
+    // OGR_SCHEMA is only supported from GDAL 3.11, and older versions would ignore it
+    let version: i32 = gdal::version::version_info("VERSION_NUM").parse().unwrap();
+    if version < 3110000 {
+        assert!(matches!(
+            result,
+            Err(Error::GdalVersionTooOld { param: "csv_column_types", .. })
+        ));
+        return;
+    }
+
+    let df = result.unwrap();
+
+    assert_eq!(df.column("population").unwrap().dtype(), &DataType::Int64);
+    assert_eq!(df.column("name").unwrap().dtype(), &DataType::Utf8);
+}
+
+#[test]
+fn test_schema_from_resource() {
+    let schema = schema_from_resource("test_data/stations.shp", None).unwrap();
+    let df = df_from_resource("test_data/stations.shp", None).unwrap();
+
+    assert_eq!(schema.len(), df.width());
+    for (name, dtype) in schema.iter() {
+        assert_eq!(df.column(name).unwrap().dtype(), dtype);
+    }
+}
+
+#[test]
+fn test_all_null_columns() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":null,"count":1},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"name":null,"count":null},"geometry":{"type":"Point","coordinates":[3,4]}}]}"#.as_bytes();
+    let df = df_from_bytes(geojson, None, None).unwrap();
+
+    assert_eq!(df.get_column_names(), &["name", "count", "geometry"]);
+    assert_eq!(df.column("name").unwrap().null_count(), 2);
+    assert_eq!(df.column("count").unwrap().dtype(), &DataType::Int32);
+    assert_eq!(df.column("count").unwrap().null_count(), 1);
+}
+
+#[test]
+fn test_track_resampling() {
+    let df = track_from_resource("test_data/track.gpx", None, TrackParams::default()).unwrap();
+    assert_eq!(df.height(), 3);
+
+    // Heading north, then east
+    let headings: Vec<Option<f64>> = df.column("heading").unwrap().f64().unwrap().into_iter().collect();
+    assert_eq!(headings[0], None);
+    assert!(headings[1].unwrap().abs() < 1.0);
+    assert!((headings[2].unwrap() - 90.0).abs() < 1.0);
+
+    let mut track_params = TrackParams::default();
+    track_params.resample_interval = Some(std::time::Duration::from_secs(5));
+    let df = track_from_resource("test_data/track.gpx", None, track_params).unwrap();
+    assert_eq!(df.height(), 5);
+
+    let elevations: Vec<f64> = df.column("ele").unwrap().f64().unwrap().into_no_null_iter().collect();
+    assert_eq!(elevations, [10.0, 11.0, 12.0, 13.0, 14.0]);
+}
+
+#[test]
+fn test_sanitize_field_names() {
+    let df = df![
+        "population_2020" => [1i64],
+        "population_2021" => [2i64],
+        "geometry" => [1i32],
+    ]
+    .unwrap();
+
+    let (sanitized, renames) = sanitize_field_names(&df, "ESRI Shapefile", "geometry").unwrap();
+    assert_eq!(sanitized.get_column_names(), &["population", "populati_1", "geometry"]);
+    assert_eq!(renames.len(), 2);
+    assert_eq!(renames[1].reason, FieldRenameReason::Duplicate);
+
+    let (sanitized, _) = sanitize_field_names(&df, "PostgreSQL", "geometry").unwrap();
+    assert_eq!(sanitized.get_column_names(), df.get_column_names());
+}
+
+#[test]
+fn test_schema_overrides() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"code":"0042","count":7},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#.as_bytes();
+
+    let overrides = std::collections::HashMap::from([
+        ("code", DataType::Int64),
+        ("count", DataType::Utf8),
+    ]);
+    let mut params = crate::ReadParams::default();
+    params.schema_overrides = Some(&overrides);
+    let df = df_from_bytes(geojson, None, Some(params)).unwrap();
+
+    assert_eq!(df.column("code").unwrap().i64().unwrap().get(0), Some(42));
+    assert_eq!(df.column("count").unwrap().utf8().unwrap().get(0), Some("7"));
+}
+
+#[test]
+fn test_layer_capabilities() {
+    let capabilities = layer_capabilities("test_data/stations.shp", None).unwrap();
+    assert!(capabilities.fast_feature_count);
+    assert!(capabilities.random_read);
+    assert!(!capabilities.curve_geometries);
+}
+
+#[cfg(feature = "geojson")]
+#[test]
+fn test_feature_collection() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"name":"bar"},"geometry":{"type":"Point","coordinates":[3,4]}}]}"#;
+    let collection: geojson::FeatureCollection = geojson.parse().unwrap();
+
+    let df = df_from_feature_collection(&collection, None).unwrap();
+    assert_eq!(df.height(), 2);
+
+    let roundtrip = feature_collection_from_df(&df, None).unwrap();
+    assert_eq!(roundtrip.features.len(), 2);
+    assert_eq!(roundtrip.features[0].property("name"), collection.features[0].property("name"));
+}
+
+#[test]
+fn test_write_list_columns() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)")
+        .unwrap()
+        .wkb()
+        .unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let df = DataFrame::new(vec![
+        Series::new(
+            "ints",
+            &[Series::new("", &[1i32, 2]), Series::new("", &[3i32])],
+        ),
+        Series::new(
+            "reals",
+            &[Series::new("", &[1.5f64]), Series::new("", &[2.5f64, 3.5])],
+        ),
+        Series::new(
+            "names",
+            &[Series::new("", &["a", "b"]), Series::new("", &["c"])],
+        ),
+        geometry,
+    ])
+    .unwrap();
+
+    let (bytes, report) =
+        gdal_bytes_from_df_with_report(&df, &VectorDriver::GeoJson, None).unwrap();
+    assert!(report.warnings.is_empty());
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    for name in ["ints", "reals", "names"] {
+        let column = roundtrip.column(name).unwrap();
+        assert_eq!(column.dtype(), df.column(name).unwrap().dtype());
+        let expected = df.column(name).unwrap().list().unwrap();
+        for (value, expected) in column.list().unwrap().into_iter().zip(expected.into_iter()) {
+            assert!(value.unwrap().series_equal(&expected.unwrap()), "{}", name);
+        }
+    }
+
+    // OGR lists can't hold nulls, so a list with a null element is reported rather than written with its elements shifted
+    let with_null = DataFrame::new(vec![
+        Series::new(
+            "ints",
+            &[
+                Series::new("", &[Some(1i32), None, Some(2)]),
+                Series::new("", &[Some(3i32)]),
+            ],
+        ),
+        df.column("geometry").unwrap().clone(),
+    ])
+    .unwrap();
+    let (bytes, report) =
+        gdal_bytes_from_df_with_report(&with_null, &VectorDriver::GeoJson, None).unwrap();
+    assert!(matches!(
+        report.warnings.as_slice(),
+        [WriteWarning::ValueNotWritten { row: 0, column }] if column == "ints"
+    ));
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    let ints = roundtrip.column("ints").unwrap().list().unwrap();
+    assert!(ints.get(0).is_none());
+    assert!(ints.get(1).unwrap().series_equal(&Series::new("", &[3i32])));
+
+    let params = WriteParams {
+        strict: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        gdal_bytes_from_df(&with_null, &VectorDriver::GeoJson, Some(params)),
+        Err(Error::StrictWrite(_))
+    ));
+}
+
+#[test]
+fn test_write_dates() {
+    use polars::export::chrono::NaiveDate;
+
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let dates = DateChunked::from_naive_date_options(
+        "date",
+        [NaiveDate::from_ymd_opt(2023, 3, 14), None],
+    );
+    let df = DataFrame::new(vec![dates.into_series(), geometry]).unwrap();
+
+    let json_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdal_bytes_from_df(&df, &json_driver, None).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+
+    assert!(roundtrip
+        .column("date")
+        .unwrap()
+        .series_equal_missing(df.column("date").unwrap()));
+}
+
+#[test]
+fn test_write_times() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    // 12:30:00 and null, in nanoseconds since midnight
+    let times = Int64Chunked::new("time", &[Some(45_000_000_000_000), None]).into_time();
+    let df = DataFrame::new(vec![times.into_series(), geometry]).unwrap();
+
+    let json_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdal_bytes_from_df(&df, &json_driver, None).unwrap();
+    assert!(String::from_utf8_lossy(&bytes).contains("\"12:30:00\""));
+
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    assert!(roundtrip
+        .column("time")
+        .unwrap()
+        .series_equal_missing(df.column("time").unwrap()));
+}
+
+#[test]
+fn test_null_sentinels() {
+    let geojson = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"elevation": -9999, "name": "   "}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "properties": {"elevation": 12, "name": "hill"}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+    ]}"#;
+    let sentinels = [
+        ("elevation", NullSentinel::Integer(-9999)),
+        ("name", NullSentinel::Blank),
+    ];
+
+    let mut params = crate::ReadParams::default();
+    params.null_sentinels = Some(&sentinels);
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    assert_eq!(df.column("elevation").unwrap().null_count(), 1);
+    assert_eq!(df.column("name").unwrap().null_count(), 1);
+
+    let mut params = crate::WriteParams::default();
+    params.null_sentinels = Some(&sentinels[..1]);
+    let json_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdal_bytes_from_df(&df, &json_driver, Some(params)).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    assert_eq!(
+        roundtrip.column("elevation").unwrap().i32().unwrap().get(0),
+        Some(-9999)
+    );
+}
+
+#[test]
+fn test_parse_json_fields() {
+    let geojson = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"address": {"street": "Main St", "number": 12}}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "properties": {"address": {"street": "High St"}}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+    ]}"#;
+
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+    assert_eq!(df.column("address").unwrap().dtype(), &DataType::Utf8);
+
+    let mut params = crate::ReadParams::default();
+    params.parse_json_fields = true;
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    let address = df.column("address").unwrap().struct_().unwrap();
+    let number = address.field_by_name("number").unwrap();
+    assert_eq!(number.i64().unwrap().get(0), Some(12));
+    assert_eq!(number.i64().unwrap().get(1), None);
+}
+
+#[test]
+fn test_write_struct_as_json() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let props = StructChunked::new(
+        "props",
+        &[Series::new("a", &[1i32]), Series::new("b", &["x"])],
+    )
+    .unwrap()
+    .into_series();
+    let df = DataFrame::new(vec![props, geometry]).unwrap();
+
+    let mut params = crate::WriteParams::default();
+    params.struct_as_json = true;
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+
+    let json = roundtrip.column("props").unwrap().utf8().unwrap().get(0).unwrap().to_owned();
+    assert!(json.contains("\"a\"") && json.contains("\"x\""), "{}", json);
+}
+
+#[test]
+fn test_page_from_resource() {
+    let sort = [SortKey {
+        column: "name",
+        descending: false,
+    }];
+    let request = PageRequest {
+        filter: Some("region = 'Midwest'"),
+        sort: &sort,
+        page: 1,
+        page_size: 5,
+        ..Default::default()
+    };
+    let (df, total_count) = page_from_resource(
+        "test_data/us_states.feature_collection.implicit_4326.json",
+        None,
+        request,
+    )
+    .unwrap();
+
+    // The twelve Midwest states, sorted by name: the second page starts at Minnesota
+    assert_eq!(total_count, 12);
+    assert_eq!(df.height(), 5);
+    assert_eq!(
+        df.column("name").unwrap().utf8().unwrap().get(0),
+        Some("Minnesota")
+    );
+}
+
+#[test]
+fn test_field_domains() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let land_use = Series::new("land_use", &["residential", "park", "residential"])
+        .cast(&DataType::Categorical(None))
+        .unwrap();
+    let df = DataFrame::new(vec![land_use, geometry]).unwrap();
+
+    let path = "/vsimem/polars_gdal/test_field_domains.gpkg";
+    let mut params = crate::WriteParams::default();
+    params.categorical_as_domain = true;
+    let dataset = gdal_resource_from_df(&df, &VectorDriver::Gpkg, path, Some(params)).unwrap();
+    drop(dataset);
+
+    let roundtrip = df_from_resource(path, None).unwrap();
+    let land_use = roundtrip.column("land_use").unwrap();
+    assert!(matches!(land_use.dtype(), DataType::Categorical(_)));
+    assert_eq!(
+        land_use.cast(&DataType::Utf8).unwrap().utf8().unwrap().get(1),
+        Some("park")
+    );
+
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
+
+#[test]
+fn test_write_geojson_ids() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let df = DataFrame::new(vec![Series::new("code", &["a", "b"]), geometry]).unwrap();
+
+    let mut params = crate::WriteParams::default();
+    params.id_column = Some("code");
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)).unwrap();
+    assert!(String::from_utf8(bytes).unwrap().contains("\"id\": \"a\""));
+
+    let mut params = crate::WriteParams::default();
+    params.id_column = Some("code");
+    let result = gdal_bytes_from_df(&df, &VectorDriver::Csv, Some(params));
+    assert!(matches!(result, Err(Error::UnsupportedByDriver { .. })));
+}
+
+#[test]
+fn test_write_binary() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let mut payload = [Some(vec![0u8, 1, 2, 255]), None]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    payload.rename("payload");
+    let df = DataFrame::new(vec![payload, geometry]).unwrap();
+
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::FlatGeobuf, None).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+
+    assert!(roundtrip
+        .column("payload")
+        .unwrap()
+        .series_equal_missing(df.column("payload").unwrap()));
+}
+
+#[test]
+fn test_roundtrip_check() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let df = DataFrame::new(vec![
+        Series::new("population_total", &[1i32, 2]),
+        Series::new("name", &["a", "b"]),
+        geometry,
+    ])
+    .unwrap();
+
+    let report = roundtrip_check(&df, &VectorDriver::GeoJson, None).unwrap();
+    assert!(report.is_lossless(), "{:?}", report);
+
+    let report = roundtrip_check(&df, &VectorDriver::Shapefile, None).unwrap();
+    assert_eq!(report.roundtrip_row_count, 2);
+    assert_eq!(
+        report.differences,
+        vec![ColumnDifference::Renamed {
+            original: "population_total".to_owned(),
+            roundtrip: "population".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn test_wkb_wkt_conversion() {
+    use crate::geom::{wkb_to_wkt, wkt_to_wkb};
+
+    let wkt = Series::new("geometry", &[Some("POINT (1 2)"), None]);
+    let wkb = wkt_to_wkb(&wkt).unwrap();
+    assert_eq!(wkb.dtype(), &DataType::Binary);
+    assert_eq!(wkb.null_count(), 1);
+
+    let roundtrip = wkb_to_wkt(&wkb).unwrap();
+    let parsed = gdal::vector::Geometry::from_wkt(roundtrip.utf8().unwrap().get(0).unwrap()).unwrap();
+    assert_eq!(parsed.get_point(0), (1.0, 2.0, 0.0));
+}
+
+#[test]
+fn test_write_datetimes() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry)].into_iter().collect::<BinaryChunked>().into_series();
+    geometry.rename("geometry");
+
+    // 2023-07-01T12:00:00Z
+    let timestamps = Int64Chunked::new("time", &[1_688_212_800_000i64])
+        .into_datetime(TimeUnit::Milliseconds, Some("Europe/Berlin".to_owned()));
+    let df = DataFrame::new(vec![timestamps.into_series(), geometry]).unwrap();
+
+    let json_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdal_bytes_from_df(&df, &json_driver, None).unwrap();
+    let json = String::from_utf8(bytes).unwrap();
+
+    assert!(json.contains("2023-07-01T14:00:00+02:00"), "{}", json);
+}
+
+#[test]
+fn test_vector_driver() {
+    assert_eq!(VectorDriver::GeoJson.name(), "GeoJSON");
+    assert!(VectorDriver::GeoJson.available());
+
+    let df = df_from_resource("test_data/stations.shp", None).unwrap();
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, None).unwrap();
+    assert_eq!(df_from_bytes(&bytes, None, None).unwrap().height(), df.height());
+}
+
+#[test]
+fn test_read_stats() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"count":3},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"count":null},"geometry":{"type":"Point","coordinates":[3,4]}},{"type":"Feature","properties":{"count":1},"geometry":{"type":"Point","coordinates":[-1,5]}}]}"#.as_bytes();
+
+    let input_mem_path = "/vsimem/polars_gdal/test_read_stats/layer.geojson";
+    gdal::vsi::create_mem_file(input_mem_path, geojson.to_vec()).unwrap();
+    let (df, stats) = df_from_resource_with_stats(input_mem_path, None).unwrap();
+    gdal::vsi::unlink_mem_file(input_mem_path).unwrap();
+
+    assert_eq!(stats.row_count, df.height());
+    assert_eq!(stats.extent, Some([-1.0, 2.0, 3.0, 5.0]));
+    assert_eq!(stats.columns.len(), 1);
+    assert_eq!(stats.columns[0].null_count, 1);
+    assert_eq!(stats.columns[0].min, Some(AnyValue::Int32(1)));
+    assert_eq!(stats.columns[0].max, Some(AnyValue::Int32(3)));
+}
+
+#[test]
+fn test_df_from_resource_concurrent() {
+    let df = df_from_resource("test_data/stations.shp", None).unwrap();
+    let concurrent = df_from_resource_concurrent("test_data/stations.shp", 3, None).unwrap();
+
+    assert!(concurrent.frame_equal_missing(&df));
+
+    // GeoJSON isn't split between readers
+    let df = df_from_resource_concurrent(
+        "test_data/us_states.feature_collection.implicit_4326.json",
+        3,
+        None,
+    )
+    .unwrap();
+    assert!(df.height() > 0);
+}
+
+#[test]
+fn test_write_booleans() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let active = Series::new("active", &[Some(true), None, Some(false)]);
+    let df = DataFrame::new(vec![active, geometry]).unwrap();
+
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, None).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+
+    assert!(roundtrip
+        .column("active")
+        .unwrap()
+        .series_equal_missing(df.column("active").unwrap()));
+}
+
+#[test]
+fn test_layer_management() {
+    let driver = gdal::DriverManager::get_driver_by_name("GPKG").unwrap();
+    let mut dataset = driver
+        .create_vector_only("/vsimem/polars_gdal/test_layer_management.gpkg")
+        .unwrap();
+    for name in ["roads", "_polars_gdal_tmp_roads", "rivers"] {
+        dataset
+            .create_layer(gdal::LayerOptions {
+                name,
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    rename_layer(&mut dataset, "rivers", "waterways").unwrap();
+    let dropped = list_and_drop_temp_layers(&mut dataset).unwrap();
+    delete_layer(&mut dataset, "roads").unwrap();
+
+    assert_eq!(dropped, vec!["_polars_gdal_tmp_roads".to_owned()]);
+    let names: Vec<String> = dataset.layers().map(|layer| layer.name()).collect();
+    assert_eq!(names, vec!["waterways".to_owned()]);
+    assert!(matches!(
+        delete_layer(&mut dataset, "roads"),
+        Err(Error::LayerNotFound(_))
+    ));
+}
+
+#[test]
+fn test_write_narrow_numbers() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let floors = Series::new("floors", &[Some(3i16), None]);
+    let height = Series::new("height", &[Some(12.5f32), Some(-0.25)]);
+    let df = DataFrame::new(vec![floors, height, geometry]).unwrap();
+
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::FlatGeobuf, None).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+
+    assert_eq!(roundtrip.column("floors").unwrap().dtype(), &DataType::Int16);
+    assert_eq!(roundtrip.column("height").unwrap().dtype(), &DataType::Float32);
+    assert!(roundtrip
+        .column("height")
+        .unwrap()
+        .series_equal_missing(df.column("height").unwrap()));
+}
+
+#[test]
+fn test_read_utc_datetimes() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"time":"2023-07-01T14:00:00+02:00"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#.as_bytes();
+
+    let mut params = crate::ReadParams::default();
+    params.utc_datetimes = true;
+    let df = df_from_bytes(geojson, None, Some(params)).unwrap();
+
+    let time = df.column("time").unwrap();
+    assert_eq!(
+        time.dtype(),
+        &DataType::Datetime(TimeUnit::Nanoseconds, Some("UTC".to_owned()))
+    );
+    assert_eq!(
+        time.cast(&DataType::Int64).unwrap().i64().unwrap().get(0),
+        Some(1_688_212_800_000_000_000)
+    );
+}
+
+#[test]
+fn test_area_and_length() {
+    use crate::geom::{area, length, AreaUnit, LengthUnit};
+
+    let wkt = Series::new(
+        "geometry",
+        &[
+            Some("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))"),
+            Some("LINESTRING (0 0, 1 0)"),
+            None,
+        ],
+    );
+    let wkb = crate::geom::wkt_to_wkb(&wkt).unwrap();
+    let wgs84 = gdal::spatial_ref::SpatialRef::from_epsg(4326).unwrap();
+
+    // A one degree square at the equator is about 12,300 km², and a degree of longitude is about 111.2 km
+    let areas = area(&wkb, GeometryFormat::WKB, Some(&wgs84), AreaUnit::SquareKilometers).unwrap();
+    let area_km2 = areas.get(0).unwrap();
+    assert!((12_200.0..12_400.0).contains(&area_km2), "{}", area_km2);
+    assert_eq!(areas.get(1), Some(0.0));
+    assert_eq!(areas.get(2), None);
+
+    let lengths = length(&wkb, GeometryFormat::WKB, Some(&wgs84), LengthUnit::Kilometers).unwrap();
+    let length_km = lengths.get(1).unwrap();
+    assert!((111.0..111.4).contains(&length_km), "{}", length_km);
+
+    // Without an SRS, coordinates are in meters
+    let hectares = area(&wkb, GeometryFormat::WKB, None, AreaUnit::Hectares).unwrap();
+    assert_eq!(hectares.get(0), Some(0.0001));
+}
+
+#[test]
+fn test_distances() {
+    use crate::geom::{distances, wkt_to_wkb};
+
+    let left = wkt_to_wkb(&Series::new(
+        "left",
+        &[Some("POINT (0 0)"), Some("POINT (0 0)"), None],
+    ))
+    .unwrap();
+    let right = wkt_to_wkb(&Series::new(
+        "right",
+        &[Some("POINT (3 4)"), Some("LINESTRING (1 -1, 1 1)"), Some("POINT (1 1)")],
+    ))
+    .unwrap();
+
+    let planar = distances(&left, &right, GeometryFormat::WKB, false).unwrap();
+    assert_eq!(planar.get(0), Some(5.0));
+    assert_eq!(planar.get(1), Some(1.0));
+    assert_eq!(planar.get(2), None);
+
+    // A degree of longitude at the equator is about 111.2 km
+    let geodesic = distances(&left, &right, GeometryFormat::WKB, true).unwrap();
+    let distance = geodesic.get(1).unwrap();
+    assert!((111_000.0..111_400.0).contains(&distance), "{}", distance);
+}
+
+#[test]
+fn test_df_from_byte_parts() {
+    let df = df_from_byte_parts(
+        &[
+            ("stations.shp", &include_bytes!("../test_data/stations.shp")[..]),
+            ("stations.shx", &include_bytes!("../test_data/stations.shx")[..]),
+            ("stations.dbf", &include_bytes!("../test_data/stations.dbf")[..]),
+            ("stations.prj", &include_bytes!("../test_data/stations.prj")[..]),
+        ],
+        None,
+    )
+    .unwrap();
+
+    assert!(df.frame_equal_missing(&df_from_resource("test_data/stations.shp", None).unwrap()));
+}
+
+#[test]
+fn test_write_fids() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let ids = Series::new("parcel_id", &[10i64, 20]);
+    let df = DataFrame::new(vec![ids, geometry]).unwrap();
+
+    let path = "/vsimem/polars_gdal/test_write_fids.gpkg";
+    let mut params = crate::WriteParams::default();
+    params.fid_column_name = Some("parcel_id");
+    let dataset = gdal_resource_from_df(&df, &VectorDriver::Gpkg, path, Some(params)).unwrap();
+    drop(dataset);
+
+    let mut params = crate::ReadParams::default();
+    params.fid_column_name = Some("parcel_id");
+    let roundtrip = df_from_resource(path, Some(params)).unwrap();
+
+    // The FIDs aren't also written as a field
+    assert_eq!(roundtrip.get_column_names(), vec!["parcel_id", "geometry"]);
+    let fids: Vec<Option<u64>> = roundtrip.column("parcel_id").unwrap().u64().unwrap().into_iter().collect();
+    assert_eq!(fids, vec![Some(10), Some(20)]);
+}
+
+#[test]
+fn test_geometry_type_scan() {
+    use gdal::vector::OGRwkbGeometryType;
+
+    let geometry_type = geometry_type_from_resource("test_data/stations.shp", None, false).unwrap();
+    assert_eq!(geometry_type.declared, OGRwkbGeometryType::wkbPoint);
+    assert!(geometry_type.honored);
+    assert_eq!(geometry_type.scanned, None);
+
+    let layers = describe_resource_with_geometry_scan("test_data/stations.shp", None).unwrap();
+    assert_eq!(layers[0].scanned_geometry_type, Some(OGRwkbGeometryType::wkbPoint));
+    assert!(layers[0].geometry_type_honored);
+
+    // Features of mixed geometry types in a layer that doesn't declare a type
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{},"geometry":{"type":"MultiPoint","coordinates":[[3,4],[5,6]]}}]}"#;
+    let path = "/vsimem/polars_gdal/test_geometry_type_scan.geojson";
+    gdal::vsi::create_mem_file(path, geojson.as_bytes().to_vec()).unwrap();
+    let geometry_type = geometry_type_from_resource(path, None, true).unwrap();
+    gdal::vsi::unlink_mem_file(path).unwrap();
+    assert_eq!(geometry_type.scanned, Some(OGRwkbGeometryType::wkbMultiPoint));
+}
+
+#[test]
+fn test_df_from_resources_deduplicated() {
+    let point = |x: i32, name: &str| {
+        format!(
+            r#"{{"type":"Feature","properties":{{"name":"{}"}},"geometry":{{"type":"Point","coordinates":[{},0]}}}}"#,
+            name, x
+        )
+    };
+    let tiles = [
+        format!(r#"{{"type":"FeatureCollection","features":[{},{}]}}"#, point(1, "a"), point(2, "edge")),
+        format!(r#"{{"type":"FeatureCollection","features":[{},{}]}}"#, point(2, "edge"), point(3, "b")),
+    ];
+    let paths = [
+        "/vsimem/polars_gdal/test_df_from_resources/tile_0.geojson",
+        "/vsimem/polars_gdal/test_df_from_resources/tile_1.geojson",
+    ];
+    for (path, tile) in paths.iter().zip(&tiles) {
+        gdal::vsi::create_mem_file(path, tile.as_bytes().to_vec()).unwrap();
+    }
+
+    let all = df_from_resources(&paths, None, Deduplicate::None).unwrap();
+    let deduplicated = df_from_resources(&paths, None, Deduplicate::Geometry).unwrap();
+    for path in paths {
+        gdal::vsi::unlink_mem_file(path).unwrap();
+    }
+
+    assert_eq!(all.height(), 4);
+    let names: Vec<Option<&str>> = deduplicated.column("name").unwrap().utf8().unwrap().into_iter().collect();
+    assert_eq!(names, vec![Some("a"), Some("edge"), Some("b")]);
+}
+
+#[test]
+fn test_read_all_geometry_fields() {
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut layer = dataset
+        .create_layer(gdal::LayerOptions {
+            name: "parcels",
+            ty: gdal::vector::OGRwkbGeometryType::wkbPolygon,
+            ..Default::default()
+        })
+        .unwrap();
+    let polygon = gdal::vector::Geometry::from_wkt("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))").unwrap();
+    let centroid = gdal::vector::Geometry::from_wkt("POINT (1 1)").unwrap();
+    unsafe {
+        let c_name = std::ffi::CString::new("centroid").unwrap();
+        let c_geom_field_defn =
+            gdal_sys::OGR_GFld_Create(c_name.as_ptr(), gdal::vector::OGRwkbGeometryType::wkbPoint);
+        gdal_sys::OGR_L_CreateGeomField(layer.c_layer(), c_geom_field_defn, 1);
+        gdal_sys::OGR_GFld_Destroy(c_geom_field_defn);
+
+        let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+        for with_centroid in [true, false] {
+            let c_feature = gdal_sys::OGR_F_Create(c_defn);
+            gdal_sys::OGR_F_SetGeomField(c_feature, 0, polygon.c_geometry());
+            if with_centroid {
+                gdal_sys::OGR_F_SetGeomField(c_feature, 1, centroid.c_geometry());
+            }
+            gdal_sys::OGR_L_CreateFeature(layer.c_layer(), c_feature);
+            gdal_sys::OGR_F_Destroy(c_feature);
+        }
+    }
+
+    let mut params = ReadParams::default();
+    params.all_geometry_fields = true;
+    params.geometry_format = GeometryFormat::WKT;
+    let schema = schema_from_layer(&layer, Some(params.clone()));
+    let df = df_from_layer(&mut layer, Some(params)).unwrap();
+
+    assert_eq!(df.get_column_names(), vec!["geometry", "centroid"]);
+    assert_eq!(schema.len(), df.width());
+    for (name, dtype) in schema.iter() {
+        assert_eq!(df.column(name).unwrap().dtype(), dtype);
+    }
+    let centroids: Vec<Option<&str>> = df.column("centroid").unwrap().utf8().unwrap().into_iter().collect();
+    assert_eq!(centroids, vec![Some("POINT (1 1)"), None]);
+}
+
+#[test]
+fn test_write_extra_geometry_columns() {
+    let polygon = Series::new(
+        "geometry",
+        &[
+            "POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))",
+            "POLYGON ((4 4, 6 4, 6 6, 4 6, 4 4))",
+        ],
+    );
+    let centroid = Series::new("centroid", &[Some("POINT (1 1)"), None]);
+    let name = Series::new("name", &["a", "b"]);
+    let df = DataFrame::new(vec![name, polygon, centroid]).unwrap();
+
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.extra_geometry_columns = Some(&[("centroid", "centroid")]);
+    let mut layer = gdal_layer_from_df(&df, &mut dataset, Some(params)).unwrap();
+
+    let mut read_params = ReadParams::default();
+    read_params.all_geometry_fields = true;
+    read_params.geometry_format = GeometryFormat::WKT;
+    let roundtrip = df_from_layer(&mut layer, Some(read_params)).unwrap();
+
+    assert_eq!(roundtrip.get_column_names(), vec!["name", "geometry", "centroid"]);
+    assert!(roundtrip
+        .column("centroid")
+        .unwrap()
+        .series_equal_missing(df.column("centroid").unwrap()));
+
+    let geojson_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.extra_geometry_columns = Some(&[("centroid", "centroid")]);
+    assert!(matches!(
+        gdal_bytes_from_df(&df, &geojson_driver, Some(params)),
+        Err(Error::UnsupportedByDriver { .. })
+    ));
+}
+
+#[test]
+fn test_write_profiles() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1.123456789 2)").unwrap().wkb().unwrap();
+    let mut geometry = [Some(geometry)].into_iter().collect::<BinaryChunked>().into_series();
+    geometry.rename("geometry");
+    let population = Series::new("population_2020", &[1200i64]);
+    let df = DataFrame::new(vec![population, geometry]).unwrap();
+
+    let (geojson, renames) = gdal_bytes_from_df_with_profile(&df, WriteProfile::GeoJsonWeb, None).unwrap();
+    let geojson = String::from_utf8(geojson).unwrap();
+    assert!(renames.is_empty());
+    assert!(geojson.contains("1.1234568"));
+    assert!(!geojson.contains("1.12345678"));
+
+    let (shapefile_df, renames) = WriteProfile::ShapefileLegacy.prepare(&df, "geometry").unwrap();
+    assert_eq!(shapefile_df.get_column_names(), &["population", "geometry"]);
+    assert_eq!(renames[0].reason, FieldRenameReason::TooLong);
+}
+
+#[test]
+fn test_build_rtree() {
+    use crate::geom::build_rtree;
+
+    // A 20 x 20 grid of points, so the tree has several levels, followed by a null
+    let mut points: Vec<Option<String>> = (0..400)
+        .map(|row| Some(format!("POINT ({} {})", row % 20, row / 20)))
+        .collect();
+    points.push(None);
+    let points = Series::new("geometry", points);
+    let index = build_rtree(&points, GeometryFormat::WKT).unwrap();
+
+    assert_eq!(index.query_bbox([2.5, 2.5, 4.5, 4.5]), vec![63, 64, 83, 84]);
+    assert!(index.query_bbox([100.0, 100.0, 101.0, 101.0]).is_empty());
+
+    let triangle = gdal::vector::Geometry::from_wkt("POLYGON ((0 0, 2 0, 0 2, 0 0))").unwrap();
+    assert_eq!(index.query_geometry(&triangle), vec![0, 1, 2, 20, 21, 40]);
+}
+
+#[cfg(feature = "lazy")]
+#[test]
+fn test_geometry_exprs() {
+    use crate::geom::{expr, wkt_to_wkb, AreaUnit};
+
+    let geometry = wkt_to_wkb(&Series::new(
+        "geometry",
+        &["POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))", "POINT (20 20)"],
+    ))
+    .unwrap();
+    let df = DataFrame::new(vec![geometry]).unwrap();
+
+    let df = df
+        .lazy()
+        .with_columns([
+            expr::area(col("geometry"), GeometryFormat::WKB, None, AreaUnit::SquareMeters)
+                .unwrap()
+                .alias("area"),
+            expr::to_wkt(col("geometry")).alias("wkt"),
+        ])
+        .filter(expr::within_bbox(col("geometry"), GeometryFormat::WKB, [0.0, 0.0, 10.0, 10.0]))
+        .collect()
+        .unwrap();
+
+    assert_eq!(df.height(), 1);
+    assert_eq!(df.column("area").unwrap().f64().unwrap().get(0), Some(4.0));
+    assert!(df.column("wkt").unwrap().utf8().unwrap().get(0).unwrap().starts_with("POLYGON"));
+}
+
+#[test]
+fn test_null_geometry_policy() {
+    let geojson = r#"{"type":"FeatureCollection","features":[
+        {"type":"Feature","properties":{"name":"point"},"geometry":{"type":"Point","coordinates":[1,2]}},
+        {"type":"Feature","properties":{"name":"missing"},"geometry":null},
+        {"type":"Feature","properties":{"name":"empty"},"geometry":{"type":"Point","coordinates":[]}}
+    ]}"#
+    .as_bytes();
+
+    let mut params = ReadParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    let kept = df_from_bytes(geojson, None, Some(params.clone())).unwrap();
+    let geometries: Vec<Option<&str>> = kept.column("geometry").unwrap().utf8().unwrap().into_iter().collect();
+    assert_eq!(geometries, vec![Some("POINT (1 2)"), None, Some("POINT EMPTY")]);
+
+    params.null_geometry_policy = NullGeometryPolicy::Skip;
+    let skipped = df_from_bytes(geojson, None, Some(params.clone())).unwrap();
+    assert_eq!(skipped.height(), 1);
+
+    params.null_geometry_policy = NullGeometryPolicy::Error;
+    assert!(matches!(
+        df_from_bytes(geojson, None, Some(params)),
+        Err(Error::NullGeometry(1))
+    ));
+}
+
+#[test]
+fn test_write_null_geometries() {
+    let geometry = Series::new("geometry", &[Some("POINT (1 2)"), None]);
+    let name = Series::new("name", &["point", "missing"]);
+    let df = DataFrame::new(vec![name, geometry]).unwrap();
+    let geojson_driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+
+    let write = |policy| {
+        let mut params = WriteParams::default();
+        params.geometry_format = GeometryFormat::WKT;
+        params.null_geometry_policy = policy;
+        gdal_bytes_from_df(&df, &geojson_driver, Some(params))
+    };
+
+    let kept = df_from_bytes(&write(NullGeometryPolicy::Keep).unwrap(), None, None).unwrap();
+    assert_eq!(kept.height(), 2);
+    assert_eq!(kept.column("geometry").unwrap().null_count(), 1);
+
+    let skipped = df_from_bytes(&write(NullGeometryPolicy::Skip).unwrap(), None, None).unwrap();
+    assert_eq!(skipped.height(), 1);
+
+    assert!(matches!(
+        write(NullGeometryPolicy::Error),
+        Err(Error::NullGeometry(1))
+    ));
+}
+
+#[test]
+fn test_geometry_type_policy() {
+    let geometry = Series::new(
+        "geometry",
+        &[
+            "POLYGON ((0 0, 1 0, 1 1, 0 0))",
+            "MULTIPOLYGON (((2 2, 3 2, 3 3, 2 2)), ((4 4, 5 4, 5 5, 4 4)))",
+        ],
+    );
+    let df = DataFrame::new(vec![geometry]).unwrap();
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let write = |policy| -> Result<(gdal::vector::OGRwkbGeometryType::Type, u64), Error> {
+        let mut dataset = driver.create_vector_only("")?;
+        let mut params = WriteParams::default();
+        params.geometry_format = GeometryFormat::WKT;
+        params.geometry_type_policy = policy;
+        let layer = gdal_layer_from_df(&df, &mut dataset, Some(params))?;
+        let layer_type = unsafe { gdal_sys::OGR_L_GetGeomType(layer.c_layer()) };
+        Ok((layer_type, layer.feature_count()))
+    };
+
+    use gdal::vector::OGRwkbGeometryType::{wkbMultiPolygon, wkbPolygon, wkbUnknown};
+    assert_eq!(write(GeometryTypePolicy::PromoteToMulti).unwrap(), (wkbMultiPolygon, 2));
+    assert_eq!(write(GeometryTypePolicy::ConvertToGeneric).unwrap(), (wkbUnknown, 2));
+    assert_eq!(write(GeometryTypePolicy::SkipMismatched).unwrap(), (wkbPolygon, 1));
+    assert!(matches!(
+        write(GeometryTypePolicy::Error),
+        Err(Error::SchemaMismatch(_))
+    ));
+}
+
+#[test]
+fn test_force_2d_and_multi() {
+    let geojson = r#"{"type":"FeatureCollection","features":[
+        {"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[1,2,3]}},
+        {"type":"Feature","properties":{},"geometry":{"type":"MultiPoint","coordinates":[[4,5,6],[7,8,9]]}}
+    ]}"#
+    .as_bytes();
+
+    let mut params = ReadParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.force_2d = true;
+    params.force_multi = true;
+    let df = df_from_bytes(geojson, None, Some(params)).unwrap();
+    for geometry in df.column("geometry").unwrap().utf8().unwrap() {
+        let geometry = geometry.unwrap();
+        assert!(geometry.starts_with("MULTIPOINT ("), "{}", geometry);
+        assert!(!geometry.contains(['3', '6']), "{}", geometry);
+    }
+
+    let geometry = Series::new("geometry", &["POINT Z (1 2 3)", "POINT Z (4 5 6)"]);
+    let df = DataFrame::new(vec![geometry]).unwrap();
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.force_2d = true;
+    params.force_multi = true;
+    let mut layer = gdal_layer_from_df(&df, &mut dataset, Some(params)).unwrap();
+    let layer_type = unsafe { gdal_sys::OGR_L_GetGeomType(layer.c_layer()) };
+    assert_eq!(layer_type, gdal::vector::OGRwkbGeometryType::wkbMultiPoint);
+    for feature in layer.features() {
+        assert_eq!(
+            feature.geometry().geometry_type(),
+            gdal::vector::OGRwkbGeometryType::wkbMultiPoint
+        );
+    }
+}
+
+#[test]
+fn test_z_dimension() {
+    let geometry = Series::new("geometry", &["POINT (1 2)", "POINT Z (3 4 5)"]);
+    let df = DataFrame::new(vec![geometry]).unwrap();
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    let mut layer = gdal_layer_from_df(&df, &mut dataset, Some(params)).unwrap();
+    let layer_type = unsafe { gdal_sys::OGR_L_GetGeomType(layer.c_layer()) };
+    assert_eq!(layer_type, gdal::vector::OGRwkbGeometryType::wkbPoint25D);
+
+    let info = describe::describe_layer(&mut layer, true).unwrap();
+    assert!(info.has_z);
+    assert!(!info.has_m);
+    assert_eq!(layer.feature_count(), 2);
+}
+
+#[test]
+fn test_malformed_wkb_error() {
+    let point = gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap().wkb().unwrap();
+    let malformed = vec![1u8, 1, 0, 0, 0, 0xff];
+    let mut geometry = [Some(point), Some(malformed)].into_iter().collect::<BinaryChunked>().into_series();
+    geometry.rename("geometry");
+    let df = DataFrame::new(vec![geometry]).unwrap();
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+
+    match gdal_layer_from_df(&df, &mut dataset, None) {
+        Err(Error::MalformedGeometry {
+            row,
+            column,
+            preview,
+            ..
+        }) => {
+            assert_eq!(row, 1);
+            assert_eq!(column, "geometry");
+            assert_eq!(preview, "0101000000ff");
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("malformed WKB was written"),
+    }
+}
+
+#[test]
+fn test_process_unexpected_field_value() {
+    let series = UnprocessedSeries {
+        name: "population".to_owned(),
+        datatype: UnprocessedDataType::Integer,
+        nullable: true,
+        data: vec![
+            GdalData::Value(Some(GdalValue::IntegerValue(1))),
+            GdalData::Value(None),
+            GdalData::Value(Some(GdalValue::StringValue("many".to_owned()))),
+        ],
+    };
+
+    match series.process() {
+        Err(Error::UnexpectedFieldValue {
+            column,
+            feature_index,
+            value,
+        }) => {
+            assert_eq!(column, "population");
+            assert_eq!(feature_index, 2);
+            assert!(value.contains("many"), "{}", value);
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("mixed values were processed"),
+    }
+}
+
+#[test]
+fn test_mixed_type_policy() {
+    let mixed = |data: Vec<GdalValue>| UnprocessedSeries {
+        name: "value".to_owned(),
+        datatype: UnprocessedDataType::Integer,
+        nullable: true,
+        data: data.into_iter().map(|v| GdalData::Value(Some(v))).collect(),
+    };
+
+    let mut numbers = mixed(vec![GdalValue::IntegerValue(1), GdalValue::RealValue(2.5)]);
+    numbers.coerce_mixed_types(MixedTypePolicy::Coerce);
+    let numbers = numbers.process().unwrap();
+    assert_eq!(numbers.dtype(), &DataType::Float64);
+    assert_eq!(numbers.f64().unwrap().get(0), Some(1.0));
+
+    let mut strings = mixed(vec![
+        GdalValue::IntegerValue(1),
+        GdalValue::StringValue("many".to_owned()),
+    ]);
+    strings.coerce_mixed_types(MixedTypePolicy::Coerce);
+    let strings = strings.process().unwrap();
+    assert_eq!(strings.utf8().unwrap().get(0), Some("1"));
+    assert_eq!(strings.utf8().unwrap().get(1), Some("many"));
+
+    let mut stringified = mixed(vec![
+        GdalValue::IntegerValue(1),
+        GdalValue::Integer64Value(2),
+    ]);
+    stringified.coerce_mixed_types(MixedTypePolicy::Stringify);
+    assert_eq!(stringified.process().unwrap().dtype(), &DataType::Utf8);
+
+    let mut errored = mixed(vec![GdalValue::IntegerValue(1), GdalValue::RealValue(2.5)]);
+    errored.coerce_mixed_types(MixedTypePolicy::Error);
+    assert!(matches!(
+        errored.process(),
+        Err(Error::UnexpectedFieldValue {
+            feature_index: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_write_report() {
+    let geometry = Series::new(
+        "geometry",
+        &[Some("POINT (1 2)"), None, Some("POINT (3 4)")],
+    );
+    let name = Series::new("name", &["a", "b", "c"]);
+    let flags = Series::new(
+        "flags",
+        &[
+            Series::new("", &[true]),
+            Series::new("", &[false]),
+            Series::new("", &[true, false]),
+        ],
+    );
+    let df = DataFrame::new(vec![name, flags, geometry]).unwrap();
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.null_geometry_policy = NullGeometryPolicy::Skip;
+
+    let (_, report) = gdal_layer_from_df_with_report(&df, &mut dataset, Some(params)).unwrap();
+    assert_eq!(report.rows_written, 2);
+    assert_eq!(
+        report.skipped_columns,
+        vec![SkippedColumn {
+            name: "flags".to_owned(),
+            reason: SkipReason::UnsupportedDtype(DataType::List(Box::new(DataType::Boolean))),
+        }]
+    );
+    assert_eq!(
+        report.warnings,
+        vec![WriteWarning::NullGeometrySkipped { row: 1 }]
+    );
+    assert!(report.field_renames.is_empty());
+    assert!(!report.is_lossless());
+}
+
+#[test]
+fn test_strict_write() {
+    let geometry = Series::new("geometry", &["POINT (1 2)", "POINT (3 4)"]);
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let write = |column: Series| -> Result<(), Error> {
+        let df = DataFrame::new(vec![column, geometry.clone()]).unwrap();
+        let mut dataset = driver.create_vector_only("")?;
+        let mut params = WriteParams::default();
+        params.geometry_format = GeometryFormat::WKT;
+        params.strict = true;
+        gdal_layer_from_df(&df, &mut dataset, Some(params))?;
+        Ok(())
+    };
+
+    write(Series::new("count", &[1u64, 2])).unwrap();
+    assert!(matches!(
+        write(Series::new("count", &[1u64, u64::MAX])),
+        Err(Error::StrictWrite(_))
+    ));
+    assert!(matches!(
+        write(Series::new(
+            "flags",
+            &[Series::new("", &[true]), Series::new("", &[false])]
+        )),
+        Err(Error::StrictWrite(_))
+    ));
+}
+
+#[test]
+fn test_plan_write() {
+    let df = df!(
+        "geometry" => &["POINT (1 2)", "MULTIPOINT (3 4, 5 6)"],
+        "a_very_long_name" => &[1i64, 2],
+        "flag" => &[true, false],
+        "flags" => &[Series::new("", &[true]), Series::new("", &[false])],
+    )
+    .unwrap();
+    let params = |strict| WriteParams {
+        geometry_format: GeometryFormat::WKT,
+        geometry_type_policy: GeometryTypePolicy::PromoteToMulti,
+        strict,
+        ..Default::default()
+    };
+
+    let plan = plan_write(&df, &VectorDriver::Shapefile, Some(params(false))).unwrap();
+    assert_eq!(plan.driver, "ESRI Shapefile");
+    assert_eq!(
+        plan.geometry_type,
+        gdal::vector::OGRwkbGeometryType::wkbMultiPoint
+    );
+    assert_eq!(plan.fields.len(), 2);
+    assert_eq!(plan.fields[0].column, "a_very_long_name");
+    assert_eq!(plan.fields[0].name, "a_very_lon");
+    assert_eq!(plan.fields[0].field_type, OGRFieldType::OFTInteger64);
+    assert_eq!(
+        plan.fields[1].field_subtype,
+        gdal_sys::OGRFieldSubType::OFSTBoolean
+    );
+    assert_eq!(plan.field_renames.len(), 1);
+    assert_eq!(plan.skipped_columns.len(), 1);
+    assert_eq!(plan.skipped_columns[0].name, "flags");
+
+    assert!(matches!(
+        plan_write(&df, &VectorDriver::Shapefile, Some(params(true))),
+        Err(Error::StrictWrite(_))
+    ));
+}
+
+#[test]
+fn test_read_report() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let (df, report) = df_from_bytes_with_report(geojson.as_bytes(), None, None).unwrap();
+    assert_eq!(df.height(), 1);
+    assert!(report.warnings.is_empty());
+
+    let message = std::ffi::CString::new("ring orientation").unwrap();
+    let ((), messages) = capture_messages(|| unsafe {
+        gdal_sys::CPLError(gdal_sys::CPLErr::CE_Warning, 1, message.as_ptr());
+    });
+    assert_eq!(
+        messages,
+        vec![GdalMessage {
+            level: GdalMessageLevel::Warning,
+            error_number: 1,
+            message: "ring orientation".to_owned(),
+        }]
+    );
+
+    // Errors are still reported as errors
+    assert!(df_from_bytes_with_report(b"not a dataset", None, None).is_err());
+}
+
+#[test]
+fn test_log_scope_nesting() {
+    let message = std::ffi::CString::new("bad ring").unwrap();
+    let warn = || unsafe {
+        gdal_sys::CPLError(gdal_sys::CPLErr::CE_Warning, 1, message.as_ptr());
+    };
+
+    // Messages are captured by the innermost capture, whether or not they are also logged
+    let _log_scope = log_scope();
+    let ((((), inner), ()), outer) = capture_messages(|| {
+        let inner = capture_messages(warn);
+        let _log_scope = log_scope();
+        (inner, warn())
+    });
+    assert_eq!(inner.len(), 1);
+    assert_eq!(outer.len(), 1);
+}
+
+#[test]
+fn test_progress() {
+    let df = df!(
+        "geometry" => &["POINT (1 2)", "POINT (3 4)", "POINT (5 6)"],
+        "name" => &["a", "b", "c"],
+    )
+    .unwrap();
+
+    let calls = std::sync::Mutex::new(vec![]);
+    let record =
+        |processed: u64, total: Option<u64>| calls.lock().unwrap().push((processed, total));
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.progress = Some(Progress(&record));
+    let driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = gdal_bytes_from_df(&df, &driver, Some(params)).unwrap();
+    assert_eq!(
+        calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![(3, Some(3))]
+    );
+
+    let mut params = ReadParams::default();
+    params.progress = Some(Progress(&record));
+    df_from_bytes(&bytes, None, Some(params)).unwrap();
+    assert_eq!(*calls.lock().unwrap(), vec![(3, Some(3))]);
+}
+
+#[test]
+fn test_cancel() {
+    let df = df!(
+        "geometry" => &["POINT (1 2)", "POINT (3 4)"],
+        "name" => &["a", "b"],
+    )
+    .unwrap();
+    let driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    let bytes = gdal_bytes_from_df(&df, &driver, Some(params)).unwrap();
+
+    let cancel = std::sync::atomic::AtomicBool::new(true);
+    let mut params = ReadParams::default();
+    params.cancel = Some(&cancel);
+    assert!(matches!(
+        df_from_bytes(&bytes, None, Some(params)),
+        Err(Error::Cancelled)
+    ));
+
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.cancel = Some(&cancel);
+    assert!(matches!(
+        gdal_bytes_from_df(&df, &driver, Some(params)),
+        Err(Error::Cancelled)
+    ));
+}
+
+#[test]
+fn test_owned_params() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"name":"bar"},"geometry":{"type":"Point","coordinates":[3,4]}}]}"#;
+
+    let columns = ["name"];
+    let mut params = ReadParams::default();
+    params.columns = Some(&columns);
+    params.truncating_limit = Some(1);
+    let owned = ReadParamsOwned::try_from(&params).unwrap();
+
+    let df = std::thread::spawn(move || {
+        owned.with_params(|params| df_from_bytes(geojson.as_bytes(), None, Some(params)))
+    })
+    .join()
+    .unwrap()
+    .unwrap();
+    assert_eq!(df.get_column_names(), &["name", "geometry"]);
+    assert_eq!(df.height(), 1);
+
+    let mut owned = WriteParamsOwned::default();
+    owned.geometry_format = GeometryFormat::WKB;
+    owned.layer_name = Some("points".to_string());
+    let driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = owned
+        .with_params(|params| gdal_bytes_from_df(&df, &driver, Some(params)))
+        .unwrap();
+    assert!(!bytes.is_empty());
+}
+
+#[test]
+fn test_read_params_builder() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"name":"bar"},"geometry":{"type":"Point","coordinates":[3,4]}}]}"#;
+
+    let params = ReadParams::builder()
+        .bbox([2.0, 3.0, 5.0, 5.0])
+        .columns(&["name"])
+        .limit(10)
+        .build()
+        .unwrap();
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    assert_eq!(df.height(), 1);
+    assert_eq!(
+        df.column("name").unwrap().utf8().unwrap().get(0),
+        Some("bar")
+    );
+
+    assert!(matches!(
+        ReadParams::builder().limit(10).erroring_limit(20).build(),
+        Err(Error::ConflictingParams(_))
+    ));
+}
+
+#[test]
+fn test_write_params_builder() {
+    use gdal::vector::OGRwkbGeometryType;
+
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"LineString","coordinates":[[1,2],[3,4]]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+
+    let params = WriteParams::builder()
+        .layer_name("roads")
+        .srs_epsg(4326)
+        .geometry_type(OGRwkbGeometryType::wkbLineString)
+        .option("SPATIAL_INDEX", "YES")
+        .build()
+        .unwrap();
+    assert_eq!(params.options, Some(vec!["SPATIAL_INDEX=YES".to_owned()]));
+
+    let driver = gdal::DriverManager::get_driver_by_name("GPKG").unwrap();
+    let bytes = params
+        .with_params(|params| gdal_bytes_from_df(&df, &driver, Some(params)))
+        .unwrap();
+    let read_params = ReadParams {
+        layer_name: Some("roads"),
+        ..Default::default()
+    };
+    let df = df_from_bytes(&bytes, None, Some(read_params)).unwrap();
+    assert_eq!(df.height(), 1);
+
+    assert!(matches!(
+        WriteParams::builder()
+            .option("SPATIAL_INDEX", "YES")
+            .option("SPATIAL_INDEX", "NO")
+            .build(),
+        Err(Error::ConflictingParams(_))
+    ));
+    assert!(matches!(
+        WriteParams::builder()
+            .geometry_type(OGRwkbGeometryType::wkbLineString)
+            .force_multi()
+            .build(),
+        Err(Error::ConflictingParams(_))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_params_serde() {
+    let params: ReadParamsOwned = serde_json::from_str(
+        r#"{"open_flags": 4, "layer_name": "roads", "geometry_format": "WKT", "columns": ["name"]}"#,
+    )
+    .unwrap();
+    assert_eq!(params.open_flags, gdal::GdalOpenFlags::GDAL_OF_VECTOR);
+    assert_eq!(params.layer_name.as_deref(), Some("roads"));
+    assert!(matches!(params.geometry_format, GeometryFormat::WKT));
+    assert_eq!(params.columns, Some(vec!["name".to_owned()]));
+    assert_eq!(params.offset, None);
+
+    let params = WriteParams::builder()
+        .layer_name("roads")
+        .srs_epsg(4326)
+        .option("SPATIAL_INDEX", "YES")
+        .build()
+        .unwrap();
+    let json = serde_json::to_string(&params).unwrap();
+    let deserialized: WriteParamsOwned = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.layer_name, params.layer_name);
+    assert_eq!(deserialized.srs, params.srs);
+    assert_eq!(deserialized.options, params.options);
+
+    assert!(serde_json::from_str::<ReadParamsOwned>(r#"{"open_flags": 4096}"#).is_err());
+}
+
+#[test]
+fn test_driver_options() {
+    let csv_options = CsvOpenOptions {
+        x_possible_names: vec!["Lon*".to_owned()],
+        y_possible_names: vec!["Lat*".to_owned()],
+        keep_geom_columns: Some(false),
+        ..Default::default()
+    };
+    assert_eq!(
+        csv_options.to_options(),
+        vec![
+            "X_POSSIBLE_NAMES=Lon*",
+            "Y_POSSIBLE_NAMES=Lat*",
+            "KEEP_GEOM_COLUMNS=NO"
+        ]
+    );
+    let params = ReadParamsOwned {
+        open_options: Some(csv_options.to_options()),
+        ..Default::default()
+    };
+    let df = params
+        .with_params(|params| df_from_resource("test_data/lat_lon_countries.csv", Some(params)))
+        .unwrap();
+    assert!(df.column("geometry").is_ok());
+    assert!(df.column("Latitude").is_err());
+
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1.23456,2.34567]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+    let params = WriteParams::builder()
+        .creation_options(&GeoJsonCreationOptions {
+            coordinate_precision: Some(2),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    assert_eq!(
+        params.options,
+        Some(vec!["COORDINATE_PRECISION=2".to_owned()])
+    );
+    let driver = gdal::DriverManager::get_driver_by_name("GeoJSON").unwrap();
+    let bytes = params
+        .with_params(|params| gdal_bytes_from_df(&df, &driver, Some(params)))
+        .unwrap();
+    assert!(String::from_utf8(bytes).unwrap().contains("[ 1.23, 2.35 ]"));
+}
+
+#[test]
+fn test_gdal_resource_from_df_auto() {
+    assert_eq!(
+        VectorDriver::from_extension("GPKG"),
+        Some(VectorDriver::Gpkg)
+    );
+    assert_eq!(
+        VectorDriver::from_path("/data/roads.shp"),
+        Some(VectorDriver::Shapefile)
+    );
+    assert_eq!(VectorDriver::from_path("/data/roads"), None);
+
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+
+    let dataset =
+        gdal_resource_from_df_auto(&df, "/vsimem/polars_gdal/test_auto/roads.fgb", None).unwrap();
+    assert_eq!(dataset.driver().short_name(), "FlatGeobuf");
+
+    assert!(matches!(
+        gdal_resource_from_df_auto(&df, "/vsimem/polars_gdal/test_auto/roads.xyz", None),
+        Err(Error::UnknownExtension(_))
+    ));
+}
+
+#[test]
+fn test_identify_bytes() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let driver = identify_bytes(geojson.as_bytes(), None).unwrap();
+    assert_eq!(driver.name, "GeoJSON");
+    assert!(driver.extensions.contains(&"geojson".to_owned()));
+
+    let csv = "name,population\nfoo,100\n";
+    let driver = identify_bytes(csv.as_bytes(), Some("cities.csv")).unwrap();
+    assert_eq!(driver.name, "CSV");
+
+    assert!(matches!(
+        identify_bytes(b"not a vector file", Some("upload.bin")),
+        Err(Error::UnrecognizedFormat)
+    ));
+}
+
+#[test]
+fn test_list_drivers() {
+    let drivers = list_drivers();
+    let gpkg = drivers.iter().find(|d| d.name == "GPKG").unwrap();
+    assert!(gpkg.create);
+    assert!(gpkg.virtual_io);
+    assert!(gpkg.extensions.contains(&"gpkg".to_owned()));
+    assert!(gpkg
+        .field_types
+        .as_ref()
+        .unwrap()
+        .contains(&"Integer64".to_owned()));
+
+    // Raster-only drivers aren't listed
+    assert!(!drivers.iter().any(|d| d.name == "GTiff"));
+}
+
+#[test]
+fn test_gdal_files_from_df() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+
+    let params = WriteParams {
+        layer_name: Some("roads"),
+        ..Default::default()
+    };
+    let files = gdal_files_from_df(&df, &VectorDriver::Shapefile, Some(params)).unwrap();
+    for file_name in ["roads.shp", "roads.shx", "roads.dbf"] {
+        assert!(files.contains_key(file_name), "missing {}", file_name);
+    }
+
+    // The first part is the one opened
+    let mut parts: Vec<(&str, &[u8])> = files
+        .iter()
+        .map(|(name, bytes)| (name.as_str(), bytes.as_slice()))
+        .collect();
+    parts.sort_by_key(|(name, _)| !name.ends_with(".shp"));
+    let df = df_from_byte_parts(&parts, None).unwrap();
+    assert_eq!(df.height(), 1);
+
+    assert!(matches!(
+        gdal_bytes_from_df(&df, &VectorDriver::Shapefile, None),
+        Err(Error::MultipleFiles(_))
+    ));
+}
+
+#[test]
+fn test_compressed_output() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+
+    let params = WriteParams {
+        compression: OutputCompression::Gzip,
+        ..Default::default()
+    };
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)).unwrap();
+    assert_eq!(&bytes[..2], &[0x1f, 0x8b]);
+    let path = "/vsimem/polars_gdal/test_compressed_output/layer.geojson.gz";
+    gdal::vsi::create_mem_file(path, bytes).unwrap();
+    let read = df_from_resource(format!("/vsigzip/{}", path), None).unwrap();
+    assert_eq!(read.height(), 1);
+    gdal::vsi::unlink_mem_file(path).unwrap();
+
+    let params = WriteParams {
+        layer_name: Some("roads"),
+        compression: OutputCompression::Zip,
+        ..Default::default()
+    };
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::Shapefile, Some(params)).unwrap();
+    assert_eq!(&bytes[..2], b"PK");
+    let path = "/vsimem/polars_gdal/test_compressed_output/roads.zip";
+    gdal::vsi::create_mem_file(path, bytes).unwrap();
+    let members = list_archive_members(path).unwrap();
+    assert!(members.contains(&"roads.shp".to_owned()));
+    let read = df_from_resource(format!("{}!roads.shp", path), None).unwrap();
+    assert_eq!(read.height(), 1);
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
+
+#[test]
+fn test_zip_archives() {
+    assert_eq!(
+        resolve_archive_path("test_data/stations_shapefile.shp.zip").unwrap(),
+        "/vsizip/{test_data/stations_shapefile.shp.zip}/stations.shp"
+    );
+    let df = df_from_resource("test_data/stations_shapefile.shp.zip", None).unwrap();
+    let zip = std::fs::read("test_data/stations_shapefile.shp.zip").unwrap();
+    assert_eq!(
+        df_from_zip_bytes(&zip, None, None).unwrap().height(),
+        df.height()
+    );
+
+    let path = "/vsimem/polars_gdal/test_zip_archives/roads.zip";
+    gdal_zip_from_df(
+        &df,
+        &VectorDriver::GeoJson,
+        &format!("{}!roads.geojson", path),
+        None,
+    )
+    .unwrap();
+    assert_eq!(list_archive_members(path).unwrap(), vec!["roads.geojson"]);
+    assert_eq!(df_from_resource(path, None).unwrap().height(), df.height());
+
+    gdal_zip_from_df(&df, &VectorDriver::Shapefile, path, None).unwrap();
+    assert!(list_archive_members(path)
+        .unwrap()
+        .contains(&"layer.shp".to_owned()));
+    assert_eq!(df_from_resource(path, None).unwrap().height(), df.height());
+
+    compression::write_vsi_file(&format!("/vsizip/{{{}}}/extra.geojson", path), b"{}").unwrap();
+    assert!(matches!(
+        df_from_resource(path, None),
+        Err(Error::AmbiguousArchive { .. })
+    ));
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
+
+#[test]
+fn test_config_options() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"tags":["a","b"]},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+    assert!(matches!(
+        df.column("tags").unwrap().dtype(),
+        DataType::List(_)
+    ));
+
+    let params = ReadParams {
+        config_options: Some(&[("OGR_GEOJSON_ARRAY_AS_STRING", "YES")]),
+        ..Default::default()
+    };
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    assert_eq!(df.column("tags").unwrap().dtype(), &DataType::Utf8);
+
+    // The option is restored once the read is done
+    let c_name = std::ffi::CString::new("OGR_GEOJSON_ARRAY_AS_STRING").unwrap();
+    let c_value = unsafe { gdal_sys::CPLGetConfigOption(c_name.as_ptr(), std::ptr::null()) };
+    assert!(c_value.is_null());
+}
+
+#[test]
+fn test_init() {
+    init(GdalConfig {
+        curl_cache_size: Some(32 * 1024 * 1024),
+        options: vec![("POLARS_GDAL_TEST_INIT".to_owned(), "YES".to_owned())],
+        ..Default::default()
+    })
+    .unwrap();
+
+    let config_option = |name: &str| {
+        let c_name = std::ffi::CString::new(name).unwrap();
+        _string(unsafe { gdal_sys::CPLGetConfigOption(c_name.as_ptr(), std::ptr::null()) })
+    };
+    assert_eq!(config_option("CPL_VSIL_CURL_CACHE_SIZE"), "33554432");
+    assert_eq!(config_option("POLARS_GDAL_TEST_INIT"), "YES");
+    assert!(VectorDriver::GeoJson.available());
+}
+
+#[test]
+fn test_http_options() {
+    let http_options = HttpOptions {
+        max_retry: Some(3),
+        retry_delay: Some(std::time::Duration::from_millis(500)),
+        timeout: Some(std::time::Duration::from_secs(30)),
+        headers: vec![("Accept".to_owned(), "application/geo+json, */*".to_owned())],
+        bearer_token: Some("secret".to_owned()),
+        ..Default::default()
+    };
+    assert_eq!(
+        http_options.config_pairs(),
+        vec![
+            ("GDAL_HTTP_MAX_RETRY", "3".to_owned()),
+            ("GDAL_HTTP_RETRY_DELAY", "0.5".to_owned()),
+            ("GDAL_HTTP_TIMEOUT", "30".to_owned()),
+            (
+                "GDAL_HTTP_HEADERS",
+                "Accept: application/geo+json, */*\r\nAuthorization: Bearer secret\r\n".to_owned()
+            ),
+        ]
+    );
+
+    // Explicit config options take precedence, and both are restored once the read is done
+    let params = ReadParams {
+        http_options: Some(&http_options),
+        config_options: Some(&[("GDAL_HTTP_MAX_RETRY", "5")]),
+        ..Default::default()
+    };
+    let config_option = |name: &str| {
+        let c_name = std::ffi::CString::new(name).unwrap();
+        let c_value = unsafe { gdal_sys::CPLGetConfigOption(c_name.as_ptr(), std::ptr::null()) };
+        (!c_value.is_null()).then(|| _string(c_value))
+    };
+    {
+        let _config_scope = ConfigScope::for_read(&params).unwrap();
+        assert_eq!(config_option("GDAL_HTTP_MAX_RETRY").as_deref(), Some("5"));
+        assert_eq!(config_option("GDAL_HTTP_TIMEOUT").as_deref(), Some("30"));
+    }
+    assert_eq!(config_option("GDAL_HTTP_MAX_RETRY"), None);
+    assert_eq!(config_option("GDAL_HTTP_HEADERS"), None);
+
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"a"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    assert_eq!(df.height(), 1);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_async() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"a"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes_async(geojson.as_bytes().to_vec(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(df.height(), 1);
+
+    let path = "/vsimem/test_async.gpkg";
+    gdal_resource_from_df_async(df.clone(), VectorDriver::Gpkg, path, None)
+        .await
+        .unwrap();
+
+    let mut params = ReadParamsOwned::default();
+    params.columns = Some(vec!["name".to_owned()]);
+    let read = df_from_resource_async(path, Some(params)).await.unwrap();
+    assert_eq!(read.get_column_names(), &["name", "geometry"]);
+    assert!(read
+        .column("name")
+        .unwrap()
+        .series_equal(df.column("name").unwrap()));
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
+
+#[test]
+fn test_df_from_reader() {
+    let path = "test_data/us_states.feature_collection.implicit_4326.json";
+    let file = std::fs::File::open(path).unwrap();
+    let df = df_from_reader(file, Some("us_states.geojson"), None).unwrap();
+    assert!(df.frame_equal_missing(&df_from_resource(path, None).unwrap()));
+
+    // The limit is enforced while the stream is read
+    let file = std::fs::File::open(path).unwrap();
+    let params = ReadParams {
+        max_input_bytes: Some(1024),
+        ..Default::default()
+    };
+    assert!(matches!(
+        df_from_reader(file, None, Some(params)),
+        Err(Error::InputTooLarge(_, 1024))
+    ));
+
+    assert!(matches!(
+        df_from_reader(std::io::empty(), None, None),
+        Err(Error::EmptyData)
+    ));
+}
+
+#[test]
+fn test_layer_metadata() {
+    let path = "test_data/qgis_styled.gpkg";
+    let params = ReadParams {
+        layer_name: Some("parks"),
+        ..Default::default()
+    };
+    let metadata = layer_metadata_from_resource(path, Some(params.clone())).unwrap();
+    assert_eq!(
+        metadata.display_expression.as_deref(),
+        Some(r#""name" || ' (' || "kind" || ')'"#)
+    );
+    assert_eq!(metadata.hidden_fields, ["secret"]);
+    assert_eq!(metadata.identify_excluded_fields, ["internal_id"]);
+
+    // The hints survive a round trip through a DataFrame, written to the style QGIS loads
+    let df = df_from_resource(path, Some(params)).unwrap();
+    let out_path = "/vsimem/polars_gdal/test_layer_metadata.gpkg";
+    let mut write_params = WriteParams::default();
+    write_params.layer_metadata = Some(&metadata);
+    let dataset =
+        gdal_resource_from_df(&df, &VectorDriver::Gpkg, out_path, Some(write_params)).unwrap();
+    let layer_name = dataset.layer(0).unwrap().name();
+    drop(dataset);
+
+    let params = ReadParams {
+        layer_name: Some(&layer_name),
+        ..Default::default()
+    };
+    let roundtrip = layer_metadata_from_resource(out_path, Some(params)).unwrap();
+    assert_eq!(roundtrip.display_expression, metadata.display_expression);
+    assert_eq!(roundtrip.hidden_fields, metadata.hidden_fields);
+    assert_eq!(
+        roundtrip.identify_excluded_fields,
+        metadata.identify_excluded_fields
+    );
+
+    gdal::vsi::unlink_mem_file(out_path).unwrap();
+}
+
+#[test]
+fn test_gpkg_attachments() {
+    let attachments =
+        attachments_from_resource("test_data/attachments.gpkg", "poles", None).unwrap();
+    let fids: Vec<Option<u64>> = attachments
+        .column("fid")
+        .unwrap()
+        .u64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(fids, vec![Some(1), Some(2), None]);
+    let names: Vec<Option<&str>> = attachments
+        .column("name")
+        .unwrap()
+        .utf8()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(names, vec![Some("front.jpg"), None, Some("orphan.txt")]);
+    let data: Vec<Option<&[u8]>> = attachments
+        .column("data")
+        .unwrap()
+        .binary()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(data[0], Some(&b"front"[..]));
+
+    let dir = std::env::temp_dir().join(format!(
+        "polars_gdal_test_gpkg_attachments_{}",
+        std::process::id()
+    ));
+    let paths = write_attachments_to_dir(&attachments, &dir).unwrap();
+    let file_names: Vec<String> = paths
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(file_names, vec!["1_front.jpg", "2_1", "_orphan.txt"]);
+    assert_eq!(std::fs::read(&paths[1]).unwrap(), b"side");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_filegdb_attachments() {
+    // Writing File Geodatabases requires GDAL >= 3.6
+    let supported = list_drivers()
+        .iter()
+        .any(|driver| driver.name == "OpenFileGDB" && driver.create);
+    if !supported {
+        return;
+    }
+
+    let path = std::env::temp_dir().join(format!(
+        "polars_gdal_test_filegdb_attachments_{}.gdb",
+        std::process::id()
+    ));
+    let driver = gdal::DriverManager::get_driver_by_name("OpenFileGDB").unwrap();
+    let mut dataset = driver.create_vector_only(path.to_str().unwrap()).unwrap();
+    let table = dataset
+        .create_layer(gdal::LayerOptions {
+            name: "poles__ATTACH",
+            ty: gdal::vector::OGRwkbGeometryType::wkbNone,
+            ..Default::default()
+        })
+        .unwrap();
+    for (name, field_type) in [
+        ("REL_OBJECTID", OGRFieldType::OFTInteger),
+        ("CONTENT_TYPE", OGRFieldType::OFTString),
+        ("ATT_NAME", OGRFieldType::OFTString),
+        ("DATA", OGRFieldType::OFTBinary),
+    ] {
+        layer::create_field(
+            &table,
+            name,
+            field_type,
+            gdal_sys::OGRFieldSubType::OFSTNone,
+            None,
+        )
+        .unwrap();
+    }
+    for (fid, name, data) in [
+        (1, "front.jpg", &b"front"[..]),
+        (-1, "orphan.txt", &b"orphan"[..]),
+    ] {
+        let feature = gdal::vector::Feature::new(table.defn()).unwrap();
+        feature
+            .set_field("REL_OBJECTID", &GdalValue::IntegerValue(fid))
+            .unwrap();
+        feature
+            .set_field_string("CONTENT_TYPE", "application/octet-stream")
+            .unwrap();
+        feature.set_field_string("ATT_NAME", name).unwrap();
+        layer::set_field_binary(&feature, "DATA", data).unwrap();
+        feature.create(&table).unwrap();
+    }
+    drop(dataset);
+
+    let attachments = attachments_from_resource(&path, "poles", None).unwrap();
+    let fids: Vec<Option<u64>> = attachments
+        .column("fid")
+        .unwrap()
+        .u64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(fids, vec![Some(1), None]);
+    let names: Vec<Option<&str>> = attachments
+        .column("name")
+        .unwrap()
+        .utf8()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(names, vec![Some("front.jpg"), Some("orphan.txt")]);
+    let data: Vec<Option<&[u8]>> = attachments
+        .column("data")
+        .unwrap()
+        .binary()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(data[1], Some(&b"orphan"[..]));
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn test_max_input_bytes() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"a"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let params = ReadParams {
+        max_input_bytes: Some(16),
+        ..Default::default()
+    };
+    assert!(matches!(
+        df_from_bytes(geojson.as_bytes(), None, Some(params.clone())),
+        Err(Error::InputTooLarge(len, 16)) if len == geojson.len()
+    ));
+    assert!(matches!(
+        df_from_byte_parts(&[("layer.geojson", geojson.as_bytes())], Some(params)),
+        Err(Error::InputTooLarge(_, 16))
+    ));
+}
+
+#[test]
+fn test_max_columns() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"a":1,"b":2,"c":3},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let params = ReadParams {
+        max_columns: Some(2),
+        ..Default::default()
+    };
+    assert!(matches!(
+        df_from_bytes(geojson.as_bytes(), None, Some(params)),
+        Err(Error::ColumnLimitReached(2))
+    ));
+
+    // Only the columns read count towards the limit
+    let params = ReadParams {
+        max_columns: Some(2),
+        columns: Some(&["a", "b"]),
+        ..Default::default()
+    };
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    assert!(df.column("b").is_ok());
+    assert!(df.column("c").is_err());
+}
+
+#[test]
+fn test_max_string_length() {
+    let params = ReadParams {
+        max_string_length: Some(3),
+        ..Default::default()
+    };
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"abcdef"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    assert!(matches!(
+        df_from_bytes(geojson.as_bytes(), None, Some(params.clone())),
+        Err(Error::StringLengthLimitReached(name, 3)) if name == "name"
+    ));
+
+    // Every element of string lists is checked
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"tags":["a","abcdef"]},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    assert!(matches!(
+        df_from_bytes(geojson.as_bytes(), None, Some(params.clone())),
+        Err(Error::StringLengthLimitReached(name, 3)) if name == "tags"
+    ));
+
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"abc","tags":["a","b"]},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    assert_eq!(
+        df_from_bytes(geojson.as_bytes(), None, Some(params))
+            .unwrap()
+            .height(),
+        1
+    );
+}
+
+#[test]
+fn test_max_geometry_vertices() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{},"geometry":{"type":"LineString","coordinates":[[0,0],[1,1],[2,2],[3,3]]}}]}"#;
+    let params = ReadParams {
+        max_geometry_vertices: Some(3),
+        ..Default::default()
+    };
+    assert!(matches!(
+        df_from_bytes(geojson.as_bytes(), None, Some(params)),
+        Err(Error::VertexLimitReached(1, 3))
+    ));
+}
+
+#[test]
+fn test_projected_read_restores_ignored_fields() {
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut layer = dataset
+        .create_layer(gdal::LayerOptions {
+            name: "points",
+            ty: gdal::vector::OGRwkbGeometryType::wkbPoint,
+            ..Default::default()
+        })
+        .unwrap();
+    for name in ["a", "b", "c"] {
+        layer::create_field(
+            &layer,
+            name,
+            OGRFieldType::OFTInteger,
+            gdal_sys::OGRFieldSubType::OFSTNone,
+            None,
+        )
+        .unwrap();
+    }
+    let feature = gdal::vector::Feature::new(layer.defn()).unwrap();
+    feature
+        .set_geometry(gdal::vector::Geometry::from_wkt("POINT (1 2)").unwrap())
+        .unwrap();
+    for (name, value) in [("a", 1), ("b", 2), ("c", 3)] {
+        feature
+            .set_field(name, &GdalValue::IntegerValue(value))
+            .unwrap();
+    }
+    feature.create(&layer).unwrap();
+
+    // The caller ignores `c`
+    let c_ignored = std::ffi::CString::new("c").unwrap();
+    let mut c_ptrs = [c_ignored.as_ptr(), std::ptr::null()];
+    unsafe { gdal_sys::OGR_L_SetIgnoredFields(layer.c_layer(), c_ptrs.as_mut_ptr()) };
+
+    let params = ReadParams {
+        columns: Some(&["a"]),
+        ..Default::default()
+    };
+    let df = df_from_layer(&mut layer, Some(params)).unwrap();
+    assert!(df.column("b").is_err());
+    assert_eq!(df.column("a").unwrap().i32().unwrap().get(0), Some(1));
+    let geometry = df
+        .column("geometry")
+        .unwrap()
+        .binary()
+        .unwrap()
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        gdal::vector::Geometry::from_wkb(geometry)
+            .unwrap()
+            .wkt()
+            .unwrap(),
+        "POINT (1 2)"
+    );
+
+    let is_ignored = |name: &str| {
+        let c_name = std::ffi::CString::new(name).unwrap();
+        unsafe {
+            let c_defn = gdal_sys::OGR_L_GetLayerDefn(layer.c_layer());
+            let idx = gdal_sys::OGR_FD_GetFieldIndex(c_defn, c_name.as_ptr());
+            gdal_sys::OGR_Fld_IsIgnored(gdal_sys::OGR_FD_GetFieldDefn(c_defn, idx)) != 0
+        }
+    };
+    assert!(!is_ignored("a"));
+    assert!(!is_ignored("b"));
+    assert!(is_ignored("c"));
+}
+
+#[test]
+fn test_snap_to_grid() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"LineString","coordinates":[[1.26,2.74],[3.1,0.3]]}}]}"#;
+    let params = ReadParams {
+        snap_to_grid: Some(0.5),
+        geometry_format: GeometryFormat::WKT,
+        ..Default::default()
+    };
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    assert_eq!(
+        df.column("geometry").unwrap().utf8().unwrap().get(0),
+        Some("LINESTRING (1.5 2.5,3 0.5)")
+    );
+
+    let df = DataFrame::new(vec![Series::new("geometry", &["POINT (1.26 2.74)"])]).unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.snap_to_grid = Some(0.5);
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)).unwrap();
+    let read_params = ReadParams {
+        geometry_format: GeometryFormat::WKT,
+        ..Default::default()
+    };
+    let roundtrip = df_from_bytes(&bytes, None, Some(read_params)).unwrap();
+    assert_eq!(
+        roundtrip.column("geometry").unwrap().utf8().unwrap().get(0),
+        Some("POINT (1.5 2.5)")
+    );
+
+    for grid_size in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let params = ReadParams {
+            snap_to_grid: Some(grid_size),
+            ..Default::default()
+        };
+        assert!(matches!(
+            df_from_bytes(geojson.as_bytes(), None, Some(params)),
+            Err(Error::InvalidLength {
+                param: "snap_to_grid",
+                ..
+            })
+        ));
+
+        let mut params = WriteParams::default();
+        params.geometry_format = GeometryFormat::WKT;
+        params.snap_to_grid = Some(grid_size);
+        assert!(matches!(
+            gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)),
+            Err(Error::InvalidLength {
+                param: "snap_to_grid",
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn test_segmentize_max_length() {
+    use crate::geom::vertex_count;
+
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"LineString","coordinates":[[0,0],[10,0]]}}]}"#;
+    let params = ReadParams {
+        segmentize_max_length: Some(1.0),
+        ..Default::default()
+    };
+    let df = df_from_bytes(geojson.as_bytes(), None, Some(params)).unwrap();
+    let geometry = df
+        .column("geometry")
+        .unwrap()
+        .binary()
+        .unwrap()
+        .get(0)
+        .unwrap();
+    let geometry = gdal::vector::Geometry::from_wkb(geometry).unwrap();
+    assert!(vertex_count(&geometry) >= 11);
+
+    let df = DataFrame::new(vec![Series::new("geometry", &["LINESTRING (0 0,10 0)"])]).unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.segmentize_max_length = Some(1.0);
+    let bytes = gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)).unwrap();
+    let roundtrip = df_from_bytes(&bytes, None, None).unwrap();
+    let geometry = roundtrip
+        .column("geometry")
+        .unwrap()
+        .binary()
+        .unwrap()
+        .get(0)
+        .unwrap();
+    assert!(vertex_count(&gdal::vector::Geometry::from_wkb(geometry).unwrap()) >= 11);
+
+    for max_length in [0.0, -1.0, f64::NAN] {
+        let params = ReadParams {
+            segmentize_max_length: Some(max_length),
+            ..Default::default()
+        };
+        assert!(matches!(
+            df_from_bytes(geojson.as_bytes(), None, Some(params)),
+            Err(Error::InvalidLength {
+                param: "segmentize_max_length",
+                ..
+            })
+        ));
+
+        let mut params = WriteParams::default();
+        params.geometry_format = GeometryFormat::WKT;
+        params.segmentize_max_length = Some(max_length);
+        assert!(matches!(
+            gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)),
+            Err(Error::InvalidLength {
+                param: "segmentize_max_length",
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn test_spatial_filter() {
+    // Only the western stations, of the 86 in the shapefile
+    let bbox = gdal::vector::Geometry::from_wkt(
+        "POLYGON ((-77.3 38.7, -77.0 38.7, -77.0 39.2, -77.3 39.2, -77.3 38.7))",
+    )
+    .unwrap();
+    let assert_western = |df: &DataFrame| {
+        assert_eq!(df.height(), 61);
+        for wkb in df.column("geometry").unwrap().binary().unwrap().into_iter() {
+            let (x, _, _) = gdal::vector::Geometry::from_wkb(wkb.unwrap())
+                .unwrap()
+                .get_point(0);
+            assert!(x < -77.0);
+        }
+    };
+
+    let mut params = ReadParams::default();
+    params.spatial_filter = Some(&bbox);
+    let df = df_from_resource("test_data/stations.shp", Some(params)).unwrap();
+    assert_western(&df);
+
+    let mut params = ReadParams::default();
+    params.spatial_filter = Some(&bbox);
+    let df = df_from_sql(
+        "test_data/stations.shp",
+        "SELECT * FROM stations",
+        gdal::vector::sql::Dialect::DEFAULT,
+        Some(params),
+    )
+    .unwrap();
+    assert_western(&df);
+
+    let df = df_from_sql(
+        "test_data/stations.shp",
+        "SELECT * FROM stations",
+        gdal::vector::sql::Dialect::DEFAULT,
+        None,
+    )
+    .unwrap();
+    assert_eq!(df.height(), 86);
+}
+
+#[test]
+fn test_reproject_on_write() {
+    let df = DataFrame::new(vec![Series::new("geometry", &["POINT (1 2)"])]).unwrap();
+    let wgs84 = gdal::spatial_ref::SpatialRef::from_epsg(4326).unwrap();
+    unsafe {
+        gdal_sys::OSRSetAxisMappingStrategy(
+            wgs84.to_c_hsrs(),
+            gdal_sys::OSRAxisMappingStrategy::OAMS_TRADITIONAL_GIS_ORDER,
+        )
+    };
+    let web_mercator = gdal::spatial_ref::SpatialRef::from_epsg(3857).unwrap();
+
+    let driver = gdal::DriverManager::get_driver_by_name("Memory").unwrap();
+    let mut dataset = driver.create_vector_only("").unwrap();
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.source_srs = Some(&wgs84);
+    params.target_srs = Some(&web_mercator);
+    let mut layer = gdal_layer_from_df(&df, &mut dataset, Some(params)).unwrap();
+    assert_eq!(layer.spatial_ref().unwrap().auth_code().unwrap(), 3857);
+
+    let mut read_params = ReadParams::default();
+    read_params.geometry_format = GeometryFormat::WKT;
+    let roundtrip = df_from_layer(&mut layer, Some(read_params)).unwrap();
+    let (x, y, _) = gdal::vector::Geometry::from_wkt(
+        roundtrip
+            .column("geometry")
+            .unwrap()
+            .utf8()
+            .unwrap()
+            .get(0)
+            .unwrap(),
+    )
+    .unwrap()
+    .get_point(0);
+    assert!((x - 111_319.49).abs() < 0.01, "{}", x);
+    assert!((y - 222_684.21).abs() < 0.01, "{}", y);
+
+    let mut params = WriteParams::default();
+    params.geometry_format = GeometryFormat::WKT;
+    params.target_srs = Some(&web_mercator);
+    assert!(matches!(
+        gdal_bytes_from_df(&df, &VectorDriver::GeoJson, Some(params)),
+        Err(Error::MissingSourceSrs)
+    ));
+}
+
+#[test]
+fn test_optimize_for_storage() {
+    let wkt = Series::new(
+        "geometry",
+        &[
+            "POINT (10 10)",
+            "POINT (0 0)",
+            "POINT (10 0)",
+            "POINT (0 10)",
+        ],
+    );
+    let df = DataFrame::new(vec![
+        Series::new("name", &["a", "b", "c", "d"]),
+        Series::new("kind", &["x", "y", "x", "x"]),
+        Series::new("population", &[1i64, 300, -5, 2]),
+        Series::new("count", &[1u32, 2, 3, 4]),
+        crate::geom::wkt_to_wkb(&wkt).unwrap(),
+    ])
+    .unwrap();
+
+    let optimized = optimize_for_storage(&df, GeometryColumnSpec::default()).unwrap();
+    let dtypes = optimized.dtypes();
+    assert_eq!(dtypes[0], DataType::Utf8);
+    assert!(matches!(dtypes[1], DataType::Categorical(_)));
+    assert_eq!(
+        dtypes[2..],
+        [DataType::Int16, DataType::UInt8, DataType::Binary]
+    );
+
+    // Rows are reordered along the curve, but each row's values are kept together
+    assert_eq!(optimized.height(), df.height());
+    let restored = optimized.sort(["name"], false).unwrap();
+    for name in ["kind", "population", "count"] {
+        let column = restored
+            .column(name)
+            .unwrap()
+            .cast(df.column(name).unwrap().dtype())
+            .unwrap();
+        assert!(column.series_equal(df.column(name).unwrap()), "{}", name);
+    }
+    assert!(restored
+        .column("geometry")
+        .unwrap()
+        .series_equal(df.column("geometry").unwrap()));
+}
+
+#[test]
+fn test_driver_unavailable() {
+    match driver_by_name("NoSuchDriver") {
+        Err(Error::DriverUnavailable { driver, hint }) => {
+            assert_eq!(driver, "NoSuchDriver");
+            assert!(hint.contains("`NoSuchDriver`"));
+        }
+        other => panic!(
+            "expected DriverUnavailable, got {:?}",
+            other.map(|d| d.short_name())
+        ),
+    }
+    assert!(driver_by_name("GeoJSON").is_ok());
+}
+
+#[test]
+fn test_spreadsheet_options() {
+    let read = |options: SpreadsheetOptions| {
+        let mut params = ReadParams::default();
+        params.spreadsheet_options = Some(options);
+        df_from_resource("test_data/stations.ods", Some(params)).unwrap()
+    };
+    let utf8 = |df: &DataFrame, name: &str| -> Vec<Option<String>> {
+        df.column(name)
+            .unwrap()
+            .utf8()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.map(str::to_owned))
+            .collect()
+    };
+
+    // The first sheet is read by default, and its header row is detected since the second row holds a number
+    let df = read(SpreadsheetOptions::default());
+    assert_eq!(df.height(), 2);
+    assert_eq!(
+        utf8(&df, "name"),
+        [Some("Alpha".to_owned()), Some("Bravo".to_owned())]
+    );
+    let elevation = df.column("elevation").unwrap();
+    assert!(elevation.dtype().is_numeric());
+    assert_eq!(
+        elevation
+            .cast(&DataType::Int64)
+            .unwrap()
+            .i64()
+            .unwrap()
+            .get(1),
+        Some(20)
+    );
+
+    let df = read(SpreadsheetOptions {
+        field_types: SpreadsheetFieldTypes::String,
+        ..Default::default()
+    });
+    assert_eq!(
+        utf8(&df, "elevation"),
+        [Some("10".to_owned()), Some("20".to_owned())]
+    );
+
+    let df = read(SpreadsheetOptions {
+        headers: SpreadsheetHeaders::Disable,
+        ..Default::default()
+    });
+    assert_eq!(df.height(), 3);
+    assert_eq!(utf8(&df, "Field1")[0].as_deref(), Some("name"));
+
+    // Every cell of the second sheet is a string, so its header row is only used when forced
+    let df = read(SpreadsheetOptions {
+        sheet_name: Some("Codes"),
+        ..Default::default()
+    });
+    assert_eq!(df.height(), 3);
+    assert_eq!(utf8(&df, "Field1")[0].as_deref(), Some("code"));
+
+    let df = read(SpreadsheetOptions {
+        sheet_name: Some("Codes"),
+        headers: SpreadsheetHeaders::Force,
+        ..Default::default()
+    });
+    assert_eq!(
+        utf8(&df, "code"),
+        [Some("A".to_owned()), Some("B".to_owned())]
+    );
+    assert_eq!(
+        utf8(&df, "label"),
+        [Some("alpha".to_owned()), Some("bravo".to_owned())]
+    );
+}
+
+#[test]
+fn test_null_fid() {
+    let geometry = gdal::vector::Geometry::from_wkt("POINT (1 2)")
+        .unwrap()
+        .wkb()
+        .unwrap();
+    let mut geometry = [Some(geometry.clone()), Some(geometry)]
+        .into_iter()
+        .collect::<BinaryChunked>()
+        .into_series();
+    geometry.rename("geometry");
+    let df = DataFrame::new(vec![
+        Series::new("fid", &[1i64, 2]),
+        Series::new("name", &["a", "b"]),
+        geometry,
+    ])
+    .unwrap();
+
+    let path = "/vsimem/polars_gdal/test_null_fid.gpkg";
+    let mut params = crate::WriteParams::default();
+    params.fid_column_name = Some("fid");
+    let dataset = gdal_resource_from_df(&df, &VectorDriver::Gpkg, path, Some(params)).unwrap();
+
+    // OGR reports a FID of -1 as no FID at all
+    let table = dataset.layer(0).unwrap().name();
+    dataset
+        .execute_sql(
+            &format!("UPDATE \"{}\" SET fid = -1 WHERE name = 'b'", table),
+            None,
+            gdal::vector::sql::Dialect::DEFAULT,
+        )
+        .unwrap();
+    drop(dataset);
+
+    let mut params = crate::ReadParams::default();
+    params.fid_column_name = Some("fid");
+    let roundtrip = df_from_resource(path, Some(params))
+        .unwrap()
+        .sort(["name"], false)
+        .unwrap();
+    let fids: Vec<Option<u64>> = roundtrip
+        .column("fid")
+        .unwrap()
+        .u64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(fids, vec![Some(1), None]);
+}
+
+#[test]
+fn test_write_layer_name() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+
+    // Several layers can be written to the same dataset under their own names
+    let path = "/vsimem/polars_gdal/test_write_layer_name.gpkg";
+    let driver = gdal::DriverManager::get_driver_by_name("GPKG").unwrap();
+    let mut dataset = driver.create_vector_only(path).unwrap();
+    for layer_name in ["roads", "rivers"] {
+        let params = WriteParams::builder()
+            .layer_name(layer_name)
+            .build()
+            .unwrap();
+        params
+            .with_params(|params| gdal_layer_from_df(&df, &mut dataset, Some(params)).map(|_| ()))
+            .unwrap();
+    }
+    drop(dataset);
+
+    for layer_name in ["roads", "rivers"] {
+        let params = ReadParams {
+            layer_name: Some(layer_name),
+            ..Default::default()
+        };
+        let resource = df_from_resource_full(path, Some(params)).unwrap();
+        assert_eq!(resource.layer_name, layer_name);
+        assert_eq!(resource.df.height(), 1);
+    }
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
+
+#[test]
+fn test_zip_with_several_shapefiles() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{"name":"bar"},"geometry":{"type":"Point","coordinates":[3,4]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+
+    let path = "/vsimem/polars_gdal/test_zip_with_several_shapefiles/layers.shp.zip";
+    for (layer_name, height) in [("roads", 1), ("rivers", 2)] {
+        let params = WriteParams {
+            layer_name: Some(layer_name),
+            ..Default::default()
+        };
+        let files = gdal_files_from_df(
+            &df.head(Some(height)),
+            &VectorDriver::Shapefile,
+            Some(params),
+        )
+        .unwrap();
+        for (name, data) in &files {
+            compression::write_vsi_file(&format!("/vsizip/{{{}}}/{}", path, name), data).unwrap();
+        }
+    }
+
+    // The archive is opened as a directory of shapefiles, with a layer per file
+    assert_eq!(
+        resolve_archive_path(path).unwrap(),
+        format!("/vsizip/{{{}}}", path)
+    );
+    for (layer_name, height) in [("roads", 1), ("rivers", 2)] {
+        let params = ReadParams {
+            layer_name: Some(layer_name),
+            ..Default::default()
+        };
+        let resource = df_from_resource_full(path, Some(params)).unwrap();
+        assert_eq!(resource.layer_name, layer_name);
+        assert_eq!(resource.df.height(), height);
+    }
+    assert!(df_from_resource(path, None).is_ok());
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
 
+#[test]
+fn test_layer_metadata_without_styles() {
+    let geojson = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{"name":"foo"},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let df = df_from_bytes(geojson.as_bytes(), None, None).unwrap();
+    let path = "/vsimem/polars_gdal/test_layer_metadata_without_styles.gpkg";
+    drop(gdal_resource_from_df(&df, &VectorDriver::Gpkg, path, None).unwrap());
+
+    // A GeoPackage without a `layer_styles` table isn't queried for styles, so GDAL reports no error
+    let (metadata, messages) =
+        crate::messages::capture_messages(|| layer_metadata_from_resource(path, None));
+    let metadata = metadata.unwrap();
+    assert!(messages.is_empty(), "{:?}", messages);
+    assert_eq!(metadata.display_expression, None);
+    assert!(metadata.hidden_fields.is_empty());
+    gdal::vsi::unlink_mem_file(path).unwrap();
+}
+
+#[test]
+fn test_write_attachments_with_repeated_names() {
+    let data: BinaryChunked = [
+        Some(&b"first"[..]),
+        Some(&b"second"[..]),
+        Some(&b"other"[..]),
+    ]
+    .into_iter()
+    .collect();
+    let mut data = data.into_series();
+    data.rename("data");
+    let attachments = DataFrame::new(vec![
+        Series::new("fid", &[1u64, 1, 2]),
+        Series::new("name", &["IMG_0001.jpg", "IMG_0001.jpg", "IMG_0001.jpg"]),
+        data,
+    ])
+    .unwrap();
+
+    let dir = std::env::temp_dir().join(format!(
+        "polars_gdal_test_write_attachments_with_repeated_names_{}",
+        std::process::id()
+    ));
+    let paths = write_attachments_to_dir(&attachments, &dir).unwrap();
+    let file_names: Vec<String> = paths
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        file_names,
+        vec!["1_IMG_0001.jpg", "1_1_IMG_0001.jpg", "2_IMG_0001.jpg"]
+    );
+    for (path, expected) in paths.iter().zip([&b"first"[..], b"second", b"other"]) {
+        assert_eq!(std::fs::read(path).unwrap(), expected);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
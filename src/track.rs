@@ -0,0 +1,293 @@
+use crate::{df_from_resource, geom, Error, GeometryFormat, ReadParams};
+use polars::prelude::*;
+use std::ops::Range;
+use std::path::Path;
+use std::time::Duration;
+
+/// Mean radius of the earth in meters, used for great-circle distances.
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Parameters to configure how GPS track points are turned into an analysis-ready DataFrame.
+#[derive(Debug, Clone, Copy)]
+pub struct TrackParams<'a> {
+    /// Resample the points of each track segment to this fixed time interval, interpolating their position and elevation.
+    ///
+    /// Resampled rows only keep the track and segment ids, time, elevation and geometry, since other attributes can't be interpolated.
+    /// If None, the original points are kept.
+    pub resample_interval: Option<Duration>,
+
+    /// The column holding the time of each point. Defaults to `time`, as written by the GPX driver.
+    pub time_column_name: &'a str,
+
+    /// The column to write the speed into, in meters per second. Defaults to `speed`.
+    pub speed_column_name: &'a str,
+
+    /// The column to write the heading into, in degrees clockwise from north. Defaults to `heading`.
+    pub heading_column_name: &'a str,
+}
+
+impl<'a> Default for TrackParams<'a> {
+    fn default() -> Self {
+        Self {
+            resample_interval: None,
+            time_column_name: "time",
+            speed_column_name: "speed",
+            heading_column_name: "heading",
+        }
+    }
+}
+
+/// Read the track points of a GPS track and add per-point speed and heading columns, optionally resampling the points to a fixed time interval.
+///
+/// By default the `track_points` layer is read, as created by the GPX driver. Other layers of longitude / latitude points can be read by setting
+/// `ReadParams::layer_name`. Points are always read as WKB. FIT files aren't supported by GDAL, and need to be converted to GPX first.
+///
+/// # Example
+/// ```rust # ignore
+/// use polars_gdal::{track_from_resource, TrackParams};
+/// use std::time::Duration;
+///
+/// let mut track_params = TrackParams::default();
+/// track_params.resample_interval = Some(Duration::from_secs(5));
+/// let df = track_from_resource("my_ride.gpx", None, track_params)?;
+/// ```
+pub fn track_from_resource<P: AsRef<Path>>(
+    path: P,
+    params: Option<ReadParams>,
+    track_params: TrackParams,
+) -> Result<DataFrame, Error> {
+    let mut params = params.unwrap_or_default();
+    if params.layer_name.is_none() && params.layer_index.is_none() {
+        params.layer_name = Some("track_points");
+    }
+    params.geometry_format = GeometryFormat::WKB;
+    let geometry_column_name = params.geometry_column_name.unwrap_or("geometry").to_owned();
+
+    let df = df_from_resource(path, Some(params))?;
+
+    track_from_df(&df, &geometry_column_name, track_params)
+}
+
+/// Add per-point speed and heading columns to a DataFrame of GPS track points, optionally resampling the points to a fixed time interval.
+///
+/// Points must be longitude / latitude WKB points, in time order. Points are grouped into segments by the `track_fid` and `track_seg_id` columns when present.
+pub fn track_from_df(
+    df: &DataFrame,
+    geometry_column_name: &str,
+    track_params: TrackParams,
+) -> Result<DataFrame, Error> {
+    let mut df = match track_params.resample_interval {
+        None => df.clone(),
+        Some(interval) => {
+            let track = TrackColumns::from_df(df, geometry_column_name, track_params)?;
+            let interval = (interval.as_millis() as i64).max(1);
+            let mut resampled = Resampled::default();
+            let mut start = 0;
+            while start < df.height() {
+                let segment = track.segment(start);
+                let mut end = start + 1;
+                while end < df.height() && track.segment(end) == segment {
+                    end += 1;
+                }
+                resampled.resample_segment(segment, &track, start..end, interval);
+                start = end;
+            }
+
+            let mut columns = vec![];
+            if track.track_ids.is_some() {
+                columns.push(Series::new("track_fid", &resampled.track_ids));
+            }
+            if track.segment_ids.is_some() {
+                columns.push(Series::new("track_seg_id", &resampled.segment_ids));
+            }
+            columns.push(
+                Series::new(track_params.time_column_name, &resampled.times)
+                    .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?,
+            );
+            if track.elevations.is_some() {
+                columns.push(Series::new("ele", &resampled.elevations));
+            }
+            let wkb: BinaryChunked = resampled
+                .points
+                .iter()
+                .map(|&(x, y)| Some(point_wkb(x, y)))
+                .collect();
+            let mut wkb = wkb.into_series();
+            wkb.rename(geometry_column_name);
+            columns.push(wkb);
+
+            DataFrame::new(columns)?
+        }
+    };
+
+    // Compute speed and heading between consecutive points of the same segment
+    let track = TrackColumns::from_df(&df, geometry_column_name, track_params)?;
+    let mut speeds = Vec::with_capacity(df.height());
+    let mut headings = Vec::with_capacity(df.height());
+    for i in 0..df.height() {
+        if i == 0 || track.segment(i) != track.segment(i - 1) {
+            speeds.push(None);
+            headings.push(None);
+            continue;
+        }
+        let (from, to) = match (track.points[i - 1], track.points[i]) {
+            (Some(from), Some(to)) => (from, to),
+            _ => {
+                speeds.push(None);
+                headings.push(None);
+                continue;
+            }
+        };
+        let speed = match (track.times[i - 1], track.times[i]) {
+            (Some(t0), Some(t1)) if t1 > t0 => {
+                Some(haversine_distance(from, to) / ((t1 - t0) as f64 / 1000.0))
+            }
+            _ => None,
+        };
+        speeds.push(speed);
+        headings.push(Some(initial_bearing(from, to)));
+    }
+
+    df.with_column(Series::new(track_params.speed_column_name, speeds))?;
+    df.with_column(Series::new(track_params.heading_column_name, headings))?;
+
+    Ok(df)
+}
+
+/// The columns of a DataFrame of track points that are needed to compute motion and resample points.
+struct TrackColumns {
+    points: Vec<Option<(f64, f64)>>,
+    times: Vec<Option<i64>>,
+    elevations: Option<Vec<Option<f64>>>,
+    track_ids: Option<Vec<Option<i64>>>,
+    segment_ids: Option<Vec<Option<i64>>>,
+}
+
+impl TrackColumns {
+    fn from_df(
+        df: &DataFrame,
+        geometry_column_name: &str,
+        track_params: TrackParams,
+    ) -> Result<Self, Error> {
+        let time = df
+            .column(track_params.time_column_name)?
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?;
+
+        Ok(Self {
+            points: geom::bbox_centers(df.column(geometry_column_name)?, GeometryFormat::WKB)?,
+            times: int_values(&time)?,
+            elevations: df.column("ele").ok().map(float_values).transpose()?,
+            track_ids: df.column("track_fid").ok().map(int_values).transpose()?,
+            segment_ids: df.column("track_seg_id").ok().map(int_values).transpose()?,
+        })
+    }
+
+    /// The track and segment ids of a point.
+    fn segment(&self, i: usize) -> (Option<i64>, Option<i64>) {
+        (
+            self.track_ids.as_ref().and_then(|ids| ids[i]),
+            self.segment_ids.as_ref().and_then(|ids| ids[i]),
+        )
+    }
+}
+
+/// Track points resampled to a fixed time interval.
+#[derive(Default)]
+struct Resampled {
+    track_ids: Vec<Option<i64>>,
+    segment_ids: Vec<Option<i64>>,
+    times: Vec<i64>,
+    elevations: Vec<Option<f64>>,
+    points: Vec<(f64, f64)>,
+}
+
+impl Resampled {
+    /// Resample one segment, linearly interpolating between the surrounding points. Points without a time or position are skipped.
+    fn resample_segment(
+        &mut self,
+        segment: (Option<i64>, Option<i64>),
+        track: &TrackColumns,
+        rows: Range<usize>,
+        interval: i64,
+    ) {
+        let samples: Vec<(i64, (f64, f64), Option<f64>)> = rows
+            .filter_map(|i| {
+                let elevation = track.elevations.as_ref().and_then(|e| e[i]);
+                Some((track.times[i]?, track.points[i]?, elevation))
+            })
+            .collect();
+        let (first, last) = match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => return,
+        };
+
+        let mut next = 0;
+        let mut t = first;
+        while t <= last {
+            while next + 1 < samples.len() && samples[next + 1].0 < t {
+                next += 1;
+            }
+            let (t0, p0, e0) = samples[next];
+            let (t1, p1, e1) = samples[(next + 1).min(samples.len() - 1)];
+            let f = if t1 > t0 {
+                (t - t0) as f64 / (t1 - t0) as f64
+            } else {
+                0.0
+            };
+
+            self.track_ids.push(segment.0);
+            self.segment_ids.push(segment.1);
+            self.times.push(t);
+            self.elevations.push(match (e0, e1) {
+                (Some(e0), Some(e1)) => Some(e0 + (e1 - e0) * f),
+                _ => None,
+            });
+            self.points
+                .push((p0.0 + (p1.0 - p0.0) * f, p0.1 + (p1.1 - p0.1) * f));
+
+            t += interval;
+        }
+    }
+}
+
+/// Great-circle distance in meters between two longitude / latitude points.
+fn haversine_distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat0, lat1) = (from.1.to_radians(), to.1.to_radians());
+    let d_lat = lat1 - lat0;
+    let d_lon = (to.0 - from.0).to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2) + lat0.cos() * lat1.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+}
+
+/// Initial bearing in degrees clockwise from north when travelling between two longitude / latitude points.
+fn initial_bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat0, lat1) = (from.1.to_radians(), to.1.to_radians());
+    let d_lon = (to.0 - from.0).to_radians();
+
+    let y = d_lon.sin() * lat1.cos();
+    let x = lat0.cos() * lat1.sin() - lat0.sin() * lat1.cos() * d_lon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Encode a 2D point as little-endian WKB.
+fn point_wkb(x: f64, y: f64) -> Vec<u8> {
+    let mut wkb = Vec::with_capacity(21);
+    wkb.push(1);
+    wkb.extend_from_slice(&1u32.to_le_bytes());
+    wkb.extend_from_slice(&x.to_le_bytes());
+    wkb.extend_from_slice(&y.to_le_bytes());
+    wkb
+}
+
+fn int_values(series: &Series) -> Result<Vec<Option<i64>>, Error> {
+    Ok(series.cast(&DataType::Int64)?.i64()?.into_iter().collect())
+}
+
+fn float_values(series: &Series) -> Result<Vec<Option<f64>>, Error> {
+    Ok(series
+        .cast(&DataType::Float64)?
+        .f64()?
+        .into_iter()
+        .collect())
+}
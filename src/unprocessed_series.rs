@@ -1,4 +1,6 @@
+use crate::{Error, MixedTypePolicy};
 use gdal::vector::FieldValue as GdalValue;
+use gdal::vector::OGRFieldType;
 use polars::export::chrono;
 use polars::prelude::*;
 
@@ -6,10 +8,13 @@ use polars::prelude::*;
 pub(crate) enum GdalData {
     Value(Option<gdal::vector::FieldValue>),
     Geometry(Vec<u8>),
+    GeometryWithBbox(Vec<u8>, [f64; 4]),
     Fid(u64),
+    Time(i64),
+    Binary(Vec<u8>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub(crate) enum UnprocessedDataType {
     Integer,
     IntegerList,
@@ -21,26 +26,47 @@ pub(crate) enum UnprocessedDataType {
     RealList,
     Date,
     DateTime,
-    Null,
+    Time,
+    Binary,
     GeometryWKB,
+    GeometryWKBWithBbox,
     Fid,
 }
 
-pub(crate) fn gdal_type_to_unprocessed_type(
-    gdal_type: &Option<gdal::vector::FieldValue>,
-) -> UnprocessedDataType {
+/// The unprocessed type for a GDAL field type, or `None` if fields of that type can't be read.
+pub(crate) fn gdal_type_id_to_unprocessed_type(
+    gdal_type: OGRFieldType::Type,
+) -> Option<UnprocessedDataType> {
+    match gdal_type {
+        OGRFieldType::OFTInteger => Some(UnprocessedDataType::Integer),
+        OGRFieldType::OFTIntegerList => Some(UnprocessedDataType::IntegerList),
+        OGRFieldType::OFTInteger64 => Some(UnprocessedDataType::Integer64),
+        OGRFieldType::OFTInteger64List => Some(UnprocessedDataType::Integer64List),
+        OGRFieldType::OFTString => Some(UnprocessedDataType::String),
+        OGRFieldType::OFTStringList => Some(UnprocessedDataType::StringList),
+        OGRFieldType::OFTReal => Some(UnprocessedDataType::Real),
+        OGRFieldType::OFTRealList => Some(UnprocessedDataType::RealList),
+        OGRFieldType::OFTDate => Some(UnprocessedDataType::Date),
+        OGRFieldType::OFTDateTime => Some(UnprocessedDataType::DateTime),
+        OGRFieldType::OFTTime => Some(UnprocessedDataType::Time),
+        OGRFieldType::OFTBinary => Some(UnprocessedDataType::Binary),
+        _ => None,
+    }
+}
+
+/// A field type that `Feature::fields` skips, so that it's read by field index instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IndexedFieldKind {
+    Time,
+    Binary,
+}
+
+/// How fields of a GDAL type are read by field index, or `None` if they're read through `Feature::fields`.
+pub(crate) fn gdal_type_read_by_index(gdal_type: OGRFieldType::Type) -> Option<IndexedFieldKind> {
     match gdal_type {
-        Some(gdal::vector::FieldValue::IntegerValue(_)) => UnprocessedDataType::Integer,
-        Some(gdal::vector::FieldValue::IntegerListValue(_)) => UnprocessedDataType::IntegerList,
-        Some(gdal::vector::FieldValue::Integer64Value(_)) => UnprocessedDataType::Integer64,
-        Some(gdal::vector::FieldValue::Integer64ListValue(_)) => UnprocessedDataType::Integer64List,
-        Some(gdal::vector::FieldValue::StringValue(_)) => UnprocessedDataType::String,
-        Some(gdal::vector::FieldValue::StringListValue(_)) => UnprocessedDataType::StringList,
-        Some(gdal::vector::FieldValue::RealValue(_)) => UnprocessedDataType::Real,
-        Some(gdal::vector::FieldValue::RealListValue(_)) => UnprocessedDataType::RealList,
-        Some(gdal::vector::FieldValue::DateValue(_)) => UnprocessedDataType::Date,
-        Some(gdal::vector::FieldValue::DateTimeValue(_)) => UnprocessedDataType::DateTime,
-        None => UnprocessedDataType::Null,
+        OGRFieldType::OFTTime => Some(IndexedFieldKind::Time),
+        OGRFieldType::OFTBinary => Some(IndexedFieldKind::Binary),
+        _ => None,
     }
 }
 
@@ -52,257 +78,387 @@ pub(crate) struct UnprocessedSeries {
 }
 
 impl UnprocessedSeries {
-    pub(crate) fn process(self) -> Series {
+    /// Convert the values of a field with mixed types to a common type, following `policy`.
+    ///
+    /// Only attribute fields are converted. Values that don't match the type of the series are otherwise reported by [`Self::process`].
+    pub(crate) fn coerce_mixed_types(&mut self, policy: MixedTypePolicy) {
+        if policy == MixedTypePolicy::Error {
+            return;
+        }
+        let mut kinds = vec![];
+        for v in &self.data {
+            match v {
+                GdalData::Value(Some(value)) => match value_datatype(value) {
+                    Some(kind) if !kinds.contains(&kind) => kinds.push(kind),
+                    Some(_) => {}
+                    None => return,
+                },
+                GdalData::Value(None) => {}
+                _ => return,
+            }
+        }
+        if kinds.iter().all(|kind| *kind == self.datatype) {
+            return;
+        }
+
+        let numeric = |kind: &UnprocessedDataType| {
+            matches!(
+                kind,
+                UnprocessedDataType::Integer
+                    | UnprocessedDataType::Integer64
+                    | UnprocessedDataType::Real
+            )
+        };
+        let target = if policy == MixedTypePolicy::Coerce
+            && numeric(&self.datatype)
+            && kinds.iter().all(numeric)
+        {
+            if self.datatype == UnprocessedDataType::Real
+                || kinds.contains(&UnprocessedDataType::Real)
+            {
+                UnprocessedDataType::Real
+            } else {
+                UnprocessedDataType::Integer64
+            }
+        } else {
+            UnprocessedDataType::String
+        };
+
+        for v in self.data.iter_mut() {
+            if let GdalData::Value(Some(value)) = v {
+                *value = coerce_value(value, &target);
+            }
+        }
+        self.datatype = target;
+    }
+
+    /// Build the Polars series, failing if a value doesn't match the type of the series (eg a field whose type varies between features).
+    pub(crate) fn process(self) -> Result<Series, Error> {
+        let name = self.name;
+        if let Some(inner) = list_inner_type(&self.datatype) {
+            return list_series(&name, &self.datatype, inner, self.data);
+        }
         let mut series = if self.nullable {
             match self.datatype {
                 UnprocessedDataType::String => {
-                    let ca: Utf8Chunked = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::StringValue(val))) => Some(val),
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-string value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<Option<String>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::StringValue(val))) => Ok(Some(val)),
+                        GdalData::Value(None) => Ok(None),
+                        v => Err(v),
+                    })?;
+                    let ca: Utf8Chunked = vec.into_iter().collect();
                     ca.into_series()
                 }
                 UnprocessedDataType::Integer => {
-                    let vec: Vec<Option<i32>> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::IntegerValue(val))) => Some(val),
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-i32 value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<Option<i32>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::IntegerValue(val))) => Ok(Some(val)),
+                        GdalData::Value(None) => Ok(None),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Integer64 => {
-                    let vec: Vec<Option<i64>> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::Integer64Value(val))) => Some(val),
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-i64 value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<Option<i64>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::Integer64Value(val))) => Ok(Some(val)),
+                        GdalData::Value(None) => Ok(None),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Real => {
-                    let vec: Vec<Option<f64>> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::RealValue(val))) => Some(val),
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-f64 value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<Option<f64>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::RealValue(val))) => Ok(Some(val)),
+                        GdalData::Value(None) => Ok(None),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Date => {
-                    let vec: Vec<Option<chrono::NaiveDate>> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
+                    let vec: Vec<Option<chrono::NaiveDate>> =
+                        collect_values(&name, self.data, |v| match v {
                             GdalData::Value(Some(GdalValue::DateValue(val))) => {
-                                Some(val.naive_utc())
+                                Ok(Some(val.naive_utc()))
                             }
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-date value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
-                    let ca = DateChunked::from_naive_date_options(&self.name, vec);
+                            GdalData::Value(None) => Ok(None),
+                            v => Err(v),
+                        })?;
+                    let ca = DateChunked::from_naive_date_options(&name, vec);
                     ca.into_series()
                 }
                 UnprocessedDataType::DateTime => {
-                    let vec: Vec<Option<chrono::NaiveDateTime>> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
+                    let vec: Vec<Option<chrono::NaiveDateTime>> =
+                        collect_values(&name, self.data, |v| match v {
                             GdalData::Value(Some(GdalValue::DateTimeValue(val))) => {
-                                Some(val.naive_utc())
+                                Ok(Some(val.naive_utc()))
                             }
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-date value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
-                    let ca = DatetimeChunked::from_naive_datetime_options(&self.name, vec, TimeUnit::Nanoseconds);
+                            GdalData::Value(None) => Ok(None),
+                            v => Err(v),
+                        })?;
+                    let ca = DatetimeChunked::from_naive_datetime_options(
+                        &name,
+                        vec,
+                        TimeUnit::Nanoseconds,
+                    );
+                    ca.into_series()
+                }
+                UnprocessedDataType::Time => {
+                    let vec: Vec<Option<i64>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Time(val) => Ok(Some(val)),
+                        GdalData::Value(None) => Ok(None),
+                        v => Err(v),
+                    })?;
+                    let ca: Int64Chunked = vec.into_iter().collect();
+                    ca.into_time().into_series()
+                }
+                UnprocessedDataType::Binary => {
+                    let vec: Vec<Option<Vec<u8>>> =
+                        collect_values(&name, self.data, |v| match v {
+                            GdalData::Binary(val) => Ok(Some(val)),
+                            GdalData::Value(None) => Ok(None),
+                            v => Err(v),
+                        })?;
+                    let ca: BinaryChunked = vec.into_iter().collect();
                     ca.into_series()
                 }
                 UnprocessedDataType::GeometryWKB => {
-                    let ca: BinaryChunked = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Geometry(val) => Some(val),
-                            GdalData::Value(None) => None,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-geometry value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<Option<Vec<u8>>> =
+                        collect_values(&name, self.data, |v| match v {
+                            GdalData::Geometry(val) => Ok(Some(val)),
+                            GdalData::Value(None) => Ok(None),
+                            v => Err(v),
+                        })?;
+                    let ca: BinaryChunked = vec.into_iter().collect();
                     ca.into_series()
                 }
-                UnprocessedDataType::Null => {
-                    panic!("geopolars_gdal: Unexpected null value in {}", &self.name)
+                UnprocessedDataType::Fid => {
+                    let vec: Vec<Option<u64>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Fid(val) => Ok(Some(val)),
+                        GdalData::Value(None) => Ok(None),
+                        v => Err(v),
+                    })?;
+                    Series::from_iter(vec)
+                }
+                UnprocessedDataType::GeometryWKBWithBbox => {
+                    geometry_with_bbox_series(&name, self.data)?
                 }
-                _ => unimplemented!("geopolars_gdal: Error processing {} - Still need to implement Lists", self.name),
+                _ => return Err(Error::UnsupportedFieldType(name)),
             }
         } else {
             match self.datatype {
                 UnprocessedDataType::String => {
-                    let vec: Vec<String> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::StringValue(val))) => val,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-string value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<String> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::StringValue(val))) => Ok(val),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Integer => {
-                    let vec: Vec<i32> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::IntegerValue(val))) => val,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-i32 value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<i32> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::IntegerValue(val))) => Ok(val),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Integer64 => {
-                    let vec: Vec<i64> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::Integer64Value(val))) => val,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-i64 value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<i64> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::Integer64Value(val))) => Ok(val),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Real => {
-                    let vec: Vec<f64> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::RealValue(val))) => val,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-f64 value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<f64> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Value(Some(GdalValue::RealValue(val))) => Ok(val),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
                 UnprocessedDataType::Date => {
-                    let vec: Vec<chrono::NaiveDate> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::DateValue(val))) => val.naive_utc(),
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-date value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
-                    let ca = DateChunked::from_naive_date(&self.name, vec);
+                    let vec: Vec<chrono::NaiveDate> =
+                        collect_values(&name, self.data, |v| match v {
+                            GdalData::Value(Some(GdalValue::DateValue(val))) => Ok(val.naive_utc()),
+                            v => Err(v),
+                        })?;
+                    let ca = DateChunked::from_naive_date(&name, vec);
                     ca.into_series()
                 }
                 UnprocessedDataType::DateTime => {
-                    let vec: Vec<chrono::NaiveDateTime> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Value(Some(GdalValue::DateTimeValue(val))) => val.naive_utc(),
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-date value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
-                    let ca = DatetimeChunked::from_naive_datetime(
-                        &self.name,
-                        vec,
-                        TimeUnit::Nanoseconds,
-                    );
+                    let vec: Vec<chrono::NaiveDateTime> =
+                        collect_values(&name, self.data, |v| match v {
+                            GdalData::Value(Some(GdalValue::DateTimeValue(val))) => {
+                                Ok(val.naive_utc())
+                            }
+                            v => Err(v),
+                        })?;
+                    let ca =
+                        DatetimeChunked::from_naive_datetime(&name, vec, TimeUnit::Nanoseconds);
+                    ca.into_series()
+                }
+                UnprocessedDataType::Time => {
+                    let vec: Vec<i64> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Time(val) => Ok(val),
+                        v => Err(v),
+                    })?;
+                    Int64Chunked::from_vec(&name, vec).into_time().into_series()
+                }
+                UnprocessedDataType::Binary => {
+                    let vec: Vec<Vec<u8>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Binary(val) => Ok(val),
+                        v => Err(v),
+                    })?;
+                    let ca: BinaryChunked = vec.into_iter().collect();
                     ca.into_series()
                 }
                 UnprocessedDataType::GeometryWKB => {
-                    let ca: BinaryChunked = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Geometry(val) => val,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-geometry value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<Vec<u8>> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Geometry(val) => Ok(val),
+                        v => Err(v),
+                    })?;
+                    let ca: BinaryChunked = vec.into_iter().collect();
                     ca.into_series()
                 }
                 UnprocessedDataType::Fid => {
-                    let vec: Vec<u64> = self
-                        .data
-                        .into_iter()
-                        .map(|v| match v {
-                            GdalData::Fid(val) => val,
-                            _ => unreachable!(
-                                "geopadas_gdal: Unexpected non-u64 fid value `{:?}` in {}",
-                                &v, &self.name
-                            ),
-                        })
-                        .collect();
+                    let vec: Vec<u64> = collect_values(&name, self.data, |v| match v {
+                        GdalData::Fid(val) => Ok(val),
+                        v => Err(v),
+                    })?;
                     Series::from_iter(vec)
                 }
-                UnprocessedDataType::Null => {
-                    panic!("geopolars_gdal: Unexpected null value in {}", &self.name)
+                UnprocessedDataType::GeometryWKBWithBbox => {
+                    geometry_with_bbox_series(&name, self.data)?
                 }
-                _ => unimplemented!(
-                    "geopolars_gdal: Error processing {} - Still need to implement Lists",
-                    self.name
-                ),
+                _ => return Err(Error::UnsupportedFieldType(name)),
             }
         };
 
-        series.rename(&self.name);
+        series.rename(&name);
+
+        Ok(series)
+    }
+}
+
+/// The Polars type of the elements of a list type, or `None` for other types.
+fn list_inner_type(datatype: &UnprocessedDataType) -> Option<DataType> {
+    match datatype {
+        UnprocessedDataType::IntegerList => Some(DataType::Int32),
+        UnprocessedDataType::Integer64List => Some(DataType::Int64),
+        UnprocessedDataType::RealList => Some(DataType::Float64),
+        UnprocessedDataType::StringList => Some(DataType::Utf8),
+        _ => None,
+    }
+}
+
+/// Build a List series from the values of a list field.
+fn list_series(
+    name: &str,
+    datatype: &UnprocessedDataType,
+    inner: DataType,
+    data: Vec<GdalData>,
+) -> Result<Series, Error> {
+    let lists: Vec<Option<Series>> = collect_values(name, data, |v| match (datatype, v) {
+        (
+            UnprocessedDataType::IntegerList,
+            GdalData::Value(Some(GdalValue::IntegerListValue(val))),
+        ) => Ok(Some(Series::new("", val))),
+        (
+            UnprocessedDataType::Integer64List,
+            GdalData::Value(Some(GdalValue::Integer64ListValue(val))),
+        ) => Ok(Some(Series::new("", val))),
+        (UnprocessedDataType::RealList, GdalData::Value(Some(GdalValue::RealListValue(val)))) => {
+            Ok(Some(Series::new("", val)))
+        }
+        (
+            UnprocessedDataType::StringList,
+            GdalData::Value(Some(GdalValue::StringListValue(val))),
+        ) => Ok(Some(Series::new("", val))),
+        (_, GdalData::Value(None)) => Ok(None),
+        (_, v) => Err(v),
+    })?;
+
+    // Cast so that columns of only null or empty lists still get the field's element type
+    Ok(Series::new(name, lists).cast(&DataType::List(Box::new(inner)))?)
+}
 
-        series
+/// The unprocessed type of a single field value, or `None` for list values.
+fn value_datatype(value: &GdalValue) -> Option<UnprocessedDataType> {
+    match value {
+        GdalValue::IntegerValue(_) => Some(UnprocessedDataType::Integer),
+        GdalValue::Integer64Value(_) => Some(UnprocessedDataType::Integer64),
+        GdalValue::RealValue(_) => Some(UnprocessedDataType::Real),
+        GdalValue::StringValue(_) => Some(UnprocessedDataType::String),
+        GdalValue::DateValue(_) => Some(UnprocessedDataType::Date),
+        GdalValue::DateTimeValue(_) => Some(UnprocessedDataType::DateTime),
+        _ => None,
     }
 }
+
+/// Convert a field value to the common type of a mixed field, which is Integer64, Real or String.
+fn coerce_value(value: &GdalValue, target: &UnprocessedDataType) -> GdalValue {
+    match (target, value) {
+        (UnprocessedDataType::Integer64, GdalValue::IntegerValue(v)) => {
+            GdalValue::Integer64Value(*v as i64)
+        }
+        (UnprocessedDataType::Real, GdalValue::IntegerValue(v)) => GdalValue::RealValue(*v as f64),
+        (UnprocessedDataType::Real, GdalValue::Integer64Value(v)) => {
+            GdalValue::RealValue(*v as f64)
+        }
+        (UnprocessedDataType::String, value) => GdalValue::StringValue(match value {
+            GdalValue::IntegerValue(v) => v.to_string(),
+            GdalValue::Integer64Value(v) => v.to_string(),
+            GdalValue::RealValue(v) => v.to_string(),
+            GdalValue::StringValue(v) => v.clone(),
+            GdalValue::DateValue(v) => v.naive_utc().to_string(),
+            GdalValue::DateTimeValue(v) => v.to_rfc3339(),
+            other => format!("{:?}", other),
+        }),
+        (_, value) => value.clone(),
+    }
+}
+
+/// Convert the values of a series with `convert`, which hands back any value that doesn't match the type of the series.
+fn collect_values<T>(
+    name: &str,
+    data: Vec<GdalData>,
+    convert: impl Fn(GdalData) -> Result<T, GdalData>,
+) -> Result<Vec<T>, Error> {
+    data.into_iter()
+        .enumerate()
+        .map(|(idx, v)| {
+            convert(v).map_err(|v| Error::UnexpectedFieldValue {
+                column: name.to_owned(),
+                feature_index: idx,
+                value: format!("{:?}", v),
+            })
+        })
+        .collect()
+}
+
+/// Build a `{wkb, xmin, ymin, xmax, ymax}` struct series from geometries and their bounding boxes.
+fn geometry_with_bbox_series(name: &str, data: Vec<GdalData>) -> Result<Series, Error> {
+    let mut wkb: Vec<Option<Vec<u8>>> = Vec::with_capacity(data.len());
+    let mut bounds: [Vec<Option<f64>>; 4] = Default::default();
+    let data = collect_values(name, data, |v| match v {
+        GdalData::GeometryWithBbox(geometry, bbox) => Ok((Some(geometry), bbox.map(Some))),
+        GdalData::Value(None) => Ok((None, [None; 4])),
+        v => Err(v),
+    })?;
+    for (geometry, bbox) in data {
+        wkb.push(geometry);
+        for (bound, value) in bounds.iter_mut().zip(bbox) {
+            bound.push(value);
+        }
+    }
+
+    let mut wkb: Series = wkb.into_iter().collect::<BinaryChunked>().into_series();
+    wkb.rename("wkb");
+    let [xmin, ymin, xmax, ymax] = bounds;
+    let fields = [
+        wkb,
+        Series::new("xmin", xmin),
+        Series::new("ymin", ymin),
+        Series::new("xmax", xmax),
+        Series::new("ymax", ymax),
+    ];
+
+    Ok(StructChunked::new(name, &fields)?.into_series())
+}